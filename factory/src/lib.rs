@@ -3,6 +3,8 @@ use std::str::FromStr;
 
 use mintbase_deps::common::{
     NFTContractMetadata,
+    StoreCloneArgs,
+    StoreCloneInclude,
     StoreInitArgs,
 };
 use mintbase_deps::constants::{
@@ -12,7 +14,10 @@ use mintbase_deps::constants::{
     NO_DEPOSIT,
     YOCTO_PER_BYTE,
 };
-use mintbase_deps::interfaces::factory_self;
+use mintbase_deps::interfaces::{
+    ext_store_clone,
+    factory_self,
+};
 use mintbase_deps::logging::{
     NearJsonEvent,
     NftStoreCreateLog,
@@ -33,6 +38,7 @@ use mintbase_deps::near_sdk::{
     AccountId,
     Balance,
     Promise,
+    PromiseResult,
     PublicKey,
 };
 use mintbase_deps::serde_json;
@@ -253,14 +259,109 @@ impl MintbaseStoreFactory {
         metadata: NFTContractMetadata,
         owner_id: AccountId,
     ) -> Promise {
+        let metadata = self.assert_valid_new_store(metadata);
+        self.deploy_store(
+            env::predecessor_account_id(),
+            metadata,
+            owner_id,
+            None,
+            env::attached_deposit(),
+        )
+    }
+
+    /// `clone_store` creates a new store from the store metadata, and
+    /// initializes it with parts of the state of an existing `Store` produced
+    /// by this `Factory`. The requirements for the attached deposit are the
+    /// same as for `create_store`. Only the owner of the existing `Store` may
+    /// clone it, and will become owner of the clone. Tokens are never copied.
+    #[payable]
+    pub fn clone_store(
+        &mut self,
+        metadata: NFTContractMetadata,
+        store_id: AccountId,
+        include: StoreCloneInclude,
+    ) -> Promise {
+        let metadata = self.assert_valid_new_store(metadata);
+        let store_name = store_id
+            .as_str()
+            .strip_suffix(&format!(".{}", env::current_account_id()));
+        assert!(
+            matches!(store_name, Some(name) if self.check_contains_store(name.to_string())),
+            "Not a store of this factory"
+        );
+
+        ext_store_clone::get_clone_args(include, store_id, NO_DEPOSIT, gas::GET_CLONE_ARGS).then(
+            factory_self::on_clone_args(
+                env::predecessor_account_id(),
+                metadata,
+                env::attached_deposit().into(),
+                env::current_account_id(),
+                NO_DEPOSIT,
+                gas::ON_CLONE_ARGS_CALLBACK,
+            ),
+        )
+    }
+
+    /// Handle callback of retrieving the state of a `Store` to clone. Deploys
+    /// the clone if the state could be retrieved and the store creator owns
+    /// the cloned `Store`, otherwise refunds the store creator.
+    #[private]
+    pub fn on_clone_args(
+        &mut self,
+        store_creator_id: AccountId,
+        metadata: NFTContractMetadata,
+        attached_deposit: U128,
+    ) -> Promise {
+        let clone_args = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                serde_json::from_slice::<StoreCloneArgs>(&value).ok()
+            },
+            _ => None,
+        };
+
+        match clone_args {
+            Some(clone_args) if clone_args.owner_id == store_creator_id => self.deploy_store(
+                store_creator_id.clone(),
+                metadata,
+                store_creator_id,
+                Some(clone_args),
+                attached_deposit.into(),
+            ),
+            _ => {
+                env::log_str("failed store cloning");
+                Promise::new(store_creator_id).transfer(attached_deposit.into())
+            },
+        }
+    }
+}
+
+impl MintbaseStoreFactory {
+    /// Checks deposit and name of a new `Store`, and validates its metadata.
+    fn assert_valid_new_store(
+        &self,
+        metadata: NFTContractMetadata,
+    ) -> NFTContractMetadata {
         self.assert_sufficient_attached_deposit();
         self.assert_no_store_with_id(metadata.name.clone());
         assert_ne!(&metadata.name, "market"); // marketplace lives here
         assert_ne!(&metadata.name, "loan"); // loan lives here
-        let metadata = NFTContractMetadata::new(metadata);
+        NFTContractMetadata::new(metadata)
+    }
+
+    /// Deploys a `Store` with validated metadata and handles the outcome in
+    /// `on_create`.
+    fn deploy_store(
+        &self,
+        store_creator_id: AccountId,
+        metadata: NFTContractMetadata,
+        owner_id: AccountId,
+        clone_args: Option<StoreCloneArgs>,
+        attached_deposit: Balance,
+    ) -> Promise {
         let init_args = serde_json::to_vec(&StoreInitArgs {
             metadata: metadata.clone(),
             owner_id: owner_id.clone(),
+            clone_args,
         })
         .unwrap();
         // StoreId is only the subaccount. store_account_id is the full near qualified name.
@@ -276,11 +377,11 @@ impl MintbaseStoreFactory {
             .deploy_contract(include_bytes!("../../wasm/store.wasm").to_vec())
             .function_call("new".to_string(), init_args, 0, gas::CREATE_STORE)
             .then(factory_self::on_create(
-                env::predecessor_account_id(),
+                store_creator_id,
                 metadata,
                 owner_id,
                 store_account_id,
-                env::attached_deposit().into(),
+                attached_deposit.into(),
                 env::current_account_id(),
                 NO_DEPOSIT,
                 gas::ON_CREATE_CALLBACK,
@@ -3,6 +3,7 @@ use mintbase_deps::near_sdk::borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+use mintbase_deps::near_sdk::json_types::U64;
 use mintbase_deps::near_sdk::{
     self,
     env,
@@ -10,6 +11,7 @@ use mintbase_deps::near_sdk::{
     AccountId,
     PromiseOrValue,
 };
+use mintbase_deps::serde_json;
 
 // ----------------------------- smart contract ----------------------------- //
 #[near_bindgen]
@@ -38,7 +40,7 @@ impl HelperWasm {
         previous_owner_id: AccountId,
         token_id: String,
         msg: String,
-    ) -> PromiseOrValue<bool> {
+    ) -> PromiseOrValue<serde_json::Value> {
         env::log_str(
             format!(
                 "in nft_on_transfer; sender_id={}, previous_owner_id={}, token_id={}, msg={}",
@@ -46,10 +48,54 @@ impl HelperWasm {
             )
             .as_str(),
         );
-        match msg.as_str() {
-            "true" => PromiseOrValue::Value(true),
-            "false" => PromiseOrValue::Value(false),
-            _ => env::panic_str("unsupported msg"),
+        // `msg` is returned verbatim, such that receivers returning
+        // malformed values can be emulated, e.g. `"\"yes\""` or `"1"`
+        match serde_json::from_str::<serde_json::Value>(&msg) {
+            Ok(value) => PromiseOrValue::Value(value),
+            Err(_) => env::panic_str("unsupported msg"),
         }
     }
+
+    /// Like `nft_on_transfer`, `msg` is returned verbatim, thus should list
+    /// one boolean per token.
+    pub fn nft_on_batch_transfer(
+        &mut self,
+        sender_id: AccountId,
+        previous_owner_id: AccountId,
+        token_ids: Vec<U64>,
+        msg: String,
+    ) -> PromiseOrValue<serde_json::Value> {
+        env::log_str(
+            format!(
+                "in nft_on_batch_transfer; sender_id={}, previous_owner_id={}, token_ids={:?}, msg={}",
+                &sender_id,
+                &previous_owner_id,
+                token_ids.iter().map(|token_id| token_id.0).collect::<Vec<_>>(),
+                msg
+            )
+            .as_str(),
+        );
+        match serde_json::from_str::<serde_json::Value>(&msg) {
+            Ok(value) => PromiseOrValue::Value(value),
+            Err(_) => env::panic_str("unsupported msg"),
+        }
+    }
+
+    /// Approves all transfers, for testing transfer policy hooks.
+    pub fn nft_check_transfer(
+        &self,
+        token_id: U64,
+        sender_id: AccountId,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+    ) -> bool {
+        env::log_str(
+            format!(
+                "in nft_check_transfer; token_id={}, sender_id={}, owner_id={}, receiver_id={}",
+                token_id.0, &sender_id, &owner_id, &receiver_id
+            )
+            .as_str(),
+        );
+        true
+    }
 }
@@ -1,44 +1,152 @@
+pub mod attestation;
+pub mod bid_history;
+pub mod event_journal;
+pub mod fee_hook;
+pub mod gift;
 // pub mod loan;
+pub mod metadata_commitment;
+pub mod method_requirements;
+pub mod migration_certificate;
+pub mod mint_ban;
+pub mod mint_batch;
+pub mod mint_pool;
+pub mod mint_request;
+pub mod mint_session;
+pub mod mint_voucher;
+pub mod mint_window;
+pub mod minter_application;
+pub mod minter_quota;
+pub mod open_edition;
+pub mod owned_tokens;
 // pub mod owner;
 pub mod payouts;
+pub mod price_index;
+pub mod price_oracle;
+pub mod rate_limit;
 pub mod safe_fraction;
 pub mod sale_args;
+pub mod sale_phase;
 // pub mod storage;
+pub mod storage_deposit;
+pub mod store_clone_args;
+pub mod store_error;
 pub mod store_init_args;
 pub mod store_metadata;
+pub mod store_role;
+pub mod supported_standard;
+pub mod sweep_bounty;
 pub mod time;
 // pub mod token;
 pub mod token_key;
 pub mod token_listing;
 pub mod token_metadata;
 pub mod token_offer;
+pub mod token_reservation;
+pub mod transfer_policy;
+pub mod transfer_spec;
+pub mod upgrading_map;
 
+pub use attestation::Attestation;
+pub use bid_history::{
+    AuctionSettlement,
+    BidHistory,
+};
+pub use event_journal::{
+    EventJournal,
+    JournaledEvent,
+};
+pub use fee_hook::FeeHookConfig;
+pub use gift::{
+    Gift,
+    GiftClaim,
+};
 // pub use loan::Loan;
+pub use metadata_commitment::MetadataCommitment;
+pub use method_requirements::{
+    MethodRequirement,
+    MethodRequirements,
+};
+pub use migration_certificate::MigrationCertificate;
+pub use mint_ban::MintBan;
+pub use mint_batch::MintBatch;
+pub use mint_pool::MintPool;
+pub use mint_request::MintRequest;
+pub use mint_session::MintSession;
+pub use mint_voucher::MintVoucher;
+pub use mint_window::MintWindow;
+pub use minter_application::MinterApplication;
+pub use minter_quota::MinterQuota;
+pub use open_edition::OpenEdition;
+pub use owned_tokens::{
+    OwnedTokens,
+    TokensPerOwner,
+};
 // pub use owner::Owner;
 pub use payouts::{
+    BatchRoyaltyArgs,
+    ClaimableBalance,
     NewSplitOwner,
     OwnershipFractions,
     Payout,
+    PayoutArgsError,
     Royalty,
     RoyaltyArgs,
     SplitBetween,
+    SplitBetweenArgs,
     SplitBetweenUnparsed,
     SplitOwners,
+    UncheckedRoyaltyArgs,
+    UncheckedSplitBetween,
+    ValidationReport,
+    VestingSchedule,
+};
+pub use price_index::{
+    ListingPrice,
+    PriceIndex,
+};
+pub use price_oracle::{
+    ListingPriceIn,
+    OracleRate,
+    PriceOracleConfig,
+};
+pub use rate_limit::{
+    Activity,
+    ActivityWindow,
+    RateLimitError,
+    RateLimits,
 };
 pub use safe_fraction::{
     MultipliedSafeFraction,
     SafeFraction,
 };
-pub use sale_args::SaleArgs;
+pub use sale_args::{
+    ListingIntent,
+    SaleArgs,
+};
+pub use sale_phase::{
+    SalePhase,
+    SalePhaseKind,
+    SalePhases,
+};
 // pub use storage::{
 //     StorageCosts,
 //     StorageCostsMarket,
 // };
+pub use storage_deposit::StorageDeposit;
+pub use store_clone_args::{
+    StoreCloneArgs,
+    StoreCloneConfig,
+    StoreCloneInclude,
+};
+pub use store_error::StoreError;
 pub use store_init_args::StoreInitArgs;
 pub use store_metadata::{
     NFTContractMetadata,
     NonFungibleContractMetadata,
 };
+pub use store_role::StoreRole;
+pub use supported_standard::SupportedStandard;
+pub use sweep_bounty::SweepBountyPool;
 pub use time::{
     NearTime,
     TimeUnit,
@@ -50,8 +158,17 @@ pub use time::{
 pub use token_key::TokenKey;
 pub use token_listing::TokenListing;
 pub use token_metadata::{
+    LegacyTokenMetadata,
+    MediaHints,
     TokenMetadata,
     TokenMetadataCompliant,
 };
 pub use token_offer::TokenOffer;
+pub use token_reservation::TokenReservation;
+pub use transfer_policy::TransferPolicy;
+pub use transfer_spec::TransferSpec;
+pub use upgrading_map::{
+    Upgrade,
+    UpgradingMap,
+};
 // pub use store_metadata::{};
@@ -0,0 +1,29 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+use crate::common::time::NearTime;
+
+/// A claim about a token made by a registered attestor (e.g. a gallery or an
+/// authenticator), giving physical-art-backed tokens an on-chain
+/// authentication trail.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct Attestation {
+    /// The account that made this attestation.
+    pub attestor: AccountId,
+    /// Hash of the off-chain claim document.
+    pub claim_hash: Base64VecU8,
+    /// URL to the off-chain claim document.
+    pub uri: String,
+    /// When the attestation was made.
+    pub timestamp: NearTime,
+}
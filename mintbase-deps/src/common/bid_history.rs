@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+use crate::common::time::{
+    now,
+    NearTime,
+};
+use crate::common::TokenOffer;
+use crate::constants::MAX_BID_HISTORY;
+
+/// Bounded history of the bids on an auction listing, and its final
+/// settlement. Provenance and dispute resolution should not depend on
+/// scraping receipts, so the Marketplace keeps this per listing.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct BidHistory {
+    /// The most recent bids, oldest first. At most `MAX_BID_HISTORY` bids
+    /// are retained.
+    pub bids: Vec<TokenOffer>,
+    /// Total number of bids ever recorded. Bid indices are absolute, such
+    /// that pruning old bids does not shift pagination.
+    pub num_bids: u64,
+    /// Set once the auction has been settled.
+    pub settlement: Option<AuctionSettlement>,
+}
+
+/// Final record of a settled auction.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct AuctionSettlement {
+    /// The winning bid.
+    pub bid: TokenOffer,
+    /// How the proceeds were paid out.
+    pub payout: HashMap<AccountId, U128>,
+    /// When the auction was settled.
+    pub timestamp: NearTime,
+}
+
+impl BidHistory {
+    pub fn new() -> Self {
+        Self {
+            bids: vec![],
+            num_bids: 0,
+            settlement: None,
+        }
+    }
+
+    /// Record a bid, pruning the oldest bid once `MAX_BID_HISTORY` is
+    /// exceeded.
+    pub fn record_bid(
+        &mut self,
+        bid: TokenOffer,
+    ) {
+        assert!(self.settlement.is_none(), "auction already settled");
+        self.bids.push(bid);
+        self.num_bids += 1;
+        if self.bids.len() as u64 > MAX_BID_HISTORY {
+            self.bids.remove(0);
+        }
+    }
+
+    /// Record the final settlement of the auction.
+    pub fn settle(
+        &mut self,
+        bid: TokenOffer,
+        payout: HashMap<AccountId, U128>,
+    ) {
+        assert!(self.settlement.is_none(), "auction already settled");
+        self.settlement = Some(AuctionSettlement {
+            bid,
+            payout,
+            timestamp: now(),
+        });
+    }
+
+    /// Get up to `limit` bids, starting from the absolute bid index `from`.
+    /// Bids that have already been pruned are skipped.
+    pub fn get_bids(
+        &self,
+        from: u64,
+        limit: u64,
+    ) -> Vec<TokenOffer> {
+        let first_retained = self.num_bids - self.bids.len() as u64;
+        let skip = from.saturating_sub(first_retained);
+        self.bids
+            .iter()
+            .skip(skip as usize)
+            .take(limit as usize)
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for BidHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
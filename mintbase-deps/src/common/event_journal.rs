@@ -0,0 +1,125 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::collections::LookupMap;
+use near_sdk::env;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// Storage key of the `EventJournal` itself. The journal lives outside of
+/// the contract state struct, such that the logging functions can append
+/// to it without access to the contract.
+const JOURNAL_KEY: &[u8] = b"event_journal";
+/// Storage prefix of the journaled events, keyed by sequence number.
+const ENTRIES_PREFIX: &[u8] = b"event_journal:";
+
+/// Maximum number of stale events removed per call when the capacity of the
+/// journal has been reduced, bounding the gas costs of pruning.
+const MAX_PRUNE_PER_CALL: u64 = 200;
+
+/// A bounded log of the most recent events emitted by a contract, allowing
+/// a restarting indexer to catch up without access to an archival node.
+/// Once `capacity` events have been journaled, each new event replaces the
+/// oldest one.
+///
+/// The journal is disabled unless enabled via `EventJournal::configure`.
+#[derive(Debug, Deserialize, Serialize, BorshDeserialize, BorshSerialize)]
+pub struct EventJournal {
+    /// Sequence number of the next event to be journaled.
+    pub next_seq: u64,
+    /// Maximum number of journaled events. Zero if the journal is disabled.
+    pub capacity: u64,
+    /// Sequence number of the oldest event still in storage. Events before
+    /// `first_seq` are stale and removed gradually.
+    pub oldest_seq: u64,
+}
+
+/// An event as kept in the `EventJournal`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct JournaledEvent {
+    pub seq: u64,
+    /// The event as logged, including the `EVENT_JSON:` prefix.
+    pub event: String,
+}
+
+impl EventJournal {
+    /// Load the journal, if it has ever been enabled.
+    pub fn load() -> Option<Self> {
+        env::storage_read(JOURNAL_KEY).map(|bytes| Self::try_from_slice(&bytes).unwrap())
+    }
+
+    /// Set the `capacity` of the journal, disabling it if `capacity` is
+    /// zero. Events that exceed a reduced capacity are removed over this and
+    /// the following calls.
+    pub fn configure(capacity: u64) -> Self {
+        let mut journal = Self::load().unwrap_or(Self {
+            next_seq: 0,
+            capacity: 0,
+            oldest_seq: 0,
+        });
+        journal.capacity = capacity;
+        journal.prune(MAX_PRUNE_PER_CALL);
+        journal.save();
+        journal
+    }
+
+    /// Journal `event`, removing the oldest event if the journal is full.
+    /// Does nothing if the journal is disabled.
+    pub fn append(
+        &mut self,
+        event: &str,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        Self::entries().insert(&self.next_seq, &event.to_string());
+        self.next_seq += 1;
+        // removing one more event than was added drains stale events
+        self.prune(2);
+        self.save();
+    }
+
+    /// Up to `limit` journaled events, starting with sequence number `seq`
+    /// or the oldest retained event, whichever is more recent.
+    pub fn events_since(
+        &self,
+        seq: u64,
+        limit: u64,
+    ) -> Vec<JournaledEvent> {
+        let entries = Self::entries();
+        let end = self.next_seq.min(seq.saturating_add(limit));
+        (seq.max(self.first_seq())..end)
+            .filter_map(|seq| entries.get(&seq).map(|event| JournaledEvent { seq, event }))
+            .collect()
+    }
+
+    /// Sequence number of the oldest retained event.
+    pub fn first_seq(&self) -> u64 {
+        self.next_seq.saturating_sub(self.capacity)
+    }
+
+    /// Remove up to `max` stale events.
+    fn prune(
+        &mut self,
+        max: u64,
+    ) {
+        let mut entries = Self::entries();
+        let end = self.first_seq().min(self.oldest_seq + max);
+        (self.oldest_seq..end).for_each(|seq| {
+            entries.remove(&seq);
+        });
+        self.oldest_seq = self.oldest_seq.max(end);
+    }
+
+    fn save(&self) {
+        env::storage_write(JOURNAL_KEY, &self.try_to_vec().unwrap());
+    }
+
+    fn entries() -> LookupMap<u64, String> {
+        LookupMap::new(ENTRIES_PREFIX.to_vec())
+    }
+}
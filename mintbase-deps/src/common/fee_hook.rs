@@ -0,0 +1,45 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+use crate::common::SafeFraction;
+use crate::constants::FEE_HOOK_UPPER_LIMIT;
+
+/// A contract that the `Store` consults on `nft_transfer_payout` for
+/// additional fee legs, allowing dynamic fees without redeploying the
+/// `Store`. The fee legs are taken pro rata from the other payout receivers.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct FeeHookConfig {
+    /// The contract implementing `nft_fee_legs`.
+    pub hook_id: AccountId,
+    /// The fee legs in total may not exceed this fraction of the sale
+    /// balance, otherwise they are dropped.
+    pub max_fee: SafeFraction,
+}
+
+impl FeeHookConfig {
+    /// Validates that `max_fee` is within the accepted range.
+    pub fn new(
+        hook_id: AccountId,
+        max_fee: u32,
+    ) -> Self {
+        assert!(
+            max_fee <= FEE_HOOK_UPPER_LIMIT,
+            "max_fee: {} must be <= {}",
+            max_fee,
+            FEE_HOOK_UPPER_LIMIT
+        );
+        Self {
+            hook_id,
+            max_fee: SafeFraction::new(max_fee),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::{
+    AccountId,
+    PublicKey,
+};
+
+#[cfg(feature = "wasm")]
+use crate::common::mint_voucher::{
+    tagged_message,
+    verify_ed25519,
+};
+
+/// A token held in escrow by the `Store` until it is claimed with a
+/// signature by `claim_pk`, e.g. via a link that contains the private key.
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct Gift {
+    /// The previous owner of the token, who may cancel the gift.
+    pub sender_id: AccountId,
+    /// The one-time ed25519 key with which the gift is claimed.
+    pub claim_pk: PublicKey,
+    /// Attached to the transfer events of the gift.
+    pub memo: Option<String>,
+}
+
+/// The claim of a gifted token, which is signed by the private key of the
+/// gift's `claim_pk`. As the claim includes `receiver_id`, a signature
+/// observed on-chain cannot be reused to claim the token for another
+/// account.
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct GiftClaim {
+    /// The `Store` on which the token is gifted.
+    pub store_id: AccountId,
+    /// The gifted token.
+    pub token_id: U64,
+    /// The account to receive the token.
+    pub receiver_id: AccountId,
+}
+
+impl GiftClaim {
+    /// Distinguishes signed gift claims from other signed messages.
+    pub const MESSAGE_TAG: &'static [u8] = b"mintbase:gift_claim:";
+
+    /// The message that is signed with the claim key, see `tagged_message`.
+    #[cfg(feature = "wasm")]
+    pub fn message(&self) -> Vec<u8> {
+        tagged_message(Self::MESSAGE_TAG, &self.try_to_vec().unwrap())
+    }
+
+    /// Check that `signature` is a valid signature of this claim by
+    /// `public_key`. Only ed25519 keys are supported.
+    #[cfg(feature = "wasm")]
+    pub fn verify(
+        &self,
+        public_key: &PublicKey,
+        signature: &[u8],
+    ) -> bool {
+        verify_ed25519(public_key, &self.message(), signature)
+    }
+}
@@ -0,0 +1,27 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+/// Commitment to the real metadata of tokens that have been minted with
+/// placeholder metadata, until the minter reveals it via
+/// `reveal_metadata`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct MetadataCommitment {
+    /// The minter that may reveal the metadata.
+    pub minter: AccountId,
+    /// Sha256 hash of the real metadata, as returned by `hash_metadata`.
+    pub metadata_hash: Base64VecU8,
+    /// First token that shares the placeholder metadata.
+    pub first_token_id: u64,
+    /// Last token that shares the placeholder metadata.
+    pub last_token_id: u64,
+}
@@ -0,0 +1,63 @@
+use near_sdk::json_types::{
+    U128,
+    U64,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::{
+    Balance,
+    Gas,
+};
+
+/// Storage costs and per-method requirements of a `Store`, allowing SDKs to
+/// compute deposits and gas instead of hard-coding them.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MethodRequirements {
+    /// The Near-denominated price-per-byte of storage.
+    pub storage_price_per_byte: U128,
+    /// Storage stake for a single royalty, approval, split or
+    /// `tokens_per_owner` entry.
+    pub storage_common: U128,
+    /// Storage stake for a token without any metadata and royalties.
+    pub storage_token: U128,
+    /// Storage stake for the record created by each `nft_batch_mint` call.
+    pub storage_mint_batch: U128,
+    /// Requirements for each payable method.
+    pub methods: Vec<MethodRequirement>,
+}
+
+/// Deposit and gas requirements of a single payable method. The deposit to
+/// attach is `base_deposit + deposit_per_unit * <deposit_unit>`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MethodRequirement {
+    /// Name of the method.
+    pub method_name: String,
+    /// Deposit required independent of the arguments.
+    pub base_deposit: U128,
+    /// Deposit required for each unit described by `deposit_unit`.
+    pub deposit_per_unit: U128,
+    /// The arguments that `deposit_per_unit` scales with, if any.
+    pub deposit_unit: Option<String>,
+    /// Gas that should be attached when calling the method.
+    pub recommended_gas: U64,
+}
+
+impl MethodRequirement {
+    pub fn new(
+        method_name: &str,
+        base_deposit: Balance,
+        deposit_per_unit: Balance,
+        deposit_unit: Option<&str>,
+        recommended_gas: Gas,
+    ) -> Self {
+        Self {
+            method_name: method_name.to_string(),
+            base_deposit: base_deposit.into(),
+            deposit_per_unit: deposit_per_unit.into(),
+            deposit_unit: deposit_unit.map(|s| s.to_string()),
+            recommended_gas: recommended_gas.0.into(),
+        }
+    }
+}
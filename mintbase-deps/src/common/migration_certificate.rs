@@ -0,0 +1,37 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+use crate::common::{
+    Royalty,
+    TokenKey,
+    TokenMetadata,
+};
+
+/// Issued by a `Store` when a token is burned to be migrated to another
+/// `Store`. The target `Store` retrieves the certificate from the origin
+/// `Store` by cross-contract call, which authenticates it, and re-mints the
+/// token with `origin_key` preserved.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct MigrationCertificate {
+    /// The burned token on the origin `Store`.
+    pub origin_key: TokenKey,
+    /// The `Store` that may re-mint the token.
+    pub target_store: AccountId,
+    /// Owner of the token at the time of migration.
+    pub owner_id: AccountId,
+    /// The account that originally minted the token.
+    pub minter: AccountId,
+    pub metadata: TokenMetadata,
+    pub royalty: Option<Royalty>,
+    /// Block at which the token was burned.
+    pub block_height: u64,
+}
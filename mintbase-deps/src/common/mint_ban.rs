@@ -0,0 +1,47 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::common::time::{
+    now,
+    NearTime,
+};
+
+/// Record of an account being banned from minting on a `Store`. The record
+/// is kept after the ban is lifted, such that an appeal can be traced.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct MintBan {
+    /// Hash of the off-chain document stating the reason for the ban.
+    pub reason_hash: Base64VecU8,
+    /// When the ban was imposed.
+    pub banned_at: NearTime,
+    /// When the ban was lifted, if it has been.
+    pub unbanned_at: Option<NearTime>,
+}
+
+impl MintBan {
+    pub fn new(reason_hash: Base64VecU8) -> Self {
+        Self {
+            reason_hash,
+            banned_at: now(),
+            unbanned_at: None,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.unbanned_at.is_none()
+    }
+
+    pub fn lift(&mut self) {
+        assert!(self.is_active(), "ban already lifted");
+        self.unbanned_at = Some(now());
+    }
+}
@@ -0,0 +1,58 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::{
+    env,
+    AccountId,
+};
+
+/// Record of a single `nft_batch_mint` call. Off-chain systems can use the
+/// deterministic `mint_id` to reconcile payments with on-chain mints.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct MintBatch {
+    /// Hex-encoded sha256 hash of minter, block height and nonce.
+    pub mint_id: String,
+    /// The account that minted this batch.
+    pub minter: AccountId,
+    /// The account that received the tokens of this batch.
+    pub owner_id: AccountId,
+    /// The first token id of this batch.
+    pub first_token_id: u64,
+    /// The last token id of this batch (inclusive).
+    pub last_token_id: u64,
+    /// Id to look up the `TokenMetadata` of the minted tokens. For batches
+    /// minted via `nft_batch_mint_distinct`, this is the metadata of the
+    /// first token, and each token has its metadata under its own token id.
+    pub metadata_id: u64,
+    /// Id to look up the `Royalty` of the minted tokens, if any.
+    pub royalty_id: Option<u64>,
+    /// The block height at which the tokens were minted.
+    pub block_height: u64,
+}
+
+impl MintBatch {
+    /// Derive the `mint_id` from `minter`, the current block height and a
+    /// `nonce` that must be unique per store.
+    pub fn generate_id(
+        minter: &AccountId,
+        nonce: u64,
+    ) -> String {
+        let preimage = [
+            minter.as_bytes(),
+            &env::block_height().to_le_bytes(),
+            &nonce.to_le_bytes(),
+        ]
+        .concat();
+        env::sha256(&preimage)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
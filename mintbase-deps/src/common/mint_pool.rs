@@ -0,0 +1,45 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::common::time::{
+    now,
+    NearTime,
+};
+use crate::common::Royalty;
+
+/// Pool of metadata entries for a generative drop. Anyone may mint from the
+/// pool, and is assigned a pseudo-random entry, which is consumed by the
+/// mint. The entries are stored separately, keyed by pool id and index.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct MintPool {
+    pub pool_id: u64,
+    /// Royalty of each minted token.
+    pub royalty: Option<Royalty>,
+    /// Price of a token in yoctoNEAR, paid to the store owner.
+    pub price: U128,
+    /// If set, no tokens may be minted from this point in time on.
+    pub mint_end: Option<NearTime>,
+    /// Number of entries that haven't been minted yet.
+    pub remaining: u64,
+    /// Number of tokens minted so far.
+    pub minted: u64,
+}
+
+impl MintPool {
+    /// Check whether tokens may currently be minted.
+    pub fn is_open(&self) -> bool {
+        self.mint_end
+            .as_ref()
+            .map(|end| now().0 < end.0)
+            .unwrap_or(true)
+    }
+}
@@ -0,0 +1,79 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::{
+    AccountId,
+    PublicKey,
+};
+
+#[cfg(feature = "wasm")]
+use crate::common::mint_voucher::{
+    tagged_message,
+    verify_ed25519,
+};
+use crate::common::{
+    NearTime,
+    RoyaltyArgs,
+    SplitBetweenUnparsed,
+    TokenMetadata,
+};
+
+/// A minter's off-chain request to mint tokens, which a relayer submits
+/// and pays the gas for. The minter signs the `message` of the request
+/// with the ed25519 key registered on the `Store`.
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct MintRequest {
+    /// The `Store` on which the tokens are minted.
+    pub store_id: AccountId,
+    /// The minter that signed this request.
+    pub minter: AccountId,
+    /// Chosen by the minter, each nonce may be used only once. Nonces are
+    /// shared with the minter's `MintVoucher`s.
+    pub nonce: u64,
+    /// The request may not be submitted after this time.
+    pub deadline: NearTime,
+    /// Owner of the minted tokens.
+    pub owner_id: AccountId,
+    /// Metadata of the tokens to mint.
+    pub metadata: TokenMetadata,
+    /// Number of copies to mint.
+    pub num_to_mint: u64,
+    /// Royalty of the tokens to mint.
+    pub royalty_args: Option<RoyaltyArgs>,
+    /// Split owners of the tokens to mint.
+    pub split_owners: Option<SplitBetweenUnparsed>,
+}
+
+impl MintRequest {
+    /// Distinguishes signed mint requests from other signed messages.
+    pub const MESSAGE_TAG: &'static [u8] = b"mintbase:mint_request:";
+
+    /// The message that is signed by the minter, see `tagged_message`.
+    #[cfg(feature = "wasm")]
+    pub fn message(&self) -> Vec<u8> {
+        tagged_message(Self::MESSAGE_TAG, &self.try_to_vec().unwrap())
+    }
+
+    /// Check that `signature` is a valid signature of this request by
+    /// `public_key`. Only ed25519 keys are supported.
+    #[cfg(feature = "wasm")]
+    pub fn verify(
+        &self,
+        public_key: &PublicKey,
+        signature: &[u8],
+    ) -> bool {
+        verify_ed25519(public_key, &self.message(), signature)
+    }
+
+    /// Key under which the use of this request is tracked.
+    pub fn redemption_key(&self) -> String {
+        format!("{}:{}", self.minter, self.nonce)
+    }
+}
@@ -0,0 +1,50 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+use crate::common::SplitOwners;
+
+/// State of a mint that exceeds the number of tokens that can be minted in a
+/// single call. The token ids of the whole session are reserved upfront,
+/// and all tokens share a single metadata and royalty record.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct MintSession {
+    /// The `mint_id` of the `MintBatch` that is recorded for this session.
+    pub mint_id: String,
+    /// The account that started this session, and that may continue it.
+    pub minter: AccountId,
+    /// The account that receives the minted tokens.
+    pub owner_id: AccountId,
+    /// The first reserved token id.
+    pub first_token_id: u64,
+    /// The total number of tokens to mint in this session.
+    pub num_to_mint: u64,
+    /// The number of tokens minted so far.
+    pub num_minted: u64,
+    /// Id to look up the `TokenMetadata` of the minted tokens.
+    pub metadata_id: u64,
+    /// Id to look up the `Royalty` of the minted tokens, if any.
+    pub royalty_id: Option<u64>,
+    /// Split owners of each minted token, if any.
+    pub split_owners: Option<SplitOwners>,
+}
+
+impl MintSession {
+    /// Number of tokens that still have to be minted.
+    pub fn remaining(&self) -> u64 {
+        self.num_to_mint - self.num_minted
+    }
+
+    /// Token id of the next token to be minted.
+    pub fn next_token_id(&self) -> u64 {
+        self.first_token_id + self.num_minted
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(feature = "wasm")]
+use std::convert::TryFrom;
+
+#[cfg(feature = "wasm")]
+use ed25519_dalek::Verifier;
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::{
+    env,
+    AccountId,
+    PublicKey,
+};
+
+use crate::common::{
+    RoyaltyArgs,
+    TokenMetadata,
+};
+
+/// A minter's off-chain permission for a buyer to mint a token by paying
+/// `price`. The minter signs the `message` of the voucher with the ed25519
+/// key registered on the `Store`.
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct MintVoucher {
+    /// The `Store` on which this voucher may be redeemed.
+    pub store_id: AccountId,
+    /// The minter that signed this voucher.
+    pub minter: AccountId,
+    /// Chosen by the minter, each nonce may be redeemed only once.
+    pub nonce: u64,
+    /// Metadata of the token to mint.
+    pub metadata: TokenMetadata,
+    /// Royalty of the token to mint.
+    pub royalty_args: Option<RoyaltyArgs>,
+    /// Price that the buyer pays to the minter.
+    pub price: U128,
+}
+
+impl MintVoucher {
+    /// Distinguishes signed vouchers from other signed messages.
+    pub const MESSAGE_TAG: &'static [u8] = b"mintbase:mint_voucher:";
+
+    /// The message that is signed by the minter, see `tagged_message`.
+    #[cfg(feature = "wasm")]
+    pub fn message(&self) -> Vec<u8> {
+        tagged_message(Self::MESSAGE_TAG, &self.try_to_vec().unwrap())
+    }
+
+    /// Check that `signature` is a valid signature of this voucher by
+    /// `public_key`. Only ed25519 keys are supported.
+    #[cfg(feature = "wasm")]
+    pub fn verify(
+        &self,
+        public_key: &PublicKey,
+        signature: &[u8],
+    ) -> bool {
+        verify_ed25519(public_key, &self.message(), signature)
+    }
+
+    /// Key under which the redemption of this voucher is tracked.
+    pub fn redemption_key(&self) -> String {
+        format!("{}:{}", self.minter, self.nonce)
+    }
+}
+
+/// The sha256 hash of the borsh-serialized `payload`, prefixed with `tag`.
+/// The tag keeps a signature of one kind of message from being accepted
+/// as a signature of another kind with the same serialization.
+#[cfg(feature = "wasm")]
+pub fn tagged_message(
+    tag: &[u8],
+    payload: &[u8],
+) -> Vec<u8> {
+    env::sha256(&[tag, payload].concat())
+}
+
+/// Check that `signature` is a valid signature of `message` by
+/// `public_key`. Only ed25519 keys are supported.
+#[cfg(feature = "wasm")]
+pub fn verify_ed25519(
+    public_key: &PublicKey,
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    let key = match public_key.as_bytes().split_first() {
+        // curve type 0 is ed25519
+        Some((0, key)) => key,
+        _ => return false,
+    };
+    let key = match ed25519_dalek::PublicKey::from_bytes(key) {
+        Ok(key) => key,
+        Err(_) => return false,
+    };
+    let signature = match ed25519_dalek::Signature::try_from(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    key.verify(message, &signature).is_ok()
+}
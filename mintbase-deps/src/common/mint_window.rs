@@ -0,0 +1,48 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::common::time::now;
+use crate::common::NearTime;
+
+/// Time window within which minting is allowed, e.g. for a coordinated
+/// drop. An unset bound leaves the window open on that side.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct MintWindow {
+    pub mint_start: Option<NearTime>,
+    pub mint_end: Option<NearTime>,
+}
+
+impl MintWindow {
+    /// Validates that the window is not empty.
+    pub fn new(
+        mint_start: Option<NearTime>,
+        mint_end: Option<NearTime>,
+    ) -> Self {
+        if let (Some(start), Some(end)) = (&mint_start, &mint_end) {
+            assert!(start.0 < end.0, "empty mint window");
+        }
+        Self {
+            mint_start,
+            mint_end,
+        }
+    }
+
+    /// Panics unless the current block timestamp is within the window.
+    pub fn assert_open(&self) {
+        let now = now().0;
+        if let Some(start) = &self.mint_start {
+            assert!(now >= start.0, "minting not started");
+        }
+        if let Some(end) = &self.mint_end {
+            assert!(now < end.0, "minting ended");
+        }
+    }
+}
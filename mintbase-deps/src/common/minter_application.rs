@@ -0,0 +1,45 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+use crate::common::time::{
+    now,
+    NearTime,
+};
+
+/// A pending application of an account to become a minter on a `Store`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct MinterApplication {
+    pub account_id: AccountId,
+    /// Reference to an off-chain profile of the applicant.
+    pub profile_reference: String,
+    /// The anti-spam deposit attached to the application, which is refunded
+    /// unless the application is rejected with slashing.
+    pub deposit: U128,
+    /// When the application has been submitted.
+    pub applied_at: NearTime,
+}
+
+impl MinterApplication {
+    pub fn new(
+        account_id: AccountId,
+        profile_reference: String,
+        deposit: u128,
+    ) -> Self {
+        Self {
+            account_id,
+            profile_reference,
+            deposit: deposit.into(),
+            applied_at: now(),
+        }
+    }
+}
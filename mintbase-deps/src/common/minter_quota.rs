@@ -0,0 +1,18 @@
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::common::time::NearTime;
+
+/// The restrictions under which a minter may mint on a `Store`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MinterQuota {
+    /// Number of tokens the minter may still mint, or `None` if uncapped.
+    pub remaining: Option<u64>,
+    /// Before this time, the minter may not mint yet, since they have been
+    /// granted recently.
+    pub mintable_from: Option<NearTime>,
+    /// After this time, the grant of the minter has expired.
+    pub expires_at: Option<NearTime>,
+}
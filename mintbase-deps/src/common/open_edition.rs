@@ -0,0 +1,51 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::common::time::{
+    now,
+    NearTime,
+};
+use crate::common::{
+    Royalty,
+    TokenMetadata,
+};
+
+/// Token of which anyone may mint copies on demand, until the edition
+/// ends.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct OpenEdition {
+    pub edition_id: u64,
+    /// Metadata of each minted copy.
+    pub metadata: TokenMetadata,
+    /// Royalty of each minted copy.
+    pub royalty: Option<Royalty>,
+    /// Price of a copy in yoctoNEAR, paid to the store owner.
+    pub price: U128,
+    /// If set, no copies may be minted from this point in time on.
+    pub mint_end: Option<NearTime>,
+    /// Number of copies minted so far.
+    pub minted: u64,
+    /// The lookup id under which metadata and royalty shared by the minted
+    /// copies are stored, the token id of the first of them. Unset until a
+    /// copy is minted.
+    pub lookup_id: Option<u64>,
+}
+
+impl OpenEdition {
+    /// Check whether copies may currently be minted.
+    pub fn is_open(&self) -> bool {
+        self.mint_end
+            .as_ref()
+            .map(|end| now().0 < end.0)
+            .unwrap_or(true)
+    }
+}
@@ -0,0 +1,326 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::collections::{
+    LookupMap,
+    UnorderedSet,
+};
+use near_sdk::{
+    env,
+    AccountId,
+};
+
+/// Number of consecutive token ids that share a page.
+const PAGE_SPAN: u64 = 1024;
+
+/// The tokens owned by a single account, sharded into pages by token id.
+/// Each page lives in its own storage entry and holds at most `PAGE_SPAN`
+/// sorted token ids, such that inserting or removing a token only touches a
+/// single page, and paginating only loads the pages that are returned.
+///
+/// Like the `near_sdk` collections, pages are written through on
+/// modification, but the `OwnedTokens` themselves need to be saved by the
+/// caller.
+///
+/// Accounts that owned tokens before the pages were introduced keep those
+/// tokens in their previous `UnorderedSet` until they are moved into pages
+/// via `migrate_legacy`. Until then, legacy tokens are listed after the
+/// paged ones, and not in ascending order.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OwnedTokens {
+    prefix: Vec<u8>,
+    /// Key and number of tokens of each non-empty page, sorted by key.
+    pages: Vec<(u64, u32)>,
+    /// The tokens that haven't been moved into pages yet, if any.
+    legacy: Option<UnorderedSet<u64>>,
+}
+
+impl OwnedTokens {
+    pub fn new(prefix: Vec<u8>) -> Self {
+        Self {
+            prefix,
+            pages: vec![],
+            legacy: None,
+        }
+    }
+
+    /// Number of owned tokens.
+    pub fn len(&self) -> u64 {
+        self.pages.iter().map(|(_, len)| *len as u64).sum::<u64>() + self.legacy_len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty() && self.legacy_len() == 0
+    }
+
+    pub fn contains(
+        &self,
+        token_id: &u64,
+    ) -> bool {
+        (self.page_index(token_id / PAGE_SPAN).is_ok()
+            && self
+                .read_page(token_id / PAGE_SPAN)
+                .binary_search(token_id)
+                .is_ok())
+            || self
+                .legacy
+                .as_ref()
+                .map(|legacy| legacy.contains(token_id))
+                .unwrap_or(false)
+    }
+
+    /// Add `token_id`, returning false if it was already owned.
+    pub fn insert(
+        &mut self,
+        token_id: &u64,
+    ) -> bool {
+        if let Some(ref legacy) = self.legacy {
+            if legacy.contains(token_id) {
+                return false;
+            }
+        }
+        let key = token_id / PAGE_SPAN;
+        let mut page = self.read_page(key);
+        match page.binary_search(token_id) {
+            Ok(_) => return false,
+            Err(pos) => page.insert(pos, *token_id),
+        }
+        self.write_page(key, &page);
+        match self.page_index(key) {
+            Ok(index) => self.pages[index].1 += 1,
+            Err(index) => self.pages.insert(index, (key, 1)),
+        }
+        true
+    }
+
+    /// Remove `token_id`, returning false if it wasn't owned.
+    pub fn remove(
+        &mut self,
+        token_id: &u64,
+    ) -> bool {
+        let key = token_id / PAGE_SPAN;
+        let index = match self.page_index(key) {
+            Ok(index) => index,
+            Err(_) => return self.remove_legacy(token_id),
+        };
+        let mut page = self.read_page(key);
+        match page.binary_search(token_id) {
+            Ok(pos) => page.remove(pos),
+            Err(_) => return self.remove_legacy(token_id),
+        };
+        self.write_page(key, &page);
+        if page.is_empty() {
+            self.pages.remove(index);
+        } else {
+            self.pages[index].1 -= 1;
+        }
+        true
+    }
+
+    /// Iterate over all owned token ids in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        self.iter_from(0)
+    }
+
+    /// Iterate over the owned token ids in ascending order, starting at the
+    /// `from_index`th token. Pages before `from_index` are skipped without
+    /// being loaded. Legacy tokens follow the paged ones.
+    pub fn iter_from(
+        &self,
+        from_index: u64,
+    ) -> impl Iterator<Item = u64> + '_ {
+        let paged_len = self.len() - self.legacy_len();
+        let legacy = self
+            .legacy
+            .iter()
+            .flat_map(|legacy| legacy.iter())
+            .skip(from_index.saturating_sub(paged_len) as usize);
+        let mut skipped = 0;
+        let first_page = self
+            .pages
+            .iter()
+            .position(|(_, len)| {
+                skipped += *len as u64;
+                skipped > from_index
+            })
+            .unwrap_or(self.pages.len());
+        let skip_in_page = from_index
+            - self.pages[..first_page]
+                .iter()
+                .map(|(_, len)| *len as u64)
+                .sum::<u64>();
+        self.pages[first_page..]
+            .iter()
+            .flat_map(move |(key, _)| self.read_page(*key))
+            .skip(skip_in_page as usize)
+            .chain(legacy)
+    }
+
+    /// Wrap the owned-token set of an account that owned tokens before the
+    /// pages were introduced. `prefix` must not overlap with the prefixes of
+    /// the set.
+    pub fn from_legacy(
+        prefix: Vec<u8>,
+        legacy: UnorderedSet<u64>,
+    ) -> Self {
+        Self {
+            prefix,
+            pages: vec![],
+            legacy: Some(legacy),
+        }
+    }
+
+    /// Whether some tokens haven't been moved into pages yet.
+    pub fn has_legacy(&self) -> bool {
+        self.legacy.is_some()
+    }
+
+    /// Move up to `limit` legacy tokens into pages, returning whether all of
+    /// them have been moved.
+    pub fn migrate_legacy(
+        &mut self,
+        limit: u64,
+    ) -> bool {
+        let token_ids: Vec<u64> = match self.legacy {
+            Some(ref legacy) => legacy.iter().take(limit as usize).collect(),
+            None => return true,
+        };
+        for token_id in token_ids {
+            self.remove_legacy(&token_id);
+            self.insert(&token_id);
+        }
+        !self.has_legacy()
+    }
+
+    fn legacy_len(&self) -> u64 {
+        self.legacy.as_ref().map(|legacy| legacy.len()).unwrap_or(0)
+    }
+
+    fn remove_legacy(
+        &mut self,
+        token_id: &u64,
+    ) -> bool {
+        let legacy = match self.legacy {
+            Some(ref mut legacy) => legacy,
+            None => return false,
+        };
+        let removed = legacy.remove(token_id);
+        if legacy.is_empty() {
+            self.legacy = None;
+        }
+        removed
+    }
+
+    fn page_index(
+        &self,
+        key: u64,
+    ) -> Result<usize, usize> {
+        self.pages.binary_search_by_key(&key, |(key, _)| *key)
+    }
+
+    fn storage_key(
+        &self,
+        key: u64,
+    ) -> Vec<u8> {
+        let mut storage_key = self.prefix.clone();
+        storage_key.extend_from_slice(&key.to_le_bytes());
+        storage_key
+    }
+
+    fn read_page(
+        &self,
+        key: u64,
+    ) -> Vec<u64> {
+        env::storage_read(&self.storage_key(key))
+            .map(|bytes| Vec::try_from_slice(&bytes).unwrap())
+            .unwrap_or_default()
+    }
+
+    fn write_page(
+        &self,
+        key: u64,
+        page: &[u64],
+    ) {
+        if page.is_empty() {
+            env::storage_remove(&self.storage_key(key));
+        } else {
+            env::storage_write(&self.storage_key(key), &page.try_to_vec().unwrap());
+        }
+    }
+}
+
+/// The `OwnedTokens` of each account. The owned-token sets of accounts that
+/// owned tokens before `OwnedTokens` were introduced are read from the
+/// legacy map, and moved out of it once they are saved again.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TokensPerOwner {
+    owned: LookupMap<AccountId, OwnedTokens>,
+    legacy: LookupMap<AccountId, UnorderedSet<u64>>,
+    /// Prefix of the pages of each account, followed by the account id.
+    pages_prefix: Vec<u8>,
+}
+
+impl TokensPerOwner {
+    pub fn new(
+        prefix: Vec<u8>,
+        legacy_prefix: Vec<u8>,
+        pages_prefix: Vec<u8>,
+    ) -> Self {
+        Self {
+            owned: LookupMap::new(prefix),
+            legacy: LookupMap::new(legacy_prefix),
+            pages_prefix,
+        }
+    }
+
+    pub fn get(
+        &self,
+        account_id: &AccountId,
+    ) -> Option<OwnedTokens> {
+        self.owned.get(account_id).or_else(|| {
+            self.legacy
+                .get(account_id)
+                .map(|legacy| OwnedTokens::from_legacy(self.account_prefix(account_id), legacy))
+        })
+    }
+
+    /// Get the tokens owned by `account_id`, or an empty set if they have
+    /// never owned tokens on this store.
+    pub fn get_or_new(
+        &self,
+        account_id: &AccountId,
+    ) -> OwnedTokens {
+        self.get(account_id)
+            .unwrap_or_else(|| OwnedTokens::new(self.account_prefix(account_id)))
+    }
+
+    pub fn insert(
+        &mut self,
+        account_id: &AccountId,
+        owned: &OwnedTokens,
+    ) {
+        self.owned.insert(account_id, owned);
+        if owned.has_legacy() {
+            self.legacy.remove(account_id);
+        }
+    }
+
+    pub fn remove(
+        &mut self,
+        account_id: &AccountId,
+    ) {
+        self.owned.remove(account_id);
+        self.legacy.remove(account_id);
+    }
+
+    fn account_prefix(
+        &self,
+        account_id: &AccountId,
+    ) -> Vec<u8> {
+        let mut prefix = self.pages_prefix.clone();
+        prefix.extend_from_slice(account_id.as_bytes());
+        prefix
+    }
+}
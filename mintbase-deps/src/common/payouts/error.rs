@@ -0,0 +1,69 @@
+use std::fmt;
+
+/// Error returned when `Royalty::try_new` or `SplitOwners::try_new` are
+/// called with invalid arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PayoutArgsError {
+    /// The numerators don't add up to 10,000.
+    SumMismatch {
+        sum: u64,
+    },
+    /// Fewer receivers than required.
+    TooFewEntries {
+        len: usize,
+        min: usize,
+    },
+    /// More receivers than fit into a payout.
+    TooManyEntries {
+        len: usize,
+        max: usize,
+    },
+    InvalidAccount {
+        account_id: String,
+    },
+    /// A numerator is out of range.
+    InvalidShare {
+        account_id: String,
+        numerator: u32,
+    },
+    /// The overall royalty percentage is zero or above the upper limit.
+    InvalidPercentage {
+        percentage: u32,
+        max: u32,
+    },
+}
+
+impl fmt::Display for PayoutArgsError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        match self {
+            Self::SumMismatch { sum } => {
+                write!(f, "numerators add up to {} instead of 10,000", sum)
+            },
+            Self::TooFewEntries { len, min } => {
+                write!(f, "{} receivers, need at least {}", len, min)
+            },
+            Self::TooManyEntries { len, max } => {
+                write!(f, "{} receivers, allowed at most {}", len, max)
+            },
+            Self::InvalidAccount { account_id } => {
+                write!(f, "invalid account id {}", account_id)
+            },
+            Self::InvalidShare {
+                account_id,
+                numerator,
+            } => {
+                write!(
+                    f,
+                    "share {} of {} not between 1 and 10,000",
+                    numerator, account_id
+                )
+            },
+            Self::InvalidPercentage { percentage, max } => {
+                write!(f, "percentage {} not between 1 and {}", percentage, max)
+            },
+        }
+    }
+}
@@ -1,18 +1,33 @@
+pub mod error;
 pub mod payout;
 pub mod royalty;
 pub mod splits;
+pub mod validation;
+pub mod vesting;
 
+pub use error::PayoutArgsError;
 pub use payout::{
     OwnershipFractions,
     Payout,
 };
 pub use royalty::{
+    BatchRoyaltyArgs,
     Royalty,
     RoyaltyArgs,
 };
 pub use splits::{
     NewSplitOwner,
     SplitBetween,
+    SplitBetweenArgs,
     SplitBetweenUnparsed,
     SplitOwners,
 };
+pub use validation::{
+    UncheckedRoyaltyArgs,
+    UncheckedSplitBetween,
+    ValidationReport,
+};
+pub use vesting::{
+    ClaimableBalance,
+    VestingSchedule,
+};
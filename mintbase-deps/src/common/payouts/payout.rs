@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
 
 use near_sdk::json_types::U128;
 use near_sdk::serde::{
@@ -15,13 +18,133 @@ use crate::common::{
     Royalty,
     SafeFraction,
     SplitOwners,
+    VestingSchedule,
 };
 use crate::constants::MAX_LEN_PAYOUT;
+use crate::utils::mul_div;
 
 /// Whom to pay. Generated from `OwnershipFractions`.
 #[derive(Serialize, Deserialize)]
 pub struct Payout {
+    /// The amounts always sum up to the balance of the sale. The remainder
+    /// of rounding down each share is paid to the receiver with the largest
+    /// share, ties going to the lowest account id.
     pub payout: HashMap<AccountId, U128>,
+    /// Receivers whose payouts must be accrued as a `ClaimableBalance`
+    /// instead of being transferred directly.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub vesting: HashMap<AccountId, VestingSchedule>,
+    /// The fungible token contract that the payout is denominated in, or
+    /// `None` for NEAR.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ft_contract: Option<AccountId>,
+}
+
+impl Payout {
+    /// Add the `fee_legs` returned by a fee hook, reducing all other payouts
+    /// pro rata. Returns false and leaves the payout unchanged if the fee
+    /// legs exceed `max_fee` of `balance`.
+    pub fn add_fee_legs(
+        &mut self,
+        fee_legs: HashMap<AccountId, U128>,
+        balance: Balance,
+        max_fee: SafeFraction,
+    ) -> bool {
+        let total_fee = fee_legs
+            .values()
+            .try_fold(0u128, |acc, fee| acc.checked_add(fee.0));
+        let total_fee = match total_fee {
+            Some(total_fee) if total_fee <= max_fee.multiply_balance(balance) => total_fee,
+            _ => return false,
+        };
+        if total_fee == 0 {
+            return true;
+        }
+
+        let remainder_receiver = remainder_receiver(self.payout.iter().map(|(k, v)| (k, v.0)));
+        let mut deducted = 0;
+        self.payout.values_mut().for_each(|amount| {
+            let deduction = mul_div(amount.0, total_fee, balance);
+            amount.0 -= deduction;
+            deducted += deduction;
+        });
+        if let Some(receiver) = remainder_receiver {
+            let amount = self.payout.get_mut(&receiver).unwrap();
+            amount.0 = amount.0.saturating_sub(total_fee - deducted);
+        }
+        fee_legs.into_iter().for_each(|(receiver, fee)| {
+            let amount = self.payout.entry(receiver).or_insert(U128(0));
+            amount.0 += fee.0;
+        });
+        true
+    }
+
+    /// Fold the amounts below `threshold` into the amount of `owner_id`, or
+    /// of the receiver with the largest share if the owner isn't paid, e.g.
+    /// because of split owners.
+    pub fn fold_dust(
+        &mut self,
+        threshold: Balance,
+        owner_id: &AccountId,
+    ) {
+        let receiver = match self.fold_receiver(owner_id) {
+            Some(receiver) => receiver,
+            None => return,
+        };
+        let mut dust = 0;
+        self.payout.retain(|account_id, amount| {
+            let is_dust = *account_id != receiver && amount.0 < threshold;
+            if is_dust {
+                dust += amount.0;
+            }
+            !is_dust
+        });
+        self.payout.get_mut(&receiver).unwrap().0 += dust;
+    }
+
+    /// Keep at most `max_len` receivers: the receiver that dust would be
+    /// folded into, and the largest other shares, ties going to the lowest
+    /// account id. The other shares are folded as in `fold_dust`.
+    pub fn truncate(
+        &mut self,
+        max_len: usize,
+        owner_id: &AccountId,
+    ) {
+        if max_len == 0 || self.payout.len() <= max_len {
+            return;
+        }
+        let receiver = match self.fold_receiver(owner_id) {
+            Some(receiver) => receiver,
+            None => return,
+        };
+        let mut others: Vec<(AccountId, Balance)> = self
+            .payout
+            .iter()
+            .filter(|(account_id, _)| **account_id != receiver)
+            .map(|(account_id, amount)| (account_id.clone(), amount.0))
+            .collect();
+        others.sort_by(|(a_id, a), (b_id, b)| b.cmp(a).then_with(|| a_id.cmp(b_id)));
+        let folded: Balance = others
+            .into_iter()
+            .skip(max_len - 1)
+            .map(|(account_id, amount)| {
+                self.payout.remove(&account_id);
+                amount
+            })
+            .sum();
+        self.payout.get_mut(&receiver).unwrap().0 += folded;
+    }
+
+    /// The owner if paid, otherwise the receiver with the largest share.
+    fn fold_receiver(
+        &self,
+        owner_id: &AccountId,
+    ) -> Option<AccountId> {
+        match self.payout.contains_key(owner_id) {
+            true => Some(owner_id.clone()),
+            false => remainder_receiver(self.payout.iter().map(|(k, v)| (k, v.0))),
+        }
+    }
 }
 
 /// Take the Royalty and SplitOwner information for a token, and return a Vector
@@ -39,7 +162,27 @@ impl OwnershipFractions {
         royalty: &Option<Royalty>,
         split_owners: &Option<SplitOwners>,
     ) -> Self {
-        let roy_len = royalty.as_ref().map(|r| r.split_between.len()).unwrap_or(0);
+        Self::new_merged(
+            owner_id,
+            royalty.as_ref().map_or(&[], std::slice::from_ref),
+            split_owners,
+        )
+    }
+
+    /// Like `new`, but honoring several royalties, e.g. those of a token and
+    /// its composed children. Each royalty takes its percentage of the sale,
+    /// and receivers named in several royalties receive the sum of their
+    /// shares. The royalty percentages must not add up to more than 10,000.
+    pub fn new_merged(
+        owner_id: &str,
+        royalties: &[Royalty],
+        split_owners: &Option<SplitOwners>,
+    ) -> Self {
+        let roy_len = royalties
+            .iter()
+            .flat_map(|royalty| royalty.split_between.keys())
+            .collect::<HashSet<_>>()
+            .len();
         let split_len = split_owners
             .as_ref()
             .map(|r| r.split_between.len())
@@ -47,18 +190,21 @@ impl OwnershipFractions {
         assert!((roy_len + split_len) as u32 <= MAX_LEN_PAYOUT);
 
         let mut payout: HashMap<AccountId, MultipliedSafeFraction> = Default::default();
-        let percentage_not_taken_by_royalty = match royalty {
-            Some(royalty) => {
-                let (split_between, percentage) =
-                    (royalty.split_between.clone(), royalty.percentage);
-                split_between.iter().for_each(|(receiver, &rel_perc)| {
+        let mut percentage_not_taken_by_royalty = SafeFraction::new(10_000u32);
+        royalties.iter().for_each(|royalty| {
+            let percentage = royalty.percentage;
+            royalty
+                .split_between
+                .iter()
+                .for_each(|(receiver, &rel_perc)| {
                     let abs_perc: MultipliedSafeFraction = percentage * rel_perc;
-                    payout.insert(receiver.to_string().parse().unwrap(), abs_perc);
+                    match payout.get(receiver) {
+                        Some(&roy_perc) => payout.insert(receiver.clone(), abs_perc + roy_perc),
+                        None => payout.insert(receiver.clone(), abs_perc),
+                    };
                 });
-                SafeFraction::new(10_000 - percentage.numerator)
-            },
-            None => SafeFraction::new(10_000u32),
-        };
+            percentage_not_taken_by_royalty -= percentage;
+        });
 
         match split_owners {
             Some(ref split_owners) => {
@@ -99,11 +245,54 @@ impl OwnershipFractions {
         balance: Balance,
     ) -> Payout {
         Payout {
-            payout: self
-                .fractions
-                .into_iter()
-                .map(|(k, v)| (k, v.multiply_balance(balance).into()))
-                .collect(),
+            payout: self.distribute(balance, MultipliedSafeFraction::multiply_balance),
+            vesting: HashMap::new(),
+            ft_contract: None,
         }
     }
+
+    /// Like `into_payout`, but for an `amount` of the fungible token at
+    /// `ft_contract`. Shares are computed at full precision, such that
+    /// royalties are honored for tokens with few decimals.
+    pub fn into_ft_payout(
+        self,
+        amount: Balance,
+        ft_contract: AccountId,
+    ) -> Payout {
+        Payout {
+            payout: self.distribute(amount, MultipliedSafeFraction::multiply_amount),
+            vesting: HashMap::new(),
+            ft_contract: Some(ft_contract),
+        }
+    }
+
+    /// Split `amount` according to the fractions, assigning the remainder
+    /// as documented on `Payout`.
+    fn distribute(
+        self,
+        amount: Balance,
+        multiply: fn(&MultipliedSafeFraction, Balance) -> Balance,
+    ) -> HashMap<AccountId, U128> {
+        let remainder_receiver =
+            remainder_receiver(self.fractions.iter().map(|(k, v)| (k, v.numerator as u128)));
+        let mut payout: HashMap<AccountId, U128> = self
+            .fractions
+            .iter()
+            .map(|(k, v)| (k.clone(), multiply(v, amount).into()))
+            .collect();
+        let paid: Balance = payout.values().map(|amount| amount.0).sum();
+        if let Some(receiver) = remainder_receiver {
+            payout.get_mut(&receiver).unwrap().0 += amount - paid;
+        }
+        payout
+    }
+}
+
+/// The receiver with the largest share, ties going to the lowest account id.
+pub(crate) fn remainder_receiver<'a>(
+    shares: impl Iterator<Item = (&'a AccountId, u128)>
+) -> Option<AccountId> {
+    shares
+        .max_by(|(a_id, a), (b_id, b)| a.cmp(b).then_with(|| b_id.cmp(a_id)))
+        .map(|(account_id, _)| account_id.clone())
 }
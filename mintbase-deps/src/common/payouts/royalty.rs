@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{
+    BTreeMap,
+    HashMap,
+};
 use std::convert::TryFrom;
 
 use near_sdk::borsh::{
@@ -6,18 +9,27 @@ use near_sdk::borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+use near_sdk::json_types::U64;
 use near_sdk::serde::{
     Deserialize,
     Serialize,
 };
-use near_sdk::AccountId;
+use near_sdk::{
+    env,
+    AccountId,
+};
 
+use crate::common::payouts::payout::remainder_receiver;
+use crate::common::payouts::PayoutArgsError;
 use crate::common::{
     SafeFraction,
     SplitBetween,
     SplitBetweenUnparsed,
 };
-use crate::constants::ROYALTY_UPPER_LIMIT;
+use crate::constants::{
+    MAX_LEN_PAYOUT,
+    ROYALTY_UPPER_LIMIT,
+};
 
 /// A representation of permanent partial ownership of a Token's revenues.
 /// Percentages must add to 10,000. On purchase of the `Token`, a percentage of
@@ -37,44 +49,157 @@ pub struct Royalty {
 /// Stable
 impl Royalty {
     /// Validates all arguments. Addresses must be valid and percentages must be
-    /// within accepted values. Hashmap percentages must add to 10000.
+    /// within accepted values. Hashmap percentages must add to 10000. Panics
+    /// with the error of `try_new` on invalid arguments.
     pub fn new(royalty_args: RoyaltyArgs) -> Self {
-        assert!(!royalty_args.split_between.is_empty());
+        Self::try_new(royalty_args).unwrap_or_else(|err| env::panic_str(&err.to_string()))
+    }
+
+    /// Like `new`, but returning the reason for which the arguments are
+    /// invalid. The royalty must leave room for the owner in payouts, i.e.
+    /// have less than `MAX_LEN_PAYOUT` receivers.
+    pub fn try_new(royalty_args: RoyaltyArgs) -> Result<Self, PayoutArgsError> {
         let percentage = royalty_args.percentage;
         let split_between = royalty_args.split_between;
 
-        assert!(
-            percentage <= ROYALTY_UPPER_LIMIT,
-            "percentage: {} must be <= 5000",
-            percentage
-        );
-        assert!(percentage > 0, "percentage cannot be zero");
-        assert!(!split_between.is_empty(), "royalty mapping is empty");
+        if percentage == 0 || percentage > ROYALTY_UPPER_LIMIT {
+            return Err(PayoutArgsError::InvalidPercentage {
+                percentage,
+                max: ROYALTY_UPPER_LIMIT,
+            });
+        }
+        if split_between.is_empty() {
+            return Err(PayoutArgsError::TooFewEntries { len: 0, min: 1 });
+        }
+        if split_between.len() >= MAX_LEN_PAYOUT as usize {
+            return Err(PayoutArgsError::TooManyEntries {
+                len: split_between.len(),
+                max: MAX_LEN_PAYOUT as usize - 1,
+            });
+        }
+
+        let mut sum: u64 = 0;
+        for (addr, numerator) in split_between.iter() {
+            if AccountId::try_from(addr.to_string()).is_err() {
+                return Err(PayoutArgsError::InvalidAccount {
+                    account_id: addr.to_string(),
+                });
+            }
+            if *numerator == 0 || *numerator > 10_000 {
+                return Err(PayoutArgsError::InvalidShare {
+                    account_id: addr.to_string(),
+                    numerator: *numerator,
+                });
+            }
+            sum += *numerator as u64;
+        }
+        if sum != 10_000 {
+            return Err(PayoutArgsError::SumMismatch { sum });
+        }
 
-        let mut sum: u32 = 0;
         let split_between: SplitBetween = split_between
             .into_iter()
-            .map(|(addr, numerator)| {
-                assert!(AccountId::try_from(addr.to_string()).is_ok());
-                // assert!(env::is_valid_account_id(addr.as_bytes()));
-                assert!(numerator > 0, "percentage cannot be zero");
-                let sf = SafeFraction::new(numerator);
-                sum += sf.numerator;
-                (addr, sf)
-            })
+            .map(|(addr, numerator)| (addr, SafeFraction::new(numerator)))
             .collect();
-        assert_eq!(sum, 10_000, "fractions don't add to 10,000");
-
-        Self {
+        Ok(Self {
             percentage: SafeFraction::new(percentage),
             split_between,
-        }
+        })
     }
 }
 
 /// Unparsed pre-image of a Royalty struct. Used in `Store::mint_tokens`.
-#[derive(Clone, Deserialize, Serialize)]
+///
+/// Besides this canonical form, `RoyaltyArgs` may be sent as
+/// `{ "royalty_bps": { "alice.near": 500, "bob.near": 250 } }`, i.e. in basis
+/// points of the sale price per receiver as in ERC-2981, which is converted
+/// into the canonical form on deserialization.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+#[serde(try_from = "RoyaltyArgsInput")]
 pub struct RoyaltyArgs {
     pub split_between: SplitBetweenUnparsed,
     pub percentage: u32,
 }
+
+impl RoyaltyArgs {
+    /// Convert royalties in basis points of the sale price per receiver into
+    /// the canonical form. The overall percentage is the sum of all basis
+    /// points, the numerators are the shares of each receiver therein. The
+    /// rounding remainder of the numerators goes to the largest receiver,
+    /// ties going to the lowest account id.
+    pub fn from_basis_points(royalty_bps: SplitBetweenUnparsed) -> Result<Self, String> {
+        let total: u64 = royalty_bps.values().map(|bps| *bps as u64).sum();
+        if total == 0 {
+            return Err("royalty_bps must not be zero".to_string());
+        }
+        if total > 10_000 {
+            return Err(format!("royalty_bps sum {} exceeds 10,000", total));
+        }
+        let mut split_between: SplitBetweenUnparsed = royalty_bps
+            .iter()
+            .map(|(account_id, bps)| (account_id.clone(), (*bps as u64 * 10_000 / total) as u32))
+            .collect();
+        let remainder = 10_000 - split_between.values().sum::<u32>();
+        if let Some(receiver) = remainder_receiver(royalty_bps.iter().map(|(k, v)| (k, *v as u128)))
+        {
+            *split_between.get_mut(&receiver).unwrap() += remainder;
+        }
+        Ok(Self {
+            split_between,
+            percentage: total as u32,
+        })
+    }
+}
+
+/// The accepted JSON forms of `RoyaltyArgs`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RoyaltyArgsInput {
+    Fractions {
+        split_between: SplitBetweenUnparsed,
+        percentage: u32,
+    },
+    BasisPoints {
+        royalty_bps: SplitBetweenUnparsed,
+    },
+}
+
+impl TryFrom<RoyaltyArgsInput> for RoyaltyArgs {
+    type Error = String;
+
+    fn try_from(input: RoyaltyArgsInput) -> Result<Self, Self::Error> {
+        match input {
+            RoyaltyArgsInput::Fractions {
+                split_between,
+                percentage,
+            } => Ok(Self {
+                split_between,
+                percentage,
+            }),
+            RoyaltyArgsInput::BasisPoints { royalty_bps } => Self::from_basis_points(royalty_bps),
+        }
+    }
+}
+
+/// The `royalty_args` of a batch mint: either a single royalty shared by all
+/// copies, or royalties for some copies by their index within the batch.
+/// Copies without a royalty of their own get the default royalty of the
+/// `Store`, if any.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum BatchRoyaltyArgs {
+    Shared(RoyaltyArgs),
+    PerCopy(BTreeMap<U64, RoyaltyArgs>),
+}
+
+impl BatchRoyaltyArgs {
+    /// Split into the shared royalty and the royalties of individual copies.
+    pub fn split(royalty_args: Option<Self>) -> (Option<RoyaltyArgs>, BTreeMap<U64, RoyaltyArgs>) {
+        match royalty_args {
+            Some(BatchRoyaltyArgs::Shared(royalty_args)) => (Some(royalty_args), BTreeMap::new()),
+            Some(BatchRoyaltyArgs::PerCopy(overrides)) => (None, overrides),
+            None => (None, BTreeMap::new()),
+        }
+    }
+}
@@ -14,7 +14,12 @@ use serde::{
     Serialize,
 };
 
-use crate::common::SafeFraction;
+use crate::common::payouts::PayoutArgsError;
+use crate::common::{
+    SafeFraction,
+    StorageDeposit,
+};
+use crate::constants::MAX_LEN_PAYOUT;
 
 pub type SplitBetweenUnparsed = HashMap<AccountId, u32>;
 pub type SplitBetween = HashMap<near_sdk::AccountId, SafeFraction>;
@@ -22,12 +27,47 @@ pub type SplitBetween = HashMap<near_sdk::AccountId, SafeFraction>;
 /// A representation of the splitting of ownership of the Token. Percentages
 /// must add to 1. On purchase of the `Token`, the value of the transaction
 /// (minus royalty percentage) will be paid out to each account in `SplitOwners`
-/// mapping. The `SplitOwner` field on the `Token` will be set to `None` after
-/// each transfer of the token.
+/// mapping. Unless `sticky` or requiring a `burn_quorum`, the `SplitOwner`
+/// field on the `Token` will be set to `None` after each transfer of the token.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
 pub struct SplitOwners {
     pub split_between: HashMap<AccountId, SafeFraction>,
+    /// If set, burning the token requires confirmations from this many
+    /// split owners, and the split owners survive transfers of the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burn_quorum: Option<u32>,
+    /// If set, the split owners survive transfers of the token until they
+    /// are explicitly cleared.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sticky: bool,
+    /// The storage deposit taken when the split owners were set after
+    /// minting. Only the account that paid it may clear or replace sticky
+    /// split owners, and it is refunded to that account.
+    #[serde(skip)]
+    pub deposit: Option<StorageDeposit>,
+}
+
+/// The accepted JSON forms of the `split_between` argument of
+/// `set_split_owners`: either the canonical `SplitBetweenUnparsed`, or
+/// `{ "split_bps": { "alice.near": 6000, "bob.near": 4000 } }`, explicitly
+/// giving each owner's share in basis points. Both must sum to 10,000.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum SplitBetweenArgs {
+    Fractions(SplitBetweenUnparsed),
+    BasisPoints { split_bps: SplitBetweenUnparsed },
+}
+
+impl SplitBetweenArgs {
+    /// Convert into the canonical representation.
+    pub fn into_split_between(self) -> SplitBetweenUnparsed {
+        match self {
+            SplitBetweenArgs::Fractions(split_between) => split_between,
+            // numerators of `SplitOwners` already are basis points
+            SplitBetweenArgs::BasisPoints { split_bps } => split_bps,
+        }
+    }
 }
 
 // TODO: why defined here and then implemented inside store?
@@ -37,20 +77,111 @@ pub trait NewSplitOwner {
 
 impl NewSplitOwner for SplitOwners {
     fn new(split_between: HashMap<near_sdk::AccountId, u32>) -> Self {
-        assert!(split_between.len() >= 2);
-        // validate args
-        let mut sum: u32 = 0;
+        Self::try_new(split_between).unwrap_or_else(|err| env::panic_str(&err.to_string()))
+    }
+}
+
+impl SplitOwners {
+    /// Like `SplitOwners::new`, but returning the reason for which
+    /// `split_between` is invalid.
+    pub fn try_new(split_between: SplitBetweenUnparsed) -> Result<Self, PayoutArgsError> {
+        Self::parse(split_between, 2)
+    }
+
+    /// Like `SplitOwners::new`, but allowing a single receiver, e.g. to
+    /// donate the proceeds of a sale.
+    pub fn new_payout_override(split_between: SplitBetweenUnparsed) -> Self {
+        Self::parse(split_between, 1).unwrap_or_else(|err| env::panic_str(&err.to_string()))
+    }
+
+    /// Require `burn_quorum` of the split owners to confirm a burn of the
+    /// token. The quorum must be between 1 and the number of split owners.
+    pub fn with_burn_quorum(
+        self,
+        burn_quorum: u32,
+    ) -> Self {
+        assert!(
+            burn_quorum >= 1 && burn_quorum as usize <= self.split_between.len(),
+            "burn quorum must be between 1 and {}",
+            self.split_between.len()
+        );
+        Self {
+            burn_quorum: Some(burn_quorum),
+            ..self
+        }
+    }
+
+    /// Keep the split owners across transfers of the token.
+    pub fn with_sticky(
+        self,
+        sticky: bool,
+    ) -> Self {
+        Self { sticky, ..self }
+    }
+
+    /// Whether the split owners are kept when the token is transferred.
+    /// Splits with a burn quorum are always kept, otherwise the quorum
+    /// could be escaped by transferring the token before burning it.
+    pub fn survives_transfer(&self) -> bool {
+        self.sticky || self.burn_quorum.is_some()
+    }
+
+    /// Record the storage deposit taken for setting the split owners.
+    pub fn with_deposit(
+        self,
+        deposit: StorageDeposit,
+    ) -> Self {
+        Self {
+            deposit: Some(deposit),
+            ..self
+        }
+    }
+
+    fn parse(
+        split_between: SplitBetweenUnparsed,
+        min_len: usize,
+    ) -> Result<Self, PayoutArgsError> {
+        if split_between.len() < min_len {
+            return Err(PayoutArgsError::TooFewEntries {
+                len: split_between.len(),
+                min: min_len,
+            });
+        }
+        if split_between.len() > MAX_LEN_PAYOUT as usize {
+            return Err(PayoutArgsError::TooManyEntries {
+                len: split_between.len(),
+                max: MAX_LEN_PAYOUT as usize,
+            });
+        }
+
+        let mut sum: u64 = 0;
+        for (addr, numerator) in split_between.iter() {
+            if !env::is_valid_account_id(addr.as_bytes()) {
+                return Err(PayoutArgsError::InvalidAccount {
+                    account_id: addr.to_string(),
+                });
+            }
+            if *numerator > 10_000 {
+                return Err(PayoutArgsError::InvalidShare {
+                    account_id: addr.to_string(),
+                    numerator: *numerator,
+                });
+            }
+            sum += *numerator as u64;
+        }
+        if sum != 10_000 {
+            return Err(PayoutArgsError::SumMismatch { sum });
+        }
+
         let split_between: HashMap<AccountId, SafeFraction> = split_between
             .into_iter()
-            .map(|(addr, numerator)| {
-                assert!(env::is_valid_account_id(addr.as_bytes()));
-                let sf = SafeFraction::new(numerator);
-                sum += sf.numerator;
-                (addr, sf)
-            })
+            .map(|(addr, numerator)| (addr, SafeFraction::new(numerator)))
             .collect();
-        assert!(sum == 10_000, "sum not 10_000: {}", sum);
-
-        Self { split_between }
+        Ok(Self {
+            split_between,
+            burn_quorum: None,
+            sticky: false,
+            deposit: None,
+        })
     }
 }
@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+
+use near_sdk::serde::de::{
+    MapAccess,
+    Visitor,
+};
+use near_sdk::serde::ser::SerializeMap;
+use near_sdk::serde::{
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+use near_sdk::AccountId;
+
+use crate::constants::{
+    MAX_LEN_PAYOUT,
+    ROYALTY_UPPER_LIMIT,
+};
+
+/// A royalty or split map as sent by a client. Unlike `SplitBetweenUnparsed`,
+/// account ids are not parsed and duplicate accounts are kept, such that
+/// both can be reported.
+#[derive(Clone, Debug, Default)]
+pub struct UncheckedSplitBetween(pub Vec<(String, u32)>);
+
+impl<'de> Deserialize<'de> for UncheckedSplitBetween {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SplitVisitor;
+
+        impl<'de> Visitor<'de> for SplitVisitor {
+            type Value = UncheckedSplitBetween;
+
+            fn expecting(
+                &self,
+                f: &mut fmt::Formatter,
+            ) -> fmt::Result {
+                write!(f, "a map from account ids to numerators")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut entries = vec![];
+                while let Some(entry) = map.next_entry::<String, u32>()? {
+                    entries.push(entry);
+                }
+                Ok(UncheckedSplitBetween(entries))
+            }
+        }
+
+        deserializer.deserialize_map(SplitVisitor)
+    }
+}
+
+impl Serialize for UncheckedSplitBetween {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (account_id, numerator) in self.0.iter() {
+            map.serialize_entry(account_id, numerator)?;
+        }
+        map.end()
+    }
+}
+
+/// Unchecked pre-image of `RoyaltyArgs`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct UncheckedRoyaltyArgs {
+    pub split_between: UncheckedSplitBetween,
+    pub percentage: u32,
+}
+
+/// The outcome of validating royalty and split arguments before minting.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ValidationReport {
+    pub valid: bool,
+    /// Human-readable description of each problem found.
+    pub errors: Vec<String>,
+}
+
+impl ValidationReport {
+    /// Check `royalty_args` and `split_between` against the rules enforced
+    /// by `Royalty::new` and `SplitOwners::new`, collecting all problems
+    /// instead of panicking on the first one.
+    pub fn new(
+        royalty_args: &Option<UncheckedRoyaltyArgs>,
+        split_between: &Option<UncheckedSplitBetween>,
+    ) -> Self {
+        let mut report = Self {
+            valid: true,
+            errors: vec![],
+        };
+        if let Some(royalty_args) = royalty_args {
+            if royalty_args.percentage == 0 {
+                report.add_error("royalty: percentage cannot be zero".to_string());
+            }
+            if royalty_args.percentage > ROYALTY_UPPER_LIMIT {
+                report.add_error(format!(
+                    "royalty: percentage {} must be <= {}",
+                    royalty_args.percentage, ROYALTY_UPPER_LIMIT
+                ));
+            }
+            report.check_split_between("royalty", &royalty_args.split_between, 1, 1);
+        }
+        if let Some(split_between) = split_between {
+            report.check_split_between("split_between", split_between, 2, 0);
+        }
+
+        let roy_len = royalty_args
+            .as_ref()
+            .map(|royalty_args| royalty_args.split_between.0.len())
+            .unwrap_or(0);
+        let split_len = split_between
+            .as_ref()
+            .map(|split_between| split_between.0.len())
+            .unwrap_or(1);
+        if (roy_len + split_len) as u32 > MAX_LEN_PAYOUT {
+            report.add_error(format!(
+                "payout too long: {} > {}",
+                roy_len + split_len,
+                MAX_LEN_PAYOUT
+            ));
+        }
+        report
+    }
+
+    pub fn add_error(
+        &mut self,
+        error: String,
+    ) {
+        self.valid = false;
+        self.errors.push(error);
+    }
+
+    fn check_split_between(
+        &mut self,
+        name: &str,
+        split_between: &UncheckedSplitBetween,
+        min_len: usize,
+        min_numerator: u32,
+    ) {
+        if split_between.0.len() < min_len {
+            self.add_error(format!("{}: needs at least {} accounts", name, min_len));
+        }
+        let mut seen = HashSet::new();
+        let mut sum: u64 = 0;
+        split_between.0.iter().for_each(|(account_id, numerator)| {
+            if AccountId::try_from(account_id.clone()).is_err() {
+                self.add_error(format!("{}: invalid account id {}", name, account_id));
+            }
+            if !seen.insert(account_id) {
+                self.add_error(format!("{}: duplicate account {}", name, account_id));
+            }
+            if *numerator < min_numerator || *numerator > 10_000 {
+                self.add_error(format!(
+                    "{}: {} not between {} and 10,000 for {}",
+                    name, numerator, min_numerator, account_id
+                ));
+            }
+            sum += *numerator as u64;
+        });
+        if sum != 10_000 {
+            self.add_error(format!("{}: sum not 10_000: {}", name, sum));
+        }
+    }
+}
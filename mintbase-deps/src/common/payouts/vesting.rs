@@ -0,0 +1,110 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::Balance;
+
+use crate::common::NearTime;
+use crate::utils::mul_div;
+
+/// Schedule by which the payouts of a royalty or split entry become
+/// withdrawable, e.g. for team allocations on primary sales. Nothing can be
+/// withdrawn before `cliff`, after which the accrued balance vests linearly
+/// from `start` until it is fully vested at `end`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct VestingSchedule {
+    pub start: NearTime,
+    pub cliff: NearTime,
+    pub end: NearTime,
+}
+
+impl VestingSchedule {
+    /// Validates that `start <= cliff <= end` and `start < end`.
+    pub fn new(
+        start: NearTime,
+        cliff: NearTime,
+        end: NearTime,
+    ) -> Self {
+        assert!(start.0 <= cliff.0, "cliff before start of vesting");
+        assert!(cliff.0 <= end.0, "cliff after end of vesting");
+        assert!(start.0 < end.0, "empty vesting period");
+        Self { start, cliff, end }
+    }
+
+    /// The part of `total` that has vested at time `now`.
+    pub fn vested_amount(
+        &self,
+        total: Balance,
+        now: &NearTime,
+    ) -> Balance {
+        if now.0 < self.cliff.0 {
+            return 0;
+        }
+        if now.0 >= self.end.0 {
+            return total;
+        }
+        let elapsed = (now.0 - self.start.0) as u128;
+        let period = (self.end.0 - self.start.0) as u128;
+        mul_div(total, elapsed, period)
+    }
+}
+
+/// Payouts that have been accrued for an account but not yet withdrawn,
+/// optionally subject to a `VestingSchedule`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct ClaimableBalance {
+    /// Sum of all payouts ever accrued.
+    pub accrued: U128,
+    /// Sum of all withdrawals.
+    pub withdrawn: U128,
+    pub schedule: Option<VestingSchedule>,
+}
+
+impl ClaimableBalance {
+    pub fn new(schedule: Option<VestingSchedule>) -> Self {
+        Self {
+            accrued: 0.into(),
+            withdrawn: 0.into(),
+            schedule,
+        }
+    }
+
+    /// Add a payout to the balance.
+    pub fn accrue(
+        &mut self,
+        amount: Balance,
+    ) {
+        self.accrued = (self.accrued.0 + amount).into();
+    }
+
+    /// The amount that may be withdrawn at time `now`.
+    pub fn claimable(
+        &self,
+        now: &NearTime,
+    ) -> Balance {
+        let vested = match self.schedule {
+            Some(ref schedule) => schedule.vested_amount(self.accrued.0, now),
+            None => self.accrued.0,
+        };
+        vested.saturating_sub(self.withdrawn.0)
+    }
+
+    /// Mark everything claimable at time `now` as withdrawn, and return the
+    /// withdrawn amount.
+    pub fn withdraw(
+        &mut self,
+        now: &NearTime,
+    ) -> Balance {
+        let amount = self.claimable(now);
+        self.withdrawn = (self.withdrawn.0 + amount).into();
+        amount
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::BTreeSet;
+use std::ops::Bound;
+
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::Balance;
+
+/// A `TokenListing` as it appears in a `PriceIndex`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ListingPrice {
+    /// The `TokenListing::get_list_id` of the listing.
+    pub list_id: String,
+    /// The asking price of the listing.
+    pub price: U128,
+}
+
+/// Listings of a single `Store`, sorted by their asking price. Allows the
+/// Marketplace to serve order-book style views like `floor_price` and
+/// `listings_by_price` without iterating all of its listings.
+///
+/// Listings with the same price are ordered by their `list_id`.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct PriceIndex {
+    entries: BTreeSet<(Balance, String)>,
+}
+
+impl PriceIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a listing to the index. Returns false if it was already present.
+    pub fn insert(
+        &mut self,
+        list_id: String,
+        price: Balance,
+    ) -> bool {
+        self.entries.insert((price, list_id))
+    }
+
+    /// Remove a listing from the index. As the index is keyed by price, the
+    /// price under which the listing was inserted is required. Returns false
+    /// if the listing wasn't present.
+    pub fn remove(
+        &mut self,
+        list_id: &str,
+        price: Balance,
+    ) -> bool {
+        self.entries.remove(&(price, list_id.to_string()))
+    }
+
+    /// Move a listing to a new price, e.g. when its owner changes the asking
+    /// price.
+    pub fn update(
+        &mut self,
+        list_id: String,
+        old_price: Balance,
+        new_price: Balance,
+    ) {
+        self.remove(&list_id, old_price);
+        self.insert(list_id, new_price);
+    }
+
+    /// Number of listings in the index.
+    pub fn len(&self) -> u64 {
+        self.entries.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The lowest asking price, if there are any listings.
+    pub fn floor_price(&self) -> Option<U128> {
+        self.entries.iter().next().map(|(price, _)| (*price).into())
+    }
+
+    /// Up to `limit` listings with an asking price of at least `from_price`,
+    /// in ascending order of their price.
+    pub fn listings_by_price(
+        &self,
+        from_price: Balance,
+        limit: usize,
+    ) -> Vec<ListingPrice> {
+        self.entries
+            .range((
+                Bound::Included((from_price, String::new())),
+                Bound::Unbounded,
+            ))
+            .take(limit)
+            .map(|(price, list_id)| ListingPrice {
+                list_id: list_id.clone(),
+                price: (*price).into(),
+            })
+            .collect()
+    }
+}
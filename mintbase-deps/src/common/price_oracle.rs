@@ -0,0 +1,104 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::{
+    AccountId,
+    Balance,
+};
+
+use crate::common::time::{
+    now,
+    NearTime,
+};
+
+/// yoctoNEAR per NEAR.
+const YOCTO_PER_NEAR: Balance = 10u128.pow(24);
+
+/// The price-oracle account registered by the Marketplace owner, from which
+/// exchange rates are fetched and cached.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct PriceOracleConfig {
+    pub oracle_id: AccountId,
+    /// Cached rates older than this many nanoseconds are not used to
+    /// display prices.
+    pub max_rate_age: u64,
+}
+
+/// Exchange rate of NEAR into `currency` as reported by the price oracle.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct OracleRate {
+    /// Currency code, e.g. "USD".
+    pub currency: String,
+    /// Price of one NEAR in the smallest unit of `currency`.
+    pub near_price: U128,
+    /// Number of decimals of the smallest unit of `currency`, e.g. 2 for
+    /// cents.
+    pub decimals: u8,
+    /// Time at which the oracle reported the rate.
+    pub updated_at: NearTime,
+}
+
+impl OracleRate {
+    /// Convert `amount` of yoctoNEAR into the smallest unit of `currency`,
+    /// rounding down.
+    pub fn convert(
+        &self,
+        amount: Balance,
+    ) -> Balance {
+        let near_price = self.near_price.0;
+        match amount.checked_mul(near_price) {
+            Some(product) => product / YOCTO_PER_NEAR,
+            // loses precision below 1 milliNEAR, only for huge amounts
+            None => amount / 10u128.pow(21) * near_price / 1000,
+        }
+    }
+
+    /// Check that the rate is recent enough for `config`.
+    pub fn is_fresh(
+        &self,
+        config: &PriceOracleConfig,
+    ) -> bool {
+        now().0.saturating_sub(self.updated_at.0) <= config.max_rate_age
+    }
+}
+
+/// The price of a `TokenListing` in another currency, as returned by the
+/// `listing_price_in` view of the Marketplace.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ListingPriceIn {
+    pub list_id: String,
+    /// Asking price in yoctoNEAR.
+    pub price: U128,
+    pub currency: String,
+    /// Asking price in the smallest unit of `currency`.
+    pub converted_price: U128,
+    pub decimals: u8,
+    /// Time at which the oracle reported the rate used for the conversion.
+    pub rate_updated_at: NearTime,
+}
+
+impl ListingPriceIn {
+    pub fn new(
+        list_id: String,
+        price: Balance,
+        rate: &OracleRate,
+    ) -> Self {
+        Self {
+            list_id,
+            price: price.into(),
+            currency: rate.currency.clone(),
+            converted_price: rate.convert(price).into(),
+            decimals: rate.decimals,
+            rate_updated_at: rate.updated_at.clone(),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt;
+
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::common::NearTime;
+
+/// Length of the sliding window in which activities are counted.
+const HOUR_NS: u64 = 3600 * 10u64.pow(9);
+
+/// Per-account limits on store activity within any hour. `None` means
+/// unlimited.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct RateLimits {
+    /// Maximum number of tokens minted per hour.
+    pub mints_per_hour: Option<u32>,
+    /// Maximum number of approvals granted per hour.
+    pub approvals_per_hour: Option<u32>,
+}
+
+/// An activity that is subject to `RateLimits`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Activity {
+    Mint,
+    Approval,
+}
+
+/// Error returned when an activity would exceed its rate limit.
+#[derive(Clone, Debug)]
+pub enum RateLimitError {
+    MintsPerHourExceeded { limit: u32 },
+    ApprovalsPerHourExceeded { limit: u32 },
+}
+
+impl fmt::Display for RateLimitError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        match self {
+            Self::MintsPerHourExceeded { limit } => {
+                write!(f, "rate limit exceeded: {} mints per hour", limit)
+            },
+            Self::ApprovalsPerHourExceeded { limit } => {
+                write!(f, "rate limit exceeded: {} approvals per hour", limit)
+            },
+        }
+    }
+}
+
+/// The activities of an account within the last hour, as
+/// `(timestamp, count)` pairs, oldest first.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct ActivityWindow {
+    pub mints: Vec<(u64, u32)>,
+    pub approvals: Vec<(u64, u32)>,
+}
+
+impl ActivityWindow {
+    /// Record `count` activities of kind `activity` at time `now`, after
+    /// dropping all entries that left the sliding window. Fails without
+    /// recording if the activities would exceed the limit in `limits`.
+    pub fn record(
+        &mut self,
+        activity: Activity,
+        count: u32,
+        limits: &RateLimits,
+        now: &NearTime,
+    ) -> Result<(), RateLimitError> {
+        let (entries, limit) = match activity {
+            Activity::Mint => (&mut self.mints, limits.mints_per_hour),
+            Activity::Approval => (&mut self.approvals, limits.approvals_per_hour),
+        };
+        let window_start = now.0.saturating_sub(HOUR_NS);
+        entries.retain(|&(timestamp, _)| timestamp > window_start);
+
+        if let Some(limit) = limit {
+            let in_window: u64 = entries.iter().map(|&(_, n)| n as u64).sum();
+            if in_window + count as u64 > limit as u64 {
+                return Err(match activity {
+                    Activity::Mint => RateLimitError::MintsPerHourExceeded { limit },
+                    Activity::Approval => RateLimitError::ApprovalsPerHourExceeded { limit },
+                });
+            }
+        }
+        entries.push((now.0, count));
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mints.is_empty() && self.approvals.is_empty()
+    }
+}
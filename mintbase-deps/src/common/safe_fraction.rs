@@ -115,4 +115,18 @@ impl MultipliedSafeFraction {
     ) -> Balance {
         value / 100_000_000u128 * self.numerator as u128
     }
+
+    /// Fractionalize an amount of a fungible token. Unlike
+    /// `multiply_balance`, this multiplies before dividing whenever that
+    /// cannot overflow, as tokens with few decimals would otherwise be
+    /// truncated to zero (1 USDC is only 10^6 units).
+    pub fn multiply_amount(
+        &self,
+        value: Balance,
+    ) -> Balance {
+        match value.checked_mul(self.numerator as u128) {
+            Some(product) => product / 100_000_000u128,
+            None => self.multiply_balance(value),
+        }
+    }
 }
@@ -3,6 +3,7 @@ use near_sdk::serde::{
     Deserialize,
     Serialize,
 };
+use near_sdk::AccountId;
 
 /// ref: https://github.com/near-apps/nft-market/blob/main/contracts/market-simple/src/lib.rs#L54
 #[derive(Serialize, Deserialize)]
@@ -10,3 +11,15 @@ pub struct SaleArgs {
     pub price: U128,
     pub autotransfer: bool,
 }
+
+/// Listing that a `Store` passes on to a market as `msg` when listing tokens
+/// via `list_on_market`.
+#[derive(Serialize, Deserialize)]
+pub struct ListingIntent {
+    /// Price of each token.
+    pub price: U128,
+    /// The fungible token contract in which `price` is denominated, or
+    /// `None` for NEAR.
+    pub ft_contract: Option<AccountId>,
+    pub autotransfer: bool,
+}
@@ -0,0 +1,69 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::common::NearTime;
+
+/// Who may buy during a `SalePhase`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub enum SalePhaseKind {
+    Team,
+    Allowlist,
+    Public,
+}
+
+/// A time window of a primary sale with its own price and cap.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct SalePhase {
+    pub kind: SalePhaseKind,
+    /// Price per token during this phase.
+    pub price: U128,
+    /// Maximum number of tokens sold during this phase.
+    pub cap: u64,
+    pub start: NearTime,
+    pub end: NearTime,
+}
+
+/// Ordered, non-overlapping `SalePhase`s. The current phase is determined
+/// by timestamp alone, so no owner intervention is required between phases.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct SalePhases {
+    pub phases: Vec<SalePhase>,
+}
+
+impl SalePhases {
+    /// Validates that each phase has a non-empty window, and that the
+    /// phases are ordered and do not overlap.
+    pub fn new(phases: Vec<SalePhase>) -> Self {
+        phases.iter().for_each(|phase| {
+            assert!(phase.start.0 < phase.end.0, "empty sale phase window");
+        });
+        phases.windows(2).for_each(|pair| {
+            assert!(
+                pair[0].end.0 <= pair[1].start.0,
+                "sale phases unordered or overlapping"
+            );
+        });
+        Self { phases }
+    }
+
+    /// The phase whose window contains `now`, if any.
+    pub fn current_phase(
+        &self,
+        now: &NearTime,
+    ) -> Option<&SalePhase> {
+        self.phases
+            .iter()
+            .find(|phase| phase.start.0 <= now.0 && now.0 < phase.end.0)
+    }
+}
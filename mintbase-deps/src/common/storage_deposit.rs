@@ -0,0 +1,39 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::{
+    AccountId,
+    Balance,
+};
+
+/// A storage deposit taken for an entry on a `Store`, which is refunded to
+/// `account_id` once the entry is removed. Recording the amount ensures that
+/// the refund matches what was paid, even if the storage price changed in
+/// the meantime.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct StorageDeposit {
+    /// The account that paid the deposit.
+    pub account_id: AccountId,
+    /// The amount that was paid.
+    pub amount: U128,
+}
+
+impl StorageDeposit {
+    pub fn new(
+        account_id: AccountId,
+        amount: Balance,
+    ) -> Self {
+        Self {
+            account_id,
+            amount: amount.into(),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+use crate::common::{
+    FeeHookConfig,
+    MintWindow,
+    RateLimits,
+    RoyaltyArgs,
+    TransferPolicy,
+};
+
+/// Selects which parts of a `Store` are copied when cloning it via the
+/// `Factory`. Tokens are never copied.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StoreCloneInclude {
+    /// Copy the accounts that are allowed to mint.
+    pub minters: bool,
+    /// Copy the owner-managed settings of the `Store`.
+    pub config: bool,
+}
+
+/// Owner-managed settings of a `Store` that can be copied to a clone. Each
+/// field mirrors the `Store` field of the same name.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct StoreCloneConfig {
+    pub allow_moves: bool,
+    pub attestors: Vec<AccountId>,
+    pub minting_fee: U128,
+    pub mint_window: Option<MintWindow>,
+    pub rate_limits: RateLimits,
+    pub default_royalty: Option<RoyaltyArgs>,
+    pub max_royalty_bps: Option<u32>,
+    pub transfer_fee: U128,
+    /// Policies restricting receivers to a list are not copied, as the
+    /// list itself cannot be enumerated.
+    pub transfer_policy: Option<TransferPolicy>,
+    pub payout_dust_threshold: U128,
+    pub max_compose_depth: u32,
+    pub minter_cooling_off_hours: u64,
+    pub lock_timeout_minutes: u64,
+    pub mint_event_metadata: bool,
+    pub aggregate_child_royalties: bool,
+    pub fee_hook: Option<FeeHookConfig>,
+    pub royalty_registry: Option<AccountId>,
+}
+
+/// State of an existing `Store` that is used to initialize a clone.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct StoreCloneArgs {
+    /// Owner of the `Store` that is being cloned.
+    pub owner_id: AccountId,
+    pub minters: Option<Vec<AccountId>>,
+    pub config: Option<StoreCloneConfig>,
+}
@@ -0,0 +1,31 @@
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// Reasons for which an operation on a `Store` may fail, reported by
+/// methods that don't abort on the failure of a single item.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StoreError {
+    TokenNotFound,
+    TokenLoaned,
+    TokenLocked,
+    TokenFrozen,
+    /// The token is held in escrow for the receiver of a transfer offer.
+    TokenOffered,
+    TokenSoulbound,
+    /// The token expired and may only be burned.
+    TokenExpired,
+    /// The token requires its co-signer to approve transfers.
+    CoSignerRequired,
+    /// The caller neither owns the token nor holds a matching approval.
+    NotApproved,
+    /// The approval of the caller expired.
+    ApprovalExpired,
+    TransferToSelf,
+    /// The transfer policy of the `Store` rejects the receiver.
+    ReceiverRestricted,
+    /// The transfer policy hook of the `Store` requires `nft_transfer`.
+    TransferHookRequired,
+}
@@ -9,11 +9,16 @@ use near_sdk::serde::{
 };
 use near_sdk::AccountId;
 
-use crate::common::NFTContractMetadata;
+use crate::common::{
+    NFTContractMetadata,
+    StoreCloneArgs,
+};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
 pub struct StoreInitArgs {
     pub metadata: NFTContractMetadata,
     pub owner_id: AccountId,
+    /// Set if the `Store` is created as a clone of an existing `Store`.
+    pub clone_args: Option<StoreCloneArgs>,
 }
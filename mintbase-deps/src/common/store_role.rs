@@ -0,0 +1,45 @@
+use std::fmt;
+
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// Privileges that the store owner may delegate to other accounts.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+#[serde(rename_all = "snake_case")]
+pub enum StoreRole {
+    /// May mint tokens, but without royalties or split owners.
+    MintOnly,
+    /// May mint tokens, including royalties and split owners. This is what
+    /// `grant_minter` grants.
+    MintWithRoyalty,
+    /// May change the `base_uri` and icon of the `Store`.
+    MetadataEditor,
+}
+
+impl StoreRole {
+    /// Whether the role allows minting.
+    pub fn is_minting(&self) -> bool {
+        matches!(self, Self::MintOnly | Self::MintWithRoyalty)
+    }
+}
+
+impl fmt::Display for StoreRole {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        match self {
+            Self::MintOnly => write!(f, "mint_only"),
+            Self::MintWithRoyalty => write!(f, "mint_with_royalty"),
+            Self::MetadataEditor => write!(f, "metadata_editor"),
+        }
+    }
+}
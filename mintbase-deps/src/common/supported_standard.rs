@@ -0,0 +1,26 @@
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// A NEP implemented by a contract, allowing integrators to feature-detect
+/// instead of probing methods.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SupportedStandard {
+    /// Name of the standard as used in NEP-297 events, e.g. "nep171".
+    pub standard: String,
+    /// Implemented version of the standard.
+    pub version: String,
+}
+
+impl SupportedStandard {
+    pub fn new(
+        standard: &str,
+        version: &str,
+    ) -> Self {
+        Self {
+            standard: standard.to_string(),
+            version: version.to_string(),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::Balance;
+
+/// Pool from which the Marketplace pays callers of the permissionless
+/// `sweep_expired_offers` for each expired offer they refund, keeping
+/// escrow balances from accumulating without relying on the operator.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct SweepBountyPool {
+    /// Bounty paid per refunded offer.
+    pub bounty_per_offer: U128,
+    /// Remaining funds of the pool.
+    pub balance: U128,
+}
+
+impl SweepBountyPool {
+    pub fn new(bounty_per_offer: U128) -> Self {
+        Self {
+            bounty_per_offer,
+            balance: 0.into(),
+        }
+    }
+
+    /// Add funds to the pool.
+    pub fn fund(
+        &mut self,
+        amount: Balance,
+    ) {
+        self.balance = (self.balance.0 + amount).into();
+    }
+
+    /// Take the bounty for `num_swept` refunded offers out of the pool. If
+    /// the pool is insufficient, the remaining funds are paid out.
+    pub fn take_bounty(
+        &mut self,
+        num_swept: u64,
+    ) -> Balance {
+        let bounty = self
+            .bounty_per_offer
+            .0
+            .saturating_mul(num_swept as u128)
+            .min(self.balance.0);
+        self.balance = (self.balance.0 - bounty).into();
+        bounty
+    }
+}
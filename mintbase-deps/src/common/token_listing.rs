@@ -89,4 +89,22 @@ impl TokenListing {
     pub fn assert_not_locked(&self) {
         assert!(!self.locked);
     }
+
+    /// Remove the current `Offer` if it has expired, returning it such that
+    /// the offerer can be refunded. Locked listings are never swept.
+    pub fn take_expired_offer(&mut self) -> Option<TokenOffer> {
+        match self.current_offer {
+            Some(ref offer) if !self.locked && !offer.is_active() => self.current_offer.take(),
+            _ => None,
+        }
+    }
+
+    /// Restore the listing to an active state after its settlement failed,
+    /// e.g. because the `Store` panicked after the funds were taken. The
+    /// token is unlocked, and the `Offer` that triggered the settlement is
+    /// removed and returned, such that the buyer can be refunded.
+    pub fn restore_after_failed_settlement(&mut self) -> Option<TokenOffer> {
+        self.locked = false;
+        self.current_offer.take()
+    }
 }
@@ -9,6 +9,8 @@ use near_sdk::serde::{
     Serialize,
 };
 
+use crate::common::Upgrade;
+
 // NON-COMPLIANT https://github.com/near/NEPs/blob/master/specs/Standards/NonFungibleToken/Metadata.md
 /// ref:
 /// https://github.com/near/NEPs/blob/master/specs/Standards/NonFungibleToken/Metadata.md
@@ -38,6 +40,18 @@ pub struct TokenMetadata {
     /// Base64-encoded sha256 hash of JSON from reference field. Required if
     /// `reference` is included.
     pub reference_hash: Option<Base64VecU8>,
+    /// Size of the media referenced by the `media` field in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_size_bytes: Option<u64>,
+    /// Width of the media referenced by the `media` field in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_width: Option<u32>,
+    /// Height of the media referenced by the `media` field in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_height: Option<u32>,
+    /// URL to an animated or interactive rendition of the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub animation_url: Option<String>,
 }
 
 impl TokenMetadata {
@@ -65,6 +79,10 @@ impl TokenMetadata {
             extra: args.extra,
             reference: args.reference,
             reference_hash: args.reference_hash,
+            media_size_bytes: args.media_size_bytes,
+            media_width: args.media_width,
+            media_height: args.media_height,
+            animation_url: args.animation_url,
         };
 
         let size = serde_json::to_vec(&metadata).unwrap().len();
@@ -73,6 +91,75 @@ impl TokenMetadata {
 
         (metadata, size as u64)
     }
+
+    /// Layout hints for the media of this token, if any were provided.
+    pub fn media_hints(&self) -> Option<MediaHints> {
+        let hints = MediaHints {
+            media_size_bytes: self.media_size_bytes,
+            media_width: self.media_width,
+            media_height: self.media_height,
+            animation_url: self.animation_url.clone(),
+        };
+        if hints.media_size_bytes.is_none()
+            && hints.media_width.is_none()
+            && hints.media_height.is_none()
+            && hints.animation_url.is_none()
+        {
+            None
+        } else {
+            Some(hints)
+        }
+    }
+}
+
+/// Hints that allow galleries to lay out grids and pick renderers without
+/// fetching the media of a token.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MediaHints {
+    pub media_size_bytes: Option<u64>,
+    pub media_width: Option<u32>,
+    pub media_height: Option<u32>,
+    pub animation_url: Option<String>,
+}
+
+/// The borsh layout of `TokenMetadata` before media hints were introduced.
+/// Metadata stored before is kept in this layout until written again.
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct LegacyTokenMetadata {
+    title: Option<String>,
+    description: Option<String>,
+    media: Option<String>,
+    media_hash: Option<Base64VecU8>,
+    copies: Option<u16>,
+    expires_at: Option<String>,
+    starts_at: Option<String>,
+    extra: Option<String>,
+    reference: Option<String>,
+    reference_hash: Option<Base64VecU8>,
+}
+
+/// Metadata is stored along with the number of remaining copies.
+impl Upgrade<(u16, TokenMetadata)> for (u16, LegacyTokenMetadata) {
+    fn upgrade(self) -> (u16, TokenMetadata) {
+        let (count, metadata) = self;
+        let metadata = TokenMetadata {
+            title: metadata.title,
+            description: metadata.description,
+            media: metadata.media,
+            media_hash: metadata.media_hash,
+            copies: metadata.copies,
+            expires_at: metadata.expires_at,
+            starts_at: metadata.starts_at,
+            extra: metadata.extra,
+            reference: metadata.reference,
+            reference_hash: metadata.reference_hash,
+            media_size_bytes: None,
+            media_width: None,
+            media_height: None,
+            animation_url: None,
+        };
+        (count, metadata)
+    }
 }
 
 // NON-COMPLIANT https://github.com/near/NEPs/blob/master/specs/Standards/NonFungibleToken/Metadata.md
@@ -107,4 +194,16 @@ pub struct TokenMetadataCompliant {
     /// Base64-encoded sha256 hash of JSON from reference field. Required if
     /// `reference` is included.
     pub reference_hash: Option<Base64VecU8>,
+    /// Size of the media referenced by the `media` field in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_size_bytes: Option<u64>,
+    /// Width of the media referenced by the `media` field in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_width: Option<u32>,
+    /// Height of the media referenced by the `media` field in pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_height: Option<u32>,
+    /// URL to an animated or interactive rendition of the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub animation_url: Option<String>,
 }
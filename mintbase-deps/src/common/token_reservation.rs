@@ -0,0 +1,38 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+/// A contiguous range of token ids reserved by a minter, such that media and
+/// metadata keyed by token id can be prepared before the tokens are minted.
+/// The reserved tokens are minted in order, and don't exist until minted.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct TokenReservation {
+    /// The account that reserved the range, and that may mint into it.
+    pub minter: AccountId,
+    /// The first reserved token id, which also identifies the reservation.
+    pub first_token_id: u64,
+    /// The last reserved token id.
+    pub last_token_id: u64,
+    /// The number of reserved tokens minted so far.
+    pub num_minted: u64,
+}
+
+impl TokenReservation {
+    /// Number of reserved token ids that haven't been minted yet.
+    pub fn remaining(&self) -> u64 {
+        self.last_token_id - self.first_token_id + 1 - self.num_minted
+    }
+
+    /// Token id of the next token to be minted.
+    pub fn next_token_id(&self) -> u64 {
+        self.first_token_id + self.num_minted
+    }
+}
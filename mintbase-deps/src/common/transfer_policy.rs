@@ -0,0 +1,43 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+/// Restrictions on the receivers of token transfers, e.g. for regulated
+/// issuers of tickets or real-world assets. List policies refer to the
+/// transfer policy list of the `Store`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub enum TransferPolicy {
+    /// Listed accounts may not receive tokens.
+    Blocklist,
+    /// Only listed accounts may receive tokens.
+    Allowlist,
+    /// Each transfer must be approved by the contract `hook_id` via
+    /// `nft_check_transfer`. As this requires a cross-contract call, only
+    /// `nft_transfer` is available under this policy.
+    Hook { hook_id: AccountId },
+}
+
+impl TransferPolicy {
+    /// Whether an account may receive tokens, given whether it is on the
+    /// transfer policy list. Always true for `Hook`, which is checked
+    /// asynchronously instead.
+    pub fn allows(
+        &self,
+        listed: bool,
+    ) -> bool {
+        match self {
+            Self::Blocklist => !listed,
+            Self::Allowlist => listed,
+            Self::Hook { .. } => true,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use near_sdk::json_types::U64;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+/// A single transfer of `nft_batch_transfer_checked`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TransferSpec {
+    pub token_id: U64,
+    pub receiver_id: AccountId,
+    /// Required unless the caller owns the token.
+    pub approval_id: Option<u64>,
+}
@@ -0,0 +1,90 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::collections::LookupMap;
+
+/// Conversion of a value stored in a previous borsh layout into the current
+/// layout.
+pub trait Upgrade<T> {
+    fn upgrade(self) -> T;
+}
+
+/// A map keyed by token id, whose values have changed their borsh layout.
+/// Values written in the previous layout are kept under the legacy prefix
+/// and upgraded when read. Once written again, they are moved to the
+/// current layout, such that each value is migrated lazily.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct UpgradingMap<V, L> {
+    current: LookupMap<u64, V>,
+    legacy: LookupMap<u64, L>,
+    /// Only keys below this bound may have a value in the previous layout.
+    legacy_below: u64,
+}
+
+impl<V, L> UpgradingMap<V, L>
+where
+    V: BorshSerialize + BorshDeserialize,
+    L: BorshSerialize + BorshDeserialize + Upgrade<V>,
+{
+    /// Create the map. `legacy_below` is the number of keys that were in
+    /// use before the layout changed, zero for new maps.
+    pub fn new(
+        prefix: Vec<u8>,
+        legacy_prefix: Vec<u8>,
+        legacy_below: u64,
+    ) -> Self {
+        Self {
+            current: LookupMap::new(prefix),
+            legacy: LookupMap::new(legacy_prefix),
+            legacy_below,
+        }
+    }
+
+    pub fn get(
+        &self,
+        key: &u64,
+    ) -> Option<V> {
+        self.current.get(key).or_else(|| {
+            self.may_be_legacy(key)
+                .then(|| self.legacy.get(key).map(Upgrade::upgrade))
+                .flatten()
+        })
+    }
+
+    pub fn contains_key(
+        &self,
+        key: &u64,
+    ) -> bool {
+        self.current.contains_key(key) || (self.may_be_legacy(key) && self.legacy.contains_key(key))
+    }
+
+    pub fn insert(
+        &mut self,
+        key: &u64,
+        value: &V,
+    ) {
+        self.current.insert(key, value);
+        if self.may_be_legacy(key) {
+            self.legacy.remove(key);
+        }
+    }
+
+    pub fn remove(
+        &mut self,
+        key: &u64,
+    ) {
+        self.current.remove(key);
+        if self.may_be_legacy(key) {
+            self.legacy.remove(key);
+        }
+    }
+
+    fn may_be_legacy(
+        &self,
+        key: &u64,
+    ) -> bool {
+        *key < self.legacy_below
+    }
+}
@@ -23,6 +23,10 @@ pub const ONE_YOCTO: Balance = 1;
 /// ref: https://github.com/near/core-contracts/blob/master/staking-pool/src/lib.rs#L26
 pub const NO_DEPOSIT: Balance = 0;
 
+/// Minimum deposit for applying to become a minter, deterring spam. It also
+/// covers the storage of the application.
+pub const MINTER_APPLICATION_DEPOSIT: Balance = 10u128.pow(23);
+
 /// This module holds gas costs for common operations
 pub mod gas {
     use near_sdk::Gas;
@@ -31,6 +35,26 @@ pub mod gas {
         Gas(n * 10u64.pow(12))
     }
 
+    /// Gas requirements for a store method without cross contract calls.
+    pub const BASE_CALL: Gas = tgas(30);
+
+    /// Gas requirements for minting the maximum of 125 tokens.
+    pub const NFT_BATCH_MINT: Gas = tgas(200);
+
+    /// Gas requirements for verifying a `MintVoucher` signature and minting
+    /// the token.
+    pub const NFT_REDEEM_VOUCHER: Gas = tgas(100);
+
+    /// Gas requirements for verifying a `MintRequest` signature and minting
+    /// the maximum of 125 tokens.
+    pub const NFT_MINT_SIGNED: Gas = tgas(250);
+
+    /// Gas requirements for minting a copy of an open edition.
+    pub const NFT_MINT_EDITION: Gas = tgas(50);
+
+    /// Gas requirements for minting a token from a mint pool.
+    pub const NFT_MINT_FROM_POOL: Gas = tgas(50);
+
     /// Gas requirements for resolving a payout struct.
     pub const PAYOUT_RESOLVE: Gas = tgas(30);
 
@@ -43,15 +67,78 @@ pub mod gas {
     /// Gas requirements for
     pub const ON_CREATE_CALLBACK: Gas = tgas(10);
 
+    /// Gas requirements for retrieving the state of a `Store` to clone.
+    pub const GET_CLONE_ARGS: Gas = tgas(10);
+
+    /// Gas requirements for creating a store from the state of a `Store` to
+    /// clone, including the creation callback.
+    pub const ON_CLONE_ARGS_CALLBACK: Gas = tgas(65 + 5 + 10 + 20);
+
+    /// Gas requirements for retrieving a `MigrationCertificate` from the
+    /// origin `Store`.
+    pub const GET_MIGRATION_CERTIFICATE: Gas = tgas(10);
+
+    /// Gas requirements for re-minting a migrated token.
+    pub const ON_MIGRATION_CERTIFICATE: Gas = tgas(30);
+
+    /// Gas requirements for querying a royalty from the royalty registry.
+    pub const GET_REGISTRY_ROYALTY: Gas = tgas(10);
+
+    /// Gas requirements for caching a royalty from the royalty registry.
+    pub const ON_REGISTRY_ROYALTY: Gas = tgas(15);
+
+    /// Gas requirements for querying the fee legs from a fee hook.
+    pub const NFT_FEE_LEGS: Gas = tgas(10);
+
+    /// Gas requirements for adding the fee legs to a payout.
+    pub const ON_FEE_LEGS: Gas = tgas(10);
+
+    /// Gas requirements for querying a transfer policy hook.
+    pub const NFT_CHECK_TRANSFER: Gas = tgas(10);
+
+    /// Gas requirements for completing a transfer approved by the transfer
+    /// policy hook.
+    pub const ON_CHECK_TRANSFER: Gas = tgas(15);
+
+    /// Gas requirements for querying the parent token of a cross-compose on
+    /// its contract.
+    pub const NFT_TOKEN: Gas = tgas(10);
+
+    /// Gas requirements for completing a cross-compose or -uncompose once
+    /// the parent token has been verified.
+    pub const ON_CROSS_COMPOSE: Gas = tgas(20);
+
     /// Gas requirements for
     pub const NFT_BATCH_APPROVE: Gas = tgas(100);
 
+    /// Gas requirements for minting and listing the maximum of 70 tokens,
+    /// including the listing callback.
+    pub const NFT_BATCH_MINT_AND_LIST: Gas = tgas(300);
+
+    /// Gas requirements for rolling back the approvals of a rejected
+    /// `nft_batch_mint_and_list` listing.
+    pub const ON_MINT_AND_LIST: Gas = tgas(20);
+
     // ref: https://github.com/near-apps/nft-market/blob/main/contracts/nft-simple/src/nft_core.rs
     /// Gas requirements for resolving a `nft_transfer_call` XCC
     pub const RESOLVE_TRANSFER: Gas = tgas(10);
 
-    /// Gas requirements for `nft_transfer_call`
-    pub const NFT_TRANSFER_CALL: Gas = tgas(35);
+    /// Gas forwarded to the receiver of `nft_transfer_call`, unless the
+    /// caller specifies `gas_for_receiver`.
+    pub const NFT_ON_TRANSFER: Gas = tgas(35);
+
+    /// Gas reserved by `nft_transfer_call` for resolving the transfer.
+    pub const RESOLVE_TRANSFER_CALL: Gas = tgas(35);
+
+    /// Maximum gas that `nft_transfer_call` may forward to the receiver, such
+    /// that enough gas remains for resolving the transfer.
+    pub const NFT_ON_TRANSFER_MAX: Gas = tgas(200);
+
+    /// Gas forwarded to the receiver of `nft_batch_transfer_call`.
+    pub const NFT_ON_BATCH_TRANSFER: Gas = tgas(50);
+
+    /// Gas requirements for resolving a `nft_batch_transfer_call` XCC
+    pub const RESOLVE_BATCH_TRANSFER: Gas = tgas(100);
 
     /// Gas requirements for `nft_transfer_call`
     pub const NFT_ON_APPROVE: Gas = tgas(25);
@@ -73,6 +160,13 @@ pub mod storage_bytes {
     /// - an entry in the `tokens_per_account` map
     /// - an entry in the `composeables` map
     pub const COMMON: StorageUsage = 80;
+
+    /// Storage bytes for the `MintBatch` record created by each mint,
+    /// including its index by first token id.
+    pub const MINT_BATCH: StorageUsage = 420;
+
+    /// Storage bytes for a single `Attestation` on a token.
+    pub const ATTESTATION: StorageUsage = 300;
 }
 
 pub mod storage_stake {
@@ -99,6 +193,12 @@ pub mod storage_stake {
     /// - adding a new entry to the `composeables` map
     pub const COMMON: Balance = bytes_to_stake(super::storage_bytes::COMMON);
 
+    /// Storage stake required for the `MintBatch` record created by each mint.
+    pub const MINT_BATCH: Balance = bytes_to_stake(super::storage_bytes::MINT_BATCH);
+
+    /// Storage stake required for a single `Attestation` on a token.
+    pub const ATTESTATION: Balance = bytes_to_stake(super::storage_bytes::ATTESTATION);
+
     /// Require 0.1 NEAR of storage stake to remain unused.
     pub const CUSHION: Balance = 10u128.pow(23);
 }
@@ -113,9 +213,37 @@ pub mod storage_stake {
 /// Royalty upper limit is 50%.
 pub const ROYALTY_UPPER_LIMIT: u32 = 5000;
 
+/// Fee hook upper limit is 20%.
+pub const FEE_HOOK_UPPER_LIMIT: u32 = 2000;
+
 /// Maximum payout (royalties + splits) participants to process
 pub const MAX_LEN_PAYOUT: u32 = 50;
 
+/// Maximum number of composed parents that are walked to find the root owner
+/// of a token, and thus the upper bound of a store's `max_compose_depth`.
+pub const MAX_COMPOSE_DEPTH: u32 = 10;
+
+/// Maximum number of hours a token may be held in escrow for a transfer
+/// offer, during which its owner may not cancel the offer.
+pub const MAX_TRANSFER_OFFER_HOURS: u64 = 30 * 24;
+
+/// Maximum length in bytes of the memo of a gift. As each gift escrows a
+/// token of its sender, this bounds the storage the `Store` covers for it.
+pub const MAX_GIFT_MEMO_LEN: usize = 256;
+
+/// Maximum number of bids retained in the history of an auction listing.
+pub const MAX_BID_HISTORY: u64 = 100;
+
+/// Maximum number of attestations that can be attached to a single token.
+pub const MAX_ATTESTATIONS_PER_TOKEN: usize = 20;
+
+/// Maximum number of events retained in the event journal of a store.
+pub const MAX_EVENT_JOURNAL_CAPACITY: u64 = 10_000;
+
+/// Maximum length of the JSON metadata embedded in a mint event. Leaves
+/// room for other logs within the 16 KiB log limit of a receipt.
+pub const MAX_EVENT_METADATA_LEN: usize = 8192;
+
 // pub const MINIMUM_CUSHION: near_sdk::Balance = 5 * 10u128.pow(23);
 
 //?
@@ -150,16 +278,20 @@ pub struct StorageCosts {
     /// - adding a new entry to the `composeables` map
     pub common: u128,
     pub token: u128,
+    /// The `MintBatch` record created by each mint as a Near price.
+    pub mint_batch: u128,
+    /// A single `Attestation` on a token as a Near price.
+    pub attestation: u128,
 }
 
 impl StorageCosts {
     pub fn new(storage_price_per_byte: u128) -> Self {
         Self {
             storage_price_per_byte,
-            // common: storage_price_per_byte * 80_u64 as u128,
-            common: storage_stake::COMMON,
-            // token: storage_price_per_byte * 360_u64 as u128,
-            token: storage_stake::TOKEN,
+            common: storage_price_per_byte * storage_bytes::COMMON as u128,
+            token: storage_price_per_byte * storage_bytes::TOKEN as u128,
+            mint_batch: storage_price_per_byte * storage_bytes::MINT_BATCH as u128,
+            attestation: storage_price_per_byte * storage_bytes::ATTESTATION as u128,
         }
     }
 }
@@ -15,7 +15,10 @@ mod market_interfaces {
         ext_contract,
     };
 
-    use crate::common::TokenListing;
+    use crate::common::{
+        OracleRate,
+        TokenListing,
+    };
 
     #[ext_contract(ext_self)]
     pub trait ExtSelf {
@@ -27,6 +30,17 @@ mod market_interfaces {
         ) -> Promise;
     }
 
+    /// Interface of the price oracle from which the Marketplace caches the
+    /// rates used by `listing_price_in`.
+    #[ext_contract(ext_price_oracle)]
+    pub trait PriceOracle {
+        /// The current rate of NEAR into `currency`.
+        fn get_near_rate(
+            &self,
+            currency: String,
+        ) -> OracleRate;
+    }
+
     #[ext_contract(nft_contract)]
     /// Impl of NEP-171. Note that the impl makes the assumption that `TokenId` has
     /// type `String`, where this contract internally uses `u64`, which is more
@@ -52,12 +66,22 @@ pub use store_interfaces::*;
 #[cfg(feature = "store-wasm")]
 #[allow(clippy::too_many_arguments)]
 mod store_interfaces {
-    use near_sdk::json_types::U64;
+    use std::collections::HashMap;
+
+    use near_sdk::json_types::{
+        U128,
+        U64,
+    };
     use near_sdk::{
         self,
         ext_contract,
     };
 
+    use crate::common::{
+        MigrationCertificate,
+        RoyaltyArgs,
+    };
+
     /// Non-Fungible Token Approval NEP 178. Ref:
     /// https://github.com/near/NEPs/blobß/master/specs/Standards/NonFungibleToken/ApprovalManagement.md
     #[ext_contract(ext_on_approve)]
@@ -120,6 +144,83 @@ mod store_interfaces {
             token_id: U64,
             msg: String,
         ) -> Promise;
+
+        /// Take some action after receiving multiple non-fungible tokens via
+        /// `nft_batch_transfer_call`.
+        ///
+        /// Arguments are those of `nft_on_transfer`, with `token_ids` listing
+        /// all transferred tokens.
+        ///
+        /// Returns a list with one boolean per token in `token_ids`, which is
+        /// true if that token should be returned to `previous_owner_id`.
+        fn nft_on_batch_transfer(
+            &mut self,
+            sender_id: AccountId,
+            previous_owner_id: AccountId,
+            token_ids: Vec<U64>,
+            msg: String,
+        ) -> Promise;
+    }
+
+    /// Contracts that can be configured as fee hook of a `Store`.
+    #[ext_contract(ext_fee_hook)]
+    pub trait FeeHook {
+        /// Return the additional fee legs for a sale of `token_id` at
+        /// `balance`, given the `payout` without fees.
+        fn nft_fee_legs(
+            &self,
+            token_id: U64,
+            balance: U128,
+            payout: HashMap<AccountId, U128>,
+        ) -> HashMap<AccountId, U128>;
+    }
+
+    /// Contracts that can be configured as transfer policy hook of a
+    /// `Store`.
+    #[ext_contract(ext_transfer_policy)]
+    pub trait TransferPolicyHook {
+        /// Return whether `token_id` may be transferred from `owner_id` to
+        /// `receiver_id`, as requested by `sender_id`.
+        fn nft_check_transfer(
+            &self,
+            token_id: U64,
+            sender_id: AccountId,
+            owner_id: AccountId,
+            receiver_id: AccountId,
+        ) -> bool;
+    }
+
+    /// NEP-171 contracts holding the parent tokens of cross-composed tokens.
+    #[ext_contract(ext_compose_parent)]
+    pub trait ComposeParent {
+        /// Get the token, `None` if it does not exist.
+        fn nft_token(
+            &self,
+            token_id: U64,
+        ) -> Option<crate::token::TokenCompliant>;
+    }
+
+    /// Retrieval of `MigrationCertificate`s from the `Store` a token is
+    /// migrated from.
+    #[ext_contract(ext_migration_origin)]
+    pub trait MigrationOrigin {
+        fn get_migration_certificate(
+            &self,
+            token_id: U64,
+        ) -> Option<MigrationCertificate>;
+    }
+
+    /// Shared on-chain registry to which a `Store` may defer royalty
+    /// resolution.
+    #[ext_contract(ext_royalty_registry)]
+    pub trait RoyaltyRegistry {
+        /// Return the royalty registered for the tokens of `nft_contract_id`
+        /// that share `lookup_id`, if any.
+        fn get_royalty(
+            &self,
+            nft_contract_id: AccountId,
+            lookup_id: U64,
+        ) -> Option<RoyaltyArgs>;
     }
 }
 
@@ -136,7 +237,11 @@ mod factory_interfaces {
         ext_contract,
     };
 
-    use crate::common::NFTContractMetadata;
+    use crate::common::{
+        NFTContractMetadata,
+        StoreCloneArgs,
+        StoreCloneInclude,
+    };
 
     #[ext_contract(factory_self)]
     pub trait OnCreateCallback {
@@ -148,6 +253,22 @@ mod factory_interfaces {
             store_account_id: AccountId,
             attached_deposit: U128,
         );
+
+        fn on_clone_args(
+            &mut self,
+            store_creator_id: AccountId,
+            metadata: NFTContractMetadata,
+            attached_deposit: U128,
+        ) -> Promise;
+    }
+
+    /// Views on an existing `Store` that the factory requires for cloning.
+    #[ext_contract(ext_store_clone)]
+    pub trait StoreClone {
+        fn get_clone_args(
+            &self,
+            include: StoreCloneInclude,
+        ) -> StoreCloneArgs;
     }
 }
 
@@ -2,6 +2,7 @@ pub mod common;
 pub mod constants;
 pub mod interfaces;
 pub mod logging;
+pub mod market;
 pub mod token;
 pub mod utils;
 
@@ -19,6 +20,7 @@ pub use crate::logging::{
     Nep171Event,
     Nep171EventLog,
     NftApproveLog,
+    NftAttestLog,
     NftBurnLog,
     NftComposeLog,
     NftListLog,
@@ -33,6 +35,7 @@ pub use crate::logging::{
     NftSetSplitOwnerLog,
     NftStoreCreateLog,
     NftStringLog,
+    NftSweepLog,
     NftTransferLog,
     NftUpdateListLog,
     NftUpdateOfferLog,
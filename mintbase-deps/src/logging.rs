@@ -3,6 +3,7 @@ use std::convert::TryFrom;
 use std::str::FromStr;
 
 use near_sdk::json_types::{
+    Base64VecU8,
     U128,
     U64,
 };
@@ -13,14 +14,37 @@ use near_sdk::serde::{
 use near_sdk::{
     env,
     AccountId,
+    Balance,
 };
 
 use crate::common::{
+    Attestation,
+    EventJournal,
+    MediaHints,
+    MigrationCertificate,
     NFTContractMetadata,
+    NearTime,
+    Payout,
     Royalty,
     SplitOwners,
+    StoreRole,
+    TokenMetadata,
     TokenOffer,
+    TokenReservation,
 };
+use crate::constants::MAX_EVENT_METADATA_LEN;
+// market events used to be defined here
+pub use crate::market::{
+    NftListLog,
+    NftMarketLog,
+    NftOfferLog,
+    NftOfferLog2,
+    NftSaleLog,
+    NftSweepLog,
+    NftUpdateListLog,
+    NftUpdateOfferLog,
+};
+use crate::token::Lease;
 
 // ----------------------------- various types ------------------------------ //
 
@@ -31,6 +55,7 @@ pub enum Nep171EventLog {
     NftMint(Vec<NftMintLog>),
     NftBurn(Vec<NftBurnLog>),
     NftTransfer(Vec<NftTransferLog>),
+    NftMetadataUpdate(Vec<NftMetadataUpdateLog>),
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -144,6 +169,108 @@ pub struct NftStringLog {
     pub data: String,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftGrantMinterUntilLog {
+    pub account_id: String,
+    pub expiry_timestamp: NearTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftChangeMintersLog {
+    pub granted: Vec<String>,
+    pub revoked: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftSetDefaultRoyaltyLog {
+    pub royalty: Option<Royalty>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftUpdateTokenRoyaltyLog {
+    pub token_ids: Vec<String>,
+    pub royalty: Option<Royalty>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftMintDelegateLog {
+    pub minter_id: String,
+    pub delegate_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftOperatorLog {
+    pub owner_id: String,
+    pub operator_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftRoleLog {
+    pub account_id: String,
+    pub role: StoreRole,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftMinterApplicationLog {
+    pub account_id: String,
+    pub profile_reference: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftRejectMinterApplicationLog {
+    pub account_id: String,
+    pub slashed: bool,
+}
+
+/// What happened to a storage deposit.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageDepositAction {
+    /// Attached by the account and kept by the `Store` to cover storage.
+    Taken,
+    /// Returned to the account, e.g. an excess deposit.
+    Refunded,
+    /// Paid from the balance of the `Store` for storage used on behalf of
+    /// the account.
+    Consumed,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftStorageDepositLog {
+    pub action: StorageDepositAction,
+    pub account_id: String,
+    pub amount: U128,
+    /// The method that took, refunded or consumed the deposit.
+    pub method: String,
+}
+
+/// How the receiver of an `nft_transfer_call` responded to
+/// `nft_on_transfer`, or the receiver of an `nft_batch_transfer_call` to
+/// `nft_on_batch_transfer` for a single token.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferCallOutcome {
+    /// Returned `false`, keeping the token.
+    Kept,
+    /// Returned `true`, asking for the token to be returned.
+    Returned,
+    /// Returned a value that is not a boolean, or for batches not a list
+    /// with one boolean per token.
+    Malformed,
+    /// Panicked or ran out of gas.
+    Failed,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftResolveTransferLog {
+    pub token_id: String,
+    pub owner_id: String,
+    pub receiver_id: String,
+    pub outcome: TransferCallOutcome,
+    /// Whether the token was transferred to `receiver_id`.
+    pub transferred: bool,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NftOptionStringLog {
     pub data: Option<String>,
@@ -164,6 +291,11 @@ pub struct NftBurnLog {
     pub memo: Option<String>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftMetadataUpdateLog {
+    pub token_ids: Vec<String>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NftApproveLog {
     pub token_id: u64,
@@ -183,6 +315,7 @@ pub struct NftTransferLog {
     pub old_owner_id: String,
     pub new_owner_id: String,
     pub token_ids: Vec<String>,
+    /// The `memo` passed to the transfer, e.g. an invoice or sale reference.
     pub memo: Option<String>,
 }
 
@@ -192,6 +325,70 @@ pub struct NftSetSplitOwnerLog {
     pub token_ids: Vec<String>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftPayoutLog {
+    pub token_id: String,
+    pub balance: U128,
+    pub payout: HashMap<String, U128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ft_contract: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RoyaltyUpdateLog {
+    pub lookup_id: String,
+    pub registry_id: String,
+    pub royalty: Option<Royalty>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftDepositRevenueLog {
+    pub payout: HashMap<String, U128>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftClaimRevenueLog {
+    pub account_id: String,
+    pub amount: U128,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftClearSplitOwnersLog {
+    pub token_ids: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftTransferRejectedLog {
+    pub token_id: String,
+    pub owner_id: String,
+    pub receiver_id: String,
+    /// The transfer policy hook that rejected the transfer.
+    pub hook_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftLeaseLog {
+    pub token_id: String,
+    pub owner_id: String,
+    pub borrower_id: String,
+    pub expires_at: NearTime,
+}
+
+/// A token together with its owner, e.g. when the owner froze it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftTokenOwnerLog {
+    pub token_id: String,
+    pub owner_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftSetPayoutOverrideLog {
+    pub token_id: String,
+    pub payout_override: Option<SplitOwners>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NftLoanSetLog {
     pub account_id: Option<String>,
@@ -252,15 +449,11 @@ pub struct NftOnMoveLog {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct NftListLog {
-    pub list_id: String,
-    pub price: String,
-    pub token_key: String,
-    pub owner_id: String,
-    pub autotransfer: bool,
-    pub approval_id: String,
-    pub token_id: String,
-    pub store_id: String,
+pub struct NftAttestLog {
+    pub token_id: u64,
+    pub attestor: String,
+    pub claim_hash: Base64VecU8,
+    pub uri: String,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -270,79 +463,271 @@ pub struct NftMintLogMemo {
     pub meta_id: Option<String>,
     pub meta_extra: Option<String>,
     pub minter: String,
+    pub mint_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_hints: Option<MediaHints>,
+    /// The full metadata of the minted tokens, if enabled on the `Store`
+    /// and within `MAX_EVENT_METADATA_LEN`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<TokenMetadata>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct NftUpdateListLog {
-    pub auto_transfer: Option<bool>,
-    pub price: Option<String>,
-    pub list_id: Option<String>,
+// --------------------------- logging functions ---------------------------- //
+
+/// Log `event`, and append it to the `EventJournal` if it is enabled.
+pub fn emit(event: &str) {
+    env::log_str(event);
+    if let Some(mut journal) = EventJournal::load() {
+        journal.append(event);
+    }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct NftOfferLog2 {
-    pub offer: TokenOffer,
-    pub list_id: String,
-    pub token_key: String,
-    pub offer_num: u64,
+pub fn log_grant_minter(account_id: &AccountId) {
+    let log = NftStringLog {
+        data: account_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_grant_minter".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct NftOfferLog {
-    pub price: String,
-    pub from: String,
-    pub timeout: String,
-    pub list_id: String,
-    pub token_key: String,
-    pub offer_num: u64,
+pub fn log_revoke_minter(account_id: &AccountId) {
+    let log = NftStringLog {
+        data: account_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_revoke_minter".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct NftUpdateOfferLog {
-    pub list_id: String,
-    pub offer_num: u64,
+pub fn log_change_minters(
+    granted: &[AccountId],
+    revoked: &[AccountId],
+) {
+    let log = NftChangeMintersLog {
+        granted: granted.iter().map(|a| a.to_string()).collect(),
+        revoked: revoked.iter().map(|a| a.to_string()).collect(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_change_minters".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct NftSaleLog {
-    pub list_id: String,
-    pub offer_num: u64,
-    pub token_key: String,
-    pub payout: HashMap<AccountId, U128>,
+pub fn log_grant_minter_until(
+    account_id: &AccountId,
+    expiry_timestamp: &NearTime,
+) {
+    let log = NftGrantMinterUntilLog {
+        account_id: account_id.to_string(),
+        expiry_timestamp: expiry_timestamp.clone(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_grant_minter_until".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct NftMarketLog {
-    pub account_id: String,
-    pub state: bool,
+pub fn log_minter_grant_lapsed(account_id: &AccountId) {
+    let log = NftStringLog {
+        data: account_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_minter_grant_lapsed".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
 }
 
-// --------------------------- logging functions ---------------------------- //
+pub fn log_approve_mint_delegate(
+    minter_id: &AccountId,
+    delegate_id: &AccountId,
+) {
+    let log = NftMintDelegateLog {
+        minter_id: minter_id.to_string(),
+        delegate_id: delegate_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_approve_mint_delegate".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
 
-pub fn log_grant_minter(account_id: &AccountId) {
+pub fn log_revoke_mint_delegate(
+    minter_id: &AccountId,
+    delegate_id: &AccountId,
+) {
+    let log = NftMintDelegateLog {
+        minter_id: minter_id.to_string(),
+        delegate_id: delegate_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_revoke_mint_delegate".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_approve_operator(
+    owner_id: &AccountId,
+    operator_id: &AccountId,
+) {
+    let log = NftOperatorLog {
+        owner_id: owner_id.to_string(),
+        operator_id: operator_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_approve_operator".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_revoke_operator(
+    owner_id: &AccountId,
+    operator_id: &AccountId,
+) {
+    let log = NftOperatorLog {
+        owner_id: owner_id.to_string(),
+        operator_id: operator_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_revoke_operator".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_grant_role(
+    account_id: &AccountId,
+    role: StoreRole,
+) {
+    let log = NftRoleLog {
+        account_id: account_id.to_string(),
+        role,
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_grant_role".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_revoke_role(
+    account_id: &AccountId,
+    role: StoreRole,
+) {
+    let log = NftRoleLog {
+        account_id: account_id.to_string(),
+        role,
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_revoke_role".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_apply_for_minter(
+    account_id: &AccountId,
+    profile_reference: &str,
+) {
+    let log = NftMinterApplicationLog {
+        account_id: account_id.to_string(),
+        profile_reference: profile_reference.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_apply_for_minter".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_approve_minter_application(account_id: &AccountId) {
     let log = NftStringLog {
         data: account_id.to_string(),
     };
     let event = NearJsonEvent {
         standard: "nep171".to_string(),
         version: "1.0.0".to_string(),
-        event: "nft_grant_minter".to_string(),
+        event: "nft_approve_minter_application".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
-pub fn log_revoke_minter(account_id: &AccountId) {
+pub fn log_reject_minter_application(
+    account_id: &AccountId,
+    slashed: bool,
+) {
+    let log = NftRejectMinterApplicationLog {
+        account_id: account_id.to_string(),
+        slashed,
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_reject_minter_application".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_ban_from_minting(account_id: &AccountId) {
     let log = NftStringLog {
         data: account_id.to_string(),
     };
     let event = NearJsonEvent {
         standard: "nep171".to_string(),
         version: "1.0.0".to_string(),
-        event: "nft_revoke_minter".to_string(),
+        event: "nft_ban_from_minting".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_unban_from_minting(account_id: &AccountId) {
+    let log = NftStringLog {
+        data: account_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_unban_from_minting".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_transfer_store(to: &AccountId) {
@@ -352,36 +737,105 @@ pub fn log_transfer_store(to: &AccountId) {
     let event = NearJsonEvent {
         standard: "nep171".to_string(),
         version: "1.0.0".to_string(),
-        event: "nft_transfer_store".to_string(),
+        event: "nft_transfer_store".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_set_icon_base64(base64: &Option<String>) {
+    let log = NftOptionStringLog {
+        data: base64.clone(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_set_icon_base64".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_set_base_uri(base_uri: &str) {
+    let log = NftStringLog {
+        data: base_uri.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_set_base_uri".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_set_minting_fee(minting_fee: U128) {
+    let log = NftStringLog {
+        data: minting_fee.0.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_set_minting_fee".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_set_transfer_fee(transfer_fee: U128) {
+    let log = NftStringLog {
+        data: transfer_fee.0.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_set_transfer_fee".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_set_default_royalty(royalty: &Option<Royalty>) {
+    let log = NftSetDefaultRoyaltyLog {
+        royalty: royalty.clone(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_set_default_royalty".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
-pub fn log_set_icon_base64(base64: &Option<String>) {
-    let log = NftOptionStringLog {
-        data: base64.clone(),
+pub fn log_update_token_royalty(
+    token_ids: &[u64],
+    royalty: &Option<Royalty>,
+) {
+    let log = NftUpdateTokenRoyaltyLog {
+        token_ids: token_ids.iter().map(|id| id.to_string()).collect(),
+        royalty: royalty.clone(),
     };
     let event = NearJsonEvent {
         standard: "nep171".to_string(),
         version: "1.0.0".to_string(),
-        event: "nft_set_icon_base64".to_string(),
+        event: "nft_update_token_royalty".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
-pub fn log_set_base_uri(base_uri: &str) {
+pub fn log_set_minter_cooling_off(hours: u64) {
     let log = NftStringLog {
-        data: base_uri.to_string(),
+        data: hours.to_string(),
     };
     let event = NearJsonEvent {
         standard: "nep171".to_string(),
         version: "1.0.0".to_string(),
-        event: "nft_set_base_uri".to_string(),
+        event: "nft_set_minter_cooling_off".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -394,13 +848,23 @@ pub fn log_nft_batch_mint(
     split_owners: &Option<SplitOwners>,
     meta_ref: &Option<String>,
     meta_extra: &Option<String>,
+    mint_id: &str,
+    media_hints: &Option<MediaHints>,
+    metadata: Option<&TokenMetadata>,
 ) {
+    // metadata that would exceed the log limits is left to be fetched
+    let metadata = metadata
+        .filter(|metadata| serde_json::to_string(metadata).unwrap().len() <= MAX_EVENT_METADATA_LEN)
+        .cloned();
     let memo = serde_json::to_string(&NftMintLogMemo {
         royalty: royalty.clone(),
         split_owners: split_owners.clone(),
         meta_id: meta_ref.clone(),
         meta_extra: meta_extra.clone(),
         minter: minter.to_string(),
+        mint_id: Some(mint_id.to_string()),
+        media_hints: media_hints.clone(),
+        metadata,
     })
     .unwrap();
     let token_ids = (first_token_id..=last_token_id)
@@ -417,7 +881,7 @@ pub fn log_nft_batch_mint(
         event_kind: Nep171EventLog::NftMint(log),
     };
 
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_nft_batch_burn(
@@ -439,7 +903,24 @@ pub fn log_nft_batch_burn(
         version: "1.0.0".to_string(),
         event_kind: Nep171EventLog::NftBurn(log),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
+}
+
+pub fn log_nft_metadata_update(
+    first_token_id: u64,
+    last_token_id: u64,
+) {
+    let token_ids = (first_token_id..=last_token_id)
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>();
+    let log = vec![NftMetadataUpdateLog { token_ids }];
+    // metadata updates were introduced with version 1.1.0 of the standard
+    let event = Nep171Event {
+        standard: "nep171".to_string(),
+        version: "1.1.0".to_string(),
+        event_kind: Nep171EventLog::NftMetadataUpdate(log),
+    };
+    emit(&event.near_json_event());
 }
 
 // ---------------------------------- NEPs ---------------------------------- //
@@ -461,7 +942,7 @@ pub fn log_approve(
         event: "nft_approve".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_batch_approve(
@@ -484,7 +965,7 @@ pub fn log_batch_approve(
         event: "nft_approve".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_revoke(
@@ -501,7 +982,7 @@ pub fn log_revoke(
         event: "nft_revoke".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_revoke_all(token_id: u64) {
@@ -514,7 +995,7 @@ pub fn log_revoke_all(token_id: u64) {
         event: "nft_revoke_all".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 // Core
@@ -536,31 +1017,184 @@ pub fn log_nft_transfer(
         version: "1.0.0".to_string(),
         event_kind: Nep171EventLog::NftTransfer(log),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
+}
+
+pub fn log_nft_transfer_rejected(
+    token_id: u64,
+    owner_id: &AccountId,
+    receiver_id: &AccountId,
+    hook_id: &AccountId,
+) {
+    let log = NftTransferRejectedLog {
+        token_id: token_id.to_string(),
+        owner_id: owner_id.to_string(),
+        receiver_id: receiver_id.to_string(),
+        hook_id: hook_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_transfer_rejected".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_nft_lease(
+    token_id: u64,
+    owner_id: &str,
+    lease: &Lease,
+) {
+    let log = NftLeaseLog {
+        token_id: token_id.to_string(),
+        owner_id: owner_id.to_string(),
+        borrower_id: lease.borrower_id.to_string(),
+        expires_at: lease.expires_at.clone(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_lease".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+/// Log that the borrower returned a token before the lease expired.
+pub fn log_nft_return_lease(
+    token_id: u64,
+    owner_id: &str,
+    lease: &Lease,
+) {
+    let log = NftLeaseLog {
+        token_id: token_id.to_string(),
+        owner_id: owner_id.to_string(),
+        borrower_id: lease.borrower_id.to_string(),
+        expires_at: lease.expires_at.clone(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_return_lease".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_nft_force_unlock(
+    token_id: u64,
+    owner_id: &AccountId,
+) {
+    let log = NftTokenOwnerLog {
+        token_id: token_id.to_string(),
+        owner_id: owner_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_force_unlock".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+/// Log that the owner froze (`frozen == true`) or unfroze a token.
+pub fn log_nft_freeze(
+    token_id: u64,
+    owner_id: &AccountId,
+    frozen: bool,
+) {
+    let log = NftTokenOwnerLog {
+        token_id: token_id.to_string(),
+        owner_id: owner_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: if frozen { "nft_freeze" } else { "nft_unfreeze" }.to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_nft_resolve_transfer(
+    token_id: u64,
+    owner_id: &AccountId,
+    receiver_id: &AccountId,
+    outcome: TransferCallOutcome,
+    transferred: bool,
+) {
+    let log = NftResolveTransferLog {
+        token_id: token_id.to_string(),
+        owner_id: owner_id.to_string(),
+        receiver_id: receiver_id.to_string(),
+        outcome,
+        transferred,
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_resolve_transfer".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
 }
 
+/// Log a batch of transfers as a single event, with one entry for each pair
+/// of old and new owner, in the order of their first transfer. `memo` is
+/// attached to each entry.
 pub fn log_nft_batch_transfer(
     tokens: &[U64],
     accounts: &[AccountId],
     old_owners: Vec<String>,
+    memo: &Option<String>,
 ) {
-    let log = accounts
-        .iter()
-        .enumerate()
-        .map(|(u, x)| NftTransferLog {
-            authorized_id: None,
-            old_owner_id: old_owners[u].clone(),
-            new_owner_id: x.to_string(),
-            token_ids: vec![tokens[u].0.to_string()],
-            memo: None,
-        })
-        .collect::<Vec<_>>();
+    let mut log: Vec<NftTransferLog> = vec![];
+    accounts.iter().enumerate().for_each(|(u, x)| {
+        let token_id = tokens[u].0.to_string();
+        match log
+            .iter_mut()
+            .find(|entry| entry.old_owner_id == old_owners[u] && entry.new_owner_id == x.as_str())
+        {
+            Some(entry) => entry.token_ids.push(token_id),
+            None => log.push(NftTransferLog {
+                authorized_id: None,
+                old_owner_id: old_owners[u].clone(),
+                new_owner_id: x.to_string(),
+                token_ids: vec![token_id],
+                memo: memo.clone(),
+            }),
+        }
+    });
     let event = Nep171Event {
         standard: "nep171".to_string(),
         version: "1.0.0".to_string(),
         event_kind: Nep171EventLog::NftTransfer(log),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
+}
+
+// storage
+pub fn log_storage_deposit(
+    action: StorageDepositAction,
+    account_id: &AccountId,
+    amount: u128,
+    method: &str,
+) {
+    let log = NftStorageDepositLog {
+        action,
+        account_id: account_id.to_string(),
+        amount: amount.into(),
+        method: method.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_storage_deposit".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
 }
 
 // payout
@@ -583,12 +1217,120 @@ pub fn log_set_split_owners(
         event: "nft_set_split_owners".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
     //         .to_string()
     //         .as_str(),
     // );
 }
 
+pub fn log_nft_payout(
+    token_id: u64,
+    balance: U128,
+    payout: &Payout,
+    memo: &Option<String>,
+) {
+    let log = NftPayoutLog {
+        token_id: token_id.to_string(),
+        balance,
+        payout: payout
+            .payout
+            .iter()
+            .map(|(account_id, amount)| (account_id.to_string(), *amount))
+            .collect(),
+        ft_contract: payout.ft_contract.as_ref().map(|x| x.to_string()),
+        memo: memo.clone(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_payout".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_royalty_update(
+    lookup_id: u64,
+    registry_id: &AccountId,
+    royalty: &Option<Royalty>,
+) {
+    let log = RoyaltyUpdateLog {
+        lookup_id: lookup_id.to_string(),
+        registry_id: registry_id.to_string(),
+        royalty: royalty.clone(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "royalty_update".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_deposit_revenue(payout: &HashMap<AccountId, U128>) {
+    let log = NftDepositRevenueLog {
+        payout: payout
+            .iter()
+            .map(|(account_id, amount)| (account_id.to_string(), *amount))
+            .collect(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_deposit_revenue".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_claim_revenue(
+    account_id: &AccountId,
+    amount: Balance,
+) {
+    let log = NftClaimRevenueLog {
+        account_id: account_id.to_string(),
+        amount: amount.into(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_claim_revenue".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_clear_split_owners(token_ids: &[U64]) {
+    let log = NftClearSplitOwnersLog {
+        token_ids: token_ids.iter().map(|x| x.0.to_string()).collect(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_clear_split_owners".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_set_payout_override(
+    token_id: u64,
+    payout_override: &Option<SplitOwners>,
+) {
+    let log = NftSetPayoutOverrideLog {
+        token_id: token_id.to_string(),
+        payout_override: payout_override.clone(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_set_payout_override".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
 pub fn log_nft_loan_set(
     token_id: u64,
     account_id: &Option<AccountId>,
@@ -603,7 +1345,80 @@ pub fn log_nft_loan_set(
         event: "nft_loan_set".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
+}
+
+// attestations
+pub fn log_grant_attestor(account_id: &AccountId) {
+    let log = NftStringLog {
+        data: account_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_grant_attestor".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_revoke_attestor(account_id: &AccountId) {
+    let log = NftStringLog {
+        data: account_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_revoke_attestor".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_attest(
+    token_id: u64,
+    attestation: &Attestation,
+) {
+    let log = NftAttestLog {
+        token_id,
+        attestor: attestation.attestor.to_string(),
+        claim_hash: attestation.claim_hash.clone(),
+        uri: attestation.uri.clone(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_attest".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+// markets
+pub fn log_add_market(account_id: &AccountId) {
+    let log = NftStringLog {
+        data: account_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_add_market".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_remove_market(account_id: &AccountId) {
+    let log = NftStringLog {
+        data: account_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_remove_market".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
 }
 
 // compose
@@ -634,7 +1449,7 @@ pub fn log_nfts_compose(
         event: "nft_compose".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_nfts_uncompose(
@@ -651,7 +1466,7 @@ pub fn log_nfts_uncompose(
         event: "nft_uncompose".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_on_compose(
@@ -679,7 +1494,7 @@ pub fn log_on_compose(
         event: "nft_on_compose".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_on_uncompose(
@@ -698,7 +1513,7 @@ pub fn log_on_uncompose(
         event: "nft_on_uncompose".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_on_move(
@@ -715,7 +1530,7 @@ pub fn log_on_move(
         event: "nft_on_move".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_nft_moved(
@@ -732,7 +1547,27 @@ pub fn log_nft_moved(
         event: "nft_moved".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
+}
+
+pub fn log_nft_migrate(certificate: &MigrationCertificate) {
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_migrate".to_string(),
+        data: serde_json::to_string(certificate).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_reserve_token_range(reservation: &TokenReservation) {
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_reserve_token_range".to_string(),
+        data: serde_json::to_string(reservation).unwrap(),
+    };
+    emit(&event.near_json_event());
 }
 
 // ----------------------------- market events ------------------------------ //
@@ -766,7 +1601,7 @@ pub fn log_listing_created(
         event: "nft_1_list".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_batch_listing_created(
@@ -801,7 +1636,7 @@ pub fn log_batch_listing_created(
         event: "nft_batch_list".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_set_token_autotransfer(
@@ -819,7 +1654,7 @@ pub fn log_set_token_autotransfer(
         event: "nft_set_autotransfer".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_set_token_asking_price(
@@ -837,7 +1672,7 @@ pub fn log_set_token_asking_price(
         event: "nft_set_price".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_make_offer(
@@ -862,7 +1697,7 @@ pub fn log_make_offer(
         event: "nft_make_offer".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_withdraw_token_offer(
@@ -879,7 +1714,7 @@ pub fn log_withdraw_token_offer(
         event: "nft_withdraw_offer".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_sale(
@@ -900,7 +1735,43 @@ pub fn log_sale(
         event: "nft_sold".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
+}
+
+pub fn log_listing_restored(
+    list_id: &str,
+    offer_num: u64,
+) {
+    let log = NftUpdateOfferLog {
+        offer_num,
+        list_id: list_id.to_string(),
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_listing_restored".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
+}
+
+pub fn log_sweep_expired_offers(
+    list_ids: &[String],
+    sweeper: &AccountId,
+    bounty: U128,
+) {
+    let log = NftSweepLog {
+        list_ids: list_ids.to_vec(),
+        sweeper: sweeper.to_string(),
+        bounty,
+    };
+    let event = NearJsonEvent {
+        standard: "nep171".to_string(),
+        version: "1.0.0".to_string(),
+        event: "nft_sweep_offers".to_string(),
+        data: serde_json::to_string(&log).unwrap(),
+    };
+    emit(&event.near_json_event());
 }
 
 pub fn log_token_removed(list_id: &str) {
@@ -913,7 +1784,7 @@ pub fn log_token_removed(list_id: &str) {
         event: "nft_removed".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_banlist_update(
@@ -930,7 +1801,7 @@ pub fn log_banlist_update(
         event: "nft_banlist".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 pub fn log_allowlist_update(
@@ -947,7 +1818,7 @@ pub fn log_allowlist_update(
         event: "nft_allowlist".to_string(),
         data: serde_json::to_string(&log).unwrap(),
     };
-    env::log_str(event.near_json_event().as_str());
+    emit(&event.near_json_event());
 }
 
 // --------------------- NFT event error (deprecated?) ---------------------- //
@@ -0,0 +1,102 @@
+//! Types shared by the market contract, the store contract, and external
+//! integrators. Everything that the market serializes, be it as method
+//! arguments, return values, or events, is defined or re-exported here, such
+//! that all parties compile against identical shapes.
+//!
+//! Integrators that don't build contracts may depend on this module via the
+//! `market-types` feature, which doesn't require the `wasm` feature.
+
+use std::collections::HashMap;
+
+use near_sdk::json_types::U128;
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+pub use crate::common::{
+    AuctionSettlement,
+    BidHistory,
+    ListingIntent,
+    ListingPrice,
+    ListingPriceIn,
+    OracleRate,
+    Payout,
+    PriceIndex,
+    PriceOracleConfig,
+    SaleArgs,
+    SalePhase,
+    SalePhaseKind,
+    SalePhases,
+    SweepBountyPool,
+    TokenKey,
+    TokenListing,
+    TokenOffer,
+};
+
+// ------------------------------ market events ----------------------------- //
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftListLog {
+    pub list_id: String,
+    pub price: String,
+    pub token_key: String,
+    pub owner_id: String,
+    pub autotransfer: bool,
+    pub approval_id: String,
+    pub token_id: String,
+    pub store_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftUpdateListLog {
+    pub auto_transfer: Option<bool>,
+    pub price: Option<String>,
+    pub list_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftOfferLog2 {
+    pub offer: TokenOffer,
+    pub list_id: String,
+    pub token_key: String,
+    pub offer_num: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftOfferLog {
+    pub price: String,
+    pub from: String,
+    pub timeout: String,
+    pub list_id: String,
+    pub token_key: String,
+    pub offer_num: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftUpdateOfferLog {
+    pub list_id: String,
+    pub offer_num: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftSaleLog {
+    pub list_id: String,
+    pub offer_num: u64,
+    pub token_key: String,
+    pub payout: HashMap<AccountId, U128>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftSweepLog {
+    pub list_ids: Vec<String>,
+    pub sweeper: String,
+    pub bounty: U128,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NftMarketLog {
+    pub account_id: String,
+    pub state: bool,
+}
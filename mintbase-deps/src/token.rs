@@ -12,18 +12,29 @@ use near_sdk::serde::{
 use near_sdk::AccountId;
 
 use crate::common::{
+    NearTime,
     Royalty,
     SplitOwners,
+    StorageDeposit,
+    StoreError,
     TokenKey,
     TokenMetadataCompliant,
 };
 
 mod composeable_stats;
 pub use composeable_stats::ComposeableStats;
+mod lease;
+pub use lease::Lease;
+mod legacy_token;
+pub use legacy_token::LegacyToken;
 mod loan;
 pub use loan::Loan;
 mod owner;
 pub use owner::Owner;
+mod pending_transfer;
+pub use pending_transfer::PendingTransfer;
+mod transfer_offer;
+pub use transfer_offer::TransferOffer;
 
 /// Supports NEP-171, 177, 178, 181. Ref:
 /// https://github.com/near/NEPs/blob/master/specs/Standards/NonFungibleToken/Core.md
@@ -64,6 +75,47 @@ pub struct Token {
     /// If the token originated on another contract and was `nft_move`d to
     /// this contract, this field will be non-nil.
     pub origin_key: Option<TokenKey>,
+    /// Set by the owner to disallow transfers and approvals of this token,
+    /// including transfers by already approved accounts.
+    pub frozen: bool,
+    /// If set, the token may only be transferred by the owner initiating a
+    /// transfer and this account approving it.
+    pub co_signer: Option<AccountId>,
+    /// A transfer initiated by the owner of a co-signed token, awaiting the
+    /// approval of the `co_signer`.
+    pub pending_transfer: Option<PendingTransfer>,
+    /// Set by the owner to distribute their share of the next sale, taking
+    /// precedence over `split_owners`. Cleared on transfer.
+    pub payout_override: Option<SplitOwners>,
+    /// Set at minting to disallow transfers and approvals of this token,
+    /// e.g. for certificates or memberships. The token may still be burned.
+    pub soulbound: bool,
+    /// Set at minting for time-bound tokens, e.g. access passes. Once
+    /// expired, the token is no longer returned by `nft_token`, may not be
+    /// transferred or approved, and may be burned by anyone.
+    pub expires_at: Option<NearTime>,
+    /// Expiry of those `approvals` that are only valid until a given time,
+    /// e.g. market listings. Expired approvals may not be exercised.
+    pub approval_expiries: HashMap<AccountId, NearTime>,
+    /// A transfer offered by the owner, awaiting acceptance by the receiver.
+    /// Cleared on transfer.
+    pub transfer_offer: Option<TransferOffer>,
+    /// The lease granting another account the use of this token. Leased
+    /// tokens are treated as loaned until the lease expires.
+    pub lease: Option<Lease>,
+    /// When the token was locked awaiting a callback, if it is locked.
+    pub locked_at: Option<NearTime>,
+    /// The token into which the owner approved composing this token by the
+    /// holder of that token. Cleared on transfer.
+    pub compose_approval: Option<u64>,
+    /// The index of this token among the copies sharing its metadata, if the
+    /// copies weren't minted with consecutive token ids, e.g. open edition
+    /// copies. Otherwise derived from `id` and `metadata_id`.
+    pub edition_number: Option<u64>,
+    /// The storage deposits paid for `approvals`, refunded to their payer
+    /// once the approval is removed. Missing for approvals granted before
+    /// deposits were recorded.
+    pub approval_deposits: HashMap<AccountId, StorageDeposit>,
 }
 
 impl Token {
@@ -88,6 +140,19 @@ impl Token {
             loan: None,
             composeable_stats: ComposeableStats::new(),
             origin_key: None,
+            frozen: false,
+            co_signer: None,
+            pending_transfer: None,
+            payout_override: None,
+            soulbound: false,
+            expires_at: None,
+            approval_expiries: HashMap::new(),
+            transfer_offer: None,
+            lease: None,
+            locked_at: None,
+            compose_approval: None,
+            edition_number: None,
+            approval_deposits: HashMap::new(),
         }
     }
 
@@ -104,7 +169,148 @@ impl Token {
     }
 
     pub fn is_loaned(&self) -> bool {
-        self.loan.is_some()
+        self.loan.is_some() || self.is_leased()
+    }
+
+    pub fn is_leased(&self) -> bool {
+        self.lease
+            .as_ref()
+            .map(|lease| !lease.is_expired())
+            .unwrap_or(false)
+    }
+
+    pub fn is_locked(&self) -> bool {
+        matches!(self.owner_id, Owner::Lock(_))
+    }
+
+    pub fn assert_not_locked(&self) {
+        assert!(!self.is_locked(), "token locked");
+    }
+
+    pub fn assert_not_frozen(&self) {
+        assert!(!self.frozen, "token frozen");
+    }
+
+    /// Whether the token is held in escrow for the receiver of an unexpired
+    /// transfer offer.
+    pub fn is_offered(&self) -> bool {
+        self.transfer_offer
+            .as_ref()
+            .map(|offer| !offer.is_expired())
+            .unwrap_or(false)
+    }
+
+    pub fn assert_not_offered(&self) {
+        assert!(!self.is_offered(), "token offered for transfer");
+    }
+
+    pub fn assert_transferable(&self) {
+        assert!(!self.soulbound, "token is soulbound");
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .as_ref()
+            .map(|expires_at| !expires_at.is_before_timeout())
+            .unwrap_or(false)
+    }
+
+    pub fn assert_not_expired(&self) {
+        assert!(!self.is_expired(), "token expired");
+    }
+
+    pub fn is_approval_expired(
+        &self,
+        account_id: &AccountId,
+    ) -> bool {
+        self.approval_expiries
+            .get(account_id)
+            .map(|expires_at| !expires_at.is_before_timeout())
+            .unwrap_or(false)
+    }
+
+    /// Remove the approval of `account_id`, returning the storage deposit
+    /// recorded for it.
+    pub fn remove_approval(
+        &mut self,
+        account_id: &AccountId,
+    ) -> Option<StorageDeposit> {
+        self.approvals.remove(account_id);
+        self.approval_expiries.remove(account_id);
+        self.approval_deposits.remove(account_id)
+    }
+
+    /// Remove all approvals, returning the storage deposits recorded for
+    /// them.
+    pub fn clear_approvals(&mut self) -> Vec<StorageDeposit> {
+        self.approvals.clear();
+        self.approval_expiries.clear();
+        self.approval_deposits
+            .drain()
+            .map(|(_, deposit)| deposit)
+            .collect()
+    }
+
+    pub fn assert_approval_not_expired(
+        &self,
+        account_id: &AccountId,
+    ) {
+        assert!(!self.is_approval_expired(account_id), "approval expired");
+    }
+
+    pub fn assert_no_co_signer(&self) {
+        assert!(self.co_signer.is_none(), "transfer requires co-signer");
+    }
+
+    /// Check that the state of the token allows its owner to move it, i.e.
+    /// to transfer, approve, offer, gift, compose or migrate it. The caller
+    /// is not checked. States that keep a token in place belong here.
+    pub fn check_movable_by_owner(&self) -> Result<(), StoreError> {
+        if self.is_locked() {
+            return Err(StoreError::TokenLocked);
+        }
+        if self.is_loaned() {
+            return Err(StoreError::TokenLoaned);
+        }
+        if self.frozen {
+            return Err(StoreError::TokenFrozen);
+        }
+        if self.is_offered() {
+            return Err(StoreError::TokenOffered);
+        }
+        if self.soulbound {
+            return Err(StoreError::TokenSoulbound);
+        }
+        if self.is_expired() {
+            return Err(StoreError::TokenExpired);
+        }
+        if self.co_signer.is_some() {
+            return Err(StoreError::CoSignerRequired);
+        }
+        Ok(())
+    }
+
+    /// Panicking version of `check_movable_by_owner`.
+    pub fn assert_movable_by_owner(&self) {
+        match self.check_movable_by_owner() {
+            Ok(()) => {},
+            Err(StoreError::TokenLocked) => panic!("token locked"),
+            Err(StoreError::TokenLoaned) => panic!("token loaned"),
+            Err(StoreError::TokenFrozen) => panic!("token frozen"),
+            Err(StoreError::TokenOffered) => panic!("token offered for transfer"),
+            Err(StoreError::TokenSoulbound) => panic!("token is soulbound"),
+            Err(StoreError::TokenExpired) => panic!("token expired"),
+            Err(StoreError::CoSignerRequired) => panic!("transfer requires co-signer"),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// The index of this token among the copies sharing its metadata,
+    /// starting at 1. Unless stored on the token, copies were minted with
+    /// consecutive token ids, the first of which is used as `metadata_id`.
+    pub fn edition_number(&self) -> u64 {
+        self.edition_number
+            .unwrap_or(self.id - self.metadata_id + 1)
     }
 }
 
@@ -145,4 +351,32 @@ pub struct TokenCompliant {
     /// If the token originated on another contract and was `nft_move`d to
     /// this contract, this field will be non-nil.
     pub origin_key: Option<TokenKey>,
+    /// Set by the owner to disallow transfers and approvals of this token,
+    /// including transfers by already approved accounts.
+    pub frozen: bool,
+    /// If set, the token may only be transferred by the owner initiating a
+    /// transfer and this account approving it.
+    pub co_signer: Option<AccountId>,
+    /// A transfer initiated by the owner of a co-signed token, awaiting the
+    /// approval of the `co_signer`.
+    pub pending_transfer: Option<PendingTransfer>,
+    /// Set by the owner to distribute their share of the next sale, taking
+    /// precedence over `split_owners`. Cleared on transfer.
+    pub payout_override: Option<SplitOwners>,
+    /// Set at minting to disallow transfers and approvals of this token,
+    /// e.g. for certificates or memberships. The token may still be burned.
+    pub soulbound: bool,
+    /// The index of this token among the copies sharing its metadata,
+    /// starting at 1, e.g. 3 for the third of `metadata.copies`.
+    pub edition_number: u64,
+    /// If set, the point in time from which on the token is invalid.
+    pub expires_at: Option<NearTime>,
+    /// A transfer offered by the owner, awaiting acceptance by the receiver.
+    pub transfer_offer: Option<TransferOffer>,
+    /// The lease granting another account the use of this token.
+    pub lease: Option<Lease>,
+    /// When the token was locked awaiting a callback, if it is locked.
+    pub locked_at: Option<NearTime>,
+    /// The token into which the owner approved composing this token.
+    pub compose_approval: Option<u64>,
 }
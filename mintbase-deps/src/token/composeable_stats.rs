@@ -15,8 +15,7 @@ use near_sdk::serde::{
 /// Tracking depth limits potential bugs around recursive ownership
 /// consuming excessive amounts of gas.
 ///
-/// Tracking the number of cross-contract children a token has prevents
-/// breaking of the Only-One-Cross-Linkage Invariant.
+/// Cross-contract children are not tracked, see `cross_contract_children`.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
 pub struct ComposeableStats {
@@ -25,10 +24,10 @@ pub struct ComposeableStats {
     /// equal to the parent's `depth`+1. If this is a top level token, this
     /// number is 0.
     pub local_depth: u8,
-    /// How many cross contract children this token has, direct AND indirect.
-    /// That is, any parent's `cross_contract_children` value equals the sum
-    /// of of its children's values. If this number is non-zero, deny calls
-    /// to `nft_cross_compose`.
+    /// Intended to count the cross contract children of this token. Tokens
+    /// on other contracts are composed into tokens on this contract without
+    /// notifying it, so the count can't be tracked and is always 0. Kept
+    /// for the storage layout of existing tokens.
     pub cross_contract_children: u8,
 }
 
@@ -0,0 +1,41 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+use crate::common::NearTime;
+
+/// The right of `borrower_id` to use a token until `expires_at`, e.g. a
+/// game item or membership. The token remains with its owner.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct Lease {
+    pub borrower_id: AccountId,
+    pub expires_at: NearTime,
+}
+
+impl Lease {
+    pub fn new(
+        borrower_id: AccountId,
+        expires_at: NearTime,
+    ) -> Self {
+        assert!(
+            expires_at.is_before_timeout(),
+            "expiry must be in the future"
+        );
+        Self {
+            borrower_id,
+            expires_at,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        !self.expires_at.is_before_timeout()
+    }
+}
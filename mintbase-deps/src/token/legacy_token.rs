@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::AccountId;
+
+use crate::common::{
+    SafeFraction,
+    SplitOwners,
+    TokenKey,
+    Upgrade,
+};
+use crate::token::{
+    ComposeableStats,
+    Loan,
+    Owner,
+    Token,
+};
+
+/// The borsh layout of `Token` before frozen, soulbound, expiring, offered
+/// and composed-with-consent tokens were introduced. Tokens minted before
+/// are stored in this layout until they are written again.
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct LegacyToken {
+    id: u64,
+    owner_id: Owner,
+    approvals: HashMap<AccountId, u64>,
+    metadata_id: u64,
+    royalty_id: Option<u64>,
+    split_owners: Option<LegacySplitOwners>,
+    minter: AccountId,
+    loan: Option<Loan>,
+    composeable_stats: ComposeableStats,
+    origin_key: Option<TokenKey>,
+}
+
+/// The borsh layout of `SplitOwners` before burn quorums and sticky split
+/// owners were introduced.
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+struct LegacySplitOwners {
+    split_between: HashMap<AccountId, SafeFraction>,
+}
+
+impl Upgrade<Token> for LegacyToken {
+    fn upgrade(self) -> Token {
+        let split_owners = self.split_owners.map(|split_owners| SplitOwners {
+            split_between: split_owners.split_between,
+            burn_quorum: None,
+            sticky: false,
+            deposit: None,
+        });
+        Token {
+            id: self.id,
+            owner_id: self.owner_id,
+            approvals: self.approvals,
+            metadata_id: self.metadata_id,
+            royalty_id: self.royalty_id,
+            split_owners,
+            minter: self.minter,
+            loan: self.loan,
+            composeable_stats: self.composeable_stats,
+            origin_key: self.origin_key,
+            frozen: false,
+            co_signer: None,
+            pending_transfer: None,
+            payout_override: None,
+            soulbound: false,
+            expires_at: None,
+            approval_expiries: HashMap::new(),
+            transfer_offer: None,
+            lease: None,
+            locked_at: None,
+            compose_approval: None,
+            edition_number: None,
+            approval_deposits: HashMap::new(),
+        }
+    }
+}
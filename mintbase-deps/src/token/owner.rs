@@ -45,7 +45,8 @@ impl fmt::Display for Owner {
             Owner::Account(s) => write!(f, "{}", s),
             Owner::TokenId(n) => write!(f, "{}", n),
             Owner::CrossKey(key) => write!(f, "{}", key),
-            Owner::Lock(_) => panic!("locked"),
+            // not a valid account id, thus never mistaken for the owner
+            Owner::Lock(s) => write!(f, "locked:{}", s),
         }
     }
 }
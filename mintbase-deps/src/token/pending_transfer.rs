@@ -0,0 +1,28 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+/// A transfer of a co-signed token, initiated by the token owner and
+/// awaiting the approval of the co-signer.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct PendingTransfer {
+    pub receiver_id: AccountId,
+    pub memo: Option<String>,
+}
+
+impl PendingTransfer {
+    pub fn new(
+        receiver_id: AccountId,
+        memo: Option<String>,
+    ) -> Self {
+        Self { receiver_id, memo }
+    }
+}
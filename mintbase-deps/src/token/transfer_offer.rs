@@ -0,0 +1,55 @@
+use near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use near_sdk::serde::{
+    Deserialize,
+    Serialize,
+};
+use near_sdk::AccountId;
+
+use crate::common::{
+    NearTime,
+    TimeUnit,
+};
+use crate::constants::MAX_TRANSFER_OFFER_HOURS;
+
+/// A transfer offered by the token owner, executed once `receiver_id`
+/// accepts it. Until `expires_at`, which is at most
+/// `MAX_TRANSFER_OFFER_HOURS` away, the token is held in escrow for the
+/// receiver.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "wasm", derive(BorshDeserialize, BorshSerialize))]
+pub struct TransferOffer {
+    pub receiver_id: AccountId,
+    pub memo: Option<String>,
+    pub expires_at: NearTime,
+}
+
+impl TransferOffer {
+    pub fn new(
+        receiver_id: AccountId,
+        memo: Option<String>,
+        expires_at: NearTime,
+    ) -> Self {
+        assert!(
+            expires_at.is_before_timeout(),
+            "expiry must be in the future"
+        );
+        assert!(
+            expires_at.0 <= NearTime::new(TimeUnit::Hours(MAX_TRANSFER_OFFER_HOURS)).0,
+            "transfer offer may last at most {} hours",
+            MAX_TRANSFER_OFFER_HOURS
+        );
+        Self {
+            receiver_id,
+            memo,
+            expires_at,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        !self.expires_at.is_before_timeout()
+    }
+}
@@ -19,6 +19,53 @@ pub const fn ntoy(near_amount: Balance) -> Balance {
     near_amount * 10u128.pow(24)
 }
 
+/// Compute `a * b / c`, rounded down, without overflowing on the
+/// intermediate product, e.g. to take a share of a balance. Panics if `c` is
+/// zero or the result exceeds `u128::MAX`.
+pub fn mul_div(
+    a: u128,
+    b: u128,
+    c: u128,
+) -> u128 {
+    assert!(c > 0, "division by zero");
+    if let Some(product) = a.checked_mul(b) {
+        return product / c;
+    }
+    let (hi, lo) = mul_wide(a, b);
+    assert!(hi < c, "mul_div overflow");
+    // long division of the 256 bit product, keeping `rem < c`
+    let mut rem = hi;
+    let mut quot = 0u128;
+    for i in (0..128).rev() {
+        let carry = rem >> 127 == 1;
+        rem = (rem << 1) | ((lo >> i) & 1);
+        quot <<= 1;
+        if carry || rem >= c {
+            rem = rem.wrapping_sub(c);
+            quot |= 1;
+        }
+    }
+    quot
+}
+
+/// The 256 bit product of `a` and `b`, as high and low 128 bits.
+fn mul_wide(
+    a: u128,
+    b: u128,
+) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+    let mid = (lo_lo >> 64) + (hi_lo & MASK) + (lo_hi & MASK);
+    let lo = (lo_lo & MASK) | (mid << 64);
+    let hi = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
 // // TODO: unused, deprecated?
 // pub fn to_yocto(value: &str) -> u128 {
 //     let vals: Vec<_> = value.split('.').collect();
@@ -0,0 +1,99 @@
+use mintbase_deps::near_sdk::{
+    self,
+    near_bindgen,
+    AccountId,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Allow `account_ids` to mint on this `Store` without being minters,
+    /// e.g. for an allowlist sale. If `max_mints` is set, each account may
+    /// mint at most that many tokens, as with `grant_minter`.
+    ///
+    /// Only the store owner may call this function.
+    ///
+    /// This method increases storage costs of the contract.
+    #[payable]
+    pub fn add_to_mint_allowlist(
+        &mut self,
+        account_ids: Vec<AccountId>,
+        max_mints: Option<u64>,
+    ) {
+        self.assert_store_owner();
+        for account_id in account_ids {
+            if let Some(max_mints) = max_mints {
+                self.minter_allowances.insert(&account_id, &max_mints);
+            }
+            self.mint_allowlist.insert(&account_id);
+        }
+    }
+
+    /// Remove `account_ids` from the mint allowlist. Minters are not
+    /// affected.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn remove_from_mint_allowlist(
+        &mut self,
+        account_ids: Vec<AccountId>,
+    ) {
+        self.assert_store_owner();
+        for account_id in account_ids {
+            if self.mint_allowlist.remove(&account_id) && !self.minters.contains(&account_id) {
+                self.minter_allowances.remove(&account_id);
+            }
+        }
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Check if `account_id` is on the mint allowlist.
+    pub fn is_on_mint_allowlist(
+        &self,
+        account_id: AccountId,
+    ) -> bool {
+        self.mint_allowlist.contains(&account_id)
+    }
+
+    /// List the accounts on the mint allowlist.
+    pub fn list_mint_allowlist(
+        &self,
+        from_index: Option<String>,
+        limit: Option<usize>,
+    ) -> Vec<AccountId> {
+        self.mint_allowlist
+            .iter()
+            .skip(
+                from_index
+                    .unwrap_or_else(|| "0".to_string())
+                    .parse()
+                    .unwrap(),
+            )
+            .take(limit.unwrap_or(10))
+            .collect()
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+
+    /// Panics unless `account_id` is a minter whose grant is active or on
+    /// the mint allowlist.
+    pub(crate) fn assert_can_mint(
+        &self,
+        account_id: &AccountId,
+    ) {
+        if self.mint_allowlist.contains(account_id) {
+            return;
+        }
+        assert!(
+            self.minters.contains(account_id),
+            "{} not a minter",
+            account_id
+        );
+        self.assert_minter_grant_active(account_id);
+    }
+}
@@ -1,3 +1,8 @@
+use mintbase_deps::common::{
+    Activity,
+    NearTime,
+    StorageDeposit,
+};
 use mintbase_deps::constants::gas;
 use mintbase_deps::interfaces::ext_on_approve;
 use mintbase_deps::logging::{
@@ -5,6 +10,8 @@ use mintbase_deps::logging::{
     log_batch_approve,
     log_revoke,
     log_revoke_all,
+    log_storage_deposit,
+    StorageDepositAction,
 };
 use mintbase_deps::near_sdk::json_types::U64;
 use mintbase_deps::near_sdk::{
@@ -13,6 +20,7 @@ use mintbase_deps::near_sdk::{
     env,
     near_bindgen,
     AccountId,
+    Balance,
     Promise,
 };
 use mintbase_deps::token::Token;
@@ -23,25 +31,61 @@ use crate::*;
 #[near_bindgen]
 impl MintbaseStore {
     // -------------------------- change methods ---------------------------
+
+    /// Approve `account_id` to transfer the token. A new approval takes the
+    /// storage deposit for a single approval, re-approving an account only
+    /// renews its approval id. The remaining deposit is refunded. If `msg`
+    /// is given, `nft_on_approve` is called on `account_id`.
+    ///
+    /// If `expires_at` is set, the approval may not be exercised after that
+    /// time, e.g. to end a market listing.
     #[payable]
     pub fn nft_approve(
         &mut self,
         token_id: U64,
         account_id: AccountId,
         msg: Option<String>,
+        expires_at: Option<NearTime>,
     ) -> Option<Promise> {
         // Note: This method only guarantees that the store-storage is covered. The
         // market may still reject.
-        assert!(env::attached_deposit() > self.storage_costs.common);
+        assert!(env::attached_deposit() > self.storage_costs().common);
         let token_idu64 = token_id.into();
+        let storage_cost = match self
+            .nft_token_internal(token_idu64)
+            .approvals
+            .contains_key(&account_id)
+        {
+            true => 0,
+            false => self.storage_costs().common,
+        };
+        self.record_activity(Activity::Approval, 1);
         // validates owner and loaned
-        let approval_id = self.approve_internal(token_idu64, &account_id);
+        let approval_id = self.approve_internal(token_idu64, &account_id, expires_at, storage_cost);
         log_approve(token_idu64, approval_id, &account_id);
+        let owner_id = env::predecessor_account_id();
+        if storage_cost > 0 {
+            log_storage_deposit(
+                StorageDepositAction::Taken,
+                &owner_id,
+                storage_cost,
+                "nft_approve",
+            );
+        }
+
+        let refund = env::attached_deposit() - storage_cost;
+        log_storage_deposit(
+            StorageDepositAction::Refunded,
+            &owner_id,
+            refund,
+            "nft_approve",
+        );
+        Promise::new(owner_id.clone()).transfer(refund);
 
         if let Some(msg) = msg {
             ext_on_approve::nft_on_approve(
                 token_id,
-                env::predecessor_account_id(),
+                owner_id,
                 approval_id,
                 msg,
                 account_id,
@@ -54,6 +98,8 @@ impl MintbaseStore {
         }
     }
 
+    /// Revoke the approval of `account_id`, refunding its storage deposit
+    /// to the account that paid it.
     #[payable]
     pub fn nft_revoke(
         &mut self,
@@ -63,15 +109,19 @@ impl MintbaseStore {
         let token_idu64 = token_id.into();
         let mut token = self.nft_token_internal(token_idu64);
         assert!(!token.is_loaned());
-        assert!(token.is_pred_owner());
+        self.assert_pred_holder(&token);
         assert_one_yocto();
 
-        if token.approvals.remove(&account_id).is_some() {
+        if token.approvals.contains_key(&account_id) {
+            let deposits = self.remove_approvals(&mut token, std::slice::from_ref(&account_id));
             self.tokens.insert(&token_idu64, &token);
             log_revoke(token_idu64, &account_id);
+            self.refund_storage_deposits(deposits, "nft_revoke");
         }
     }
 
+    /// Revoke all approvals of the token, refunding their storage deposits
+    /// to the accounts that paid them.
     #[payable]
     pub fn nft_revoke_all(
         &mut self,
@@ -80,13 +130,15 @@ impl MintbaseStore {
         let token_idu64 = token_id.into();
         let mut token = self.nft_token_internal(token_idu64);
         assert!(!token.is_loaned());
-        assert!(token.is_pred_owner());
+        self.assert_pred_holder(&token);
         assert_one_yocto();
 
         if !token.approvals.is_empty() {
-            token.approvals.clear();
+            let account_ids: Vec<AccountId> = token.approvals.keys().cloned().collect();
+            let deposits = self.remove_approvals(&mut token, &account_ids);
             self.tokens.insert(&token_idu64, &token);
             log_revoke_all(token_idu64);
+            self.refund_storage_deposits(deposits, "nft_revoke_all");
         }
     }
 
@@ -109,17 +161,22 @@ impl MintbaseStore {
 #[near_bindgen]
 impl MintbaseStore {
     // -------------------------- change methods ---------------------------
+
+    /// Like `nft_approve`, for many tokens at once. If `expires_at` is set,
+    /// all of the approvals expire at that time.
     #[payable]
     pub fn nft_batch_approve(
         &mut self,
         token_ids: Vec<U64>,
         account_id: AccountId,
         msg: Option<String>,
+        expires_at: Option<NearTime>,
     ) -> Option<Promise> {
         let tlen = token_ids.len() as u128;
         assert!(tlen > 0);
         assert!(tlen <= 70);
-        let store_approval_storage = self.storage_costs.common * tlen;
+        let storage_cost = self.storage_costs().common;
+        let store_approval_storage = storage_cost * tlen;
         // Note: This method only guarantees that the store-storage is covered.
         // The financial contract may still reject.
         assert!(
@@ -127,12 +184,27 @@ impl MintbaseStore {
             "deposit less than: {}",
             store_approval_storage
         );
+        self.record_activity(Activity::Approval, tlen as u32);
         let approval_ids: Vec<U64> = token_ids
             .iter()
             // validates owner and loaned
-            .map(|&token_id| self.approve_internal(token_id.into(), &account_id).into())
+            .map(|&token_id| {
+                self.approve_internal(
+                    token_id.into(),
+                    &account_id,
+                    expires_at.clone(),
+                    storage_cost,
+                )
+                .into()
+            })
             .collect();
         log_batch_approve(&token_ids, &approval_ids, &account_id);
+        log_storage_deposit(
+            StorageDepositAction::Taken,
+            &env::predecessor_account_id(),
+            store_approval_storage,
+            "nft_batch_approve",
+        );
 
         if let Some(msg) = msg {
             ext_on_approve::nft_on_batch_approve(
@@ -154,18 +226,66 @@ impl MintbaseStore {
     // -------------------------- private methods --------------------------
     // -------------------------- internal methods -------------------------
 
-    /// Called from nft_approve and nft_batch_approve.
-    fn approve_internal(
+    /// Remove the approvals of `account_ids` from `token`, returning their
+    /// storage deposits. Deposits of approvals granted before deposits were
+    /// recorded are assumed to have been paid by the caller at the current
+    /// price.
+    fn remove_approvals(
+        &self,
+        token: &mut Token,
+        account_ids: &[AccountId],
+    ) -> Vec<StorageDeposit> {
+        account_ids
+            .iter()
+            .map(|account_id| {
+                token.remove_approval(account_id).unwrap_or_else(|| {
+                    StorageDeposit::new(env::predecessor_account_id(), self.storage_costs().common)
+                })
+            })
+            .collect()
+    }
+
+    /// Called from nft_approve, nft_batch_approve and nft_batch_mint_and_list.
+    /// Records `deposit` as paid by the caller for the approval, in addition
+    /// to what was paid for a previous approval of `account_id`.
+    pub(crate) fn approve_internal(
         &mut self,
         token_idu64: u64,
         account_id: &AccountId,
+        expires_at: Option<NearTime>,
+        deposit: Balance,
     ) -> u64 {
         let mut token = self.nft_token_internal(token_idu64);
-        assert!(!token.is_loaned());
-        assert!(token.is_pred_owner());
+        token.assert_movable_by_owner();
+        self.assert_pred_holder(&token);
         let approval_id = self.num_approved;
         self.num_approved += 1;
-        token.approvals.insert(account_id.clone(), approval_id);
+        let paid = match token.approvals.insert(account_id.clone(), approval_id) {
+            Some(_) => token
+                .approval_deposits
+                .get(account_id)
+                .map(|deposit| deposit.amount.0)
+                .unwrap_or_else(|| self.storage_costs().common),
+            None => 0,
+        };
+        token.approval_deposits.insert(
+            account_id.clone(),
+            StorageDeposit::new(env::predecessor_account_id(), paid + deposit),
+        );
+        match expires_at {
+            Some(expires_at) => {
+                assert!(
+                    expires_at.is_before_timeout(),
+                    "expiry must be in the future"
+                );
+                token
+                    .approval_expiries
+                    .insert(account_id.clone(), expires_at);
+            },
+            None => {
+                token.approval_expiries.remove(account_id);
+            },
+        }
         self.tokens.insert(&token_idu64, &token);
         approval_id
     }
@@ -178,10 +298,15 @@ impl MintbaseStore {
         approved_account_id: AccountId,
         approval_id: Option<u64>,
     ) -> bool {
-        if approved_account_id.to_string() == token.owner_id.to_string() {
+        if approved_account_id.to_string() == token.owner_id.to_string()
+            || self.is_operator_internal(&token.owner_id.to_string(), &approved_account_id)
+        {
             true
         } else {
             let approval_id = approval_id.expect("approval_id required");
+            if token.is_approval_expired(&approved_account_id) {
+                return false;
+            }
             let stored_approval = token.approvals.get(&approved_account_id);
             match stored_approval {
                 None => false,
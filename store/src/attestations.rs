@@ -0,0 +1,128 @@
+use mintbase_deps::common::time::now;
+use mintbase_deps::common::Attestation;
+use mintbase_deps::constants::MAX_ATTESTATIONS_PER_TOKEN;
+use mintbase_deps::logging::{
+    log_attest,
+    log_grant_attestor,
+    log_revoke_attestor,
+};
+use mintbase_deps::near_sdk::json_types::{
+    Base64VecU8,
+    U64,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    AccountId,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Attach an attestation to a token, e.g. a certificate of authenticity
+    /// for the physical art backing it. The attestor must cover the storage
+    /// of the attestation.
+    ///
+    /// Only registered attestors may call this function.
+    #[payable]
+    pub fn attest(
+        &mut self,
+        token_id: U64,
+        claim_hash: Base64VecU8,
+        uri: String,
+    ) {
+        let attestor = env::predecessor_account_id();
+        assert!(
+            self.attestors.contains(&attestor),
+            "{} not an attestor",
+            attestor
+        );
+        let attestation_cost = self.storage_costs().attestation;
+        assert!(
+            env::attached_deposit() >= attestation_cost,
+            "insuf. deposit. Need: {}",
+            attestation_cost
+        );
+        assert!(uri.len() <= 100, "uri too long");
+        // panics if the token doesn't exist
+        self.nft_token_internal(token_id.0);
+
+        let mut attestations = self.attestations.get(&token_id.0).unwrap_or_default();
+        assert!(
+            attestations.len() < MAX_ATTESTATIONS_PER_TOKEN,
+            "token has too many attestations"
+        );
+        let attestation = Attestation {
+            attestor,
+            claim_hash,
+            uri,
+            timestamp: now(),
+        };
+        log_attest(token_id.0, &attestation);
+        attestations.push(attestation);
+        self.attestations.insert(&token_id.0, &attestations);
+    }
+
+    /// Register `account_id` as attestor, allowing them to attach
+    /// attestations to tokens on this `Store`.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn grant_attestor(
+        &mut self,
+        account_id: AccountId,
+    ) {
+        self.assert_store_owner();
+        // does nothing if account_id is already an attestor
+        if self.attestors.insert(&account_id) {
+            log_grant_attestor(&account_id);
+        }
+    }
+
+    /// Remove `account_id` from the registered attestors. Attestations that
+    /// have already been made are kept.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn revoke_attestor(
+        &mut self,
+        account_id: AccountId,
+    ) {
+        self.assert_store_owner();
+        if !self.attestors.remove(&account_id) {
+            env::panic_str("not an attestor")
+        } else {
+            log_revoke_attestor(&account_id);
+        }
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Check if `account_id` is a registered attestor.
+    pub fn check_is_attestor(
+        &self,
+        account_id: AccountId,
+    ) -> bool {
+        self.attestors.contains(&account_id)
+    }
+
+    /// Lists all account IDs that are currently registered as attestors.
+    pub fn list_attestors(&self) -> Vec<AccountId> {
+        self.attestors.iter().collect()
+    }
+
+    /// Get all attestations made for a token, oldest first.
+    pub fn nft_token_attestations(
+        &self,
+        token_id: U64,
+    ) -> Vec<Attestation> {
+        self.attestations.get(&token_id.0).unwrap_or_default()
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+}
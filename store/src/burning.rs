@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use mintbase_deps::logging::log_nft_batch_burn;
 use mintbase_deps::near_sdk::json_types::U64;
 use mintbase_deps::near_sdk::{
@@ -6,6 +8,7 @@ use mintbase_deps::near_sdk::{
     near_bindgen,
     AccountId,
 };
+use mintbase_deps::token::Owner;
 
 use crate::*;
 
@@ -27,6 +30,81 @@ impl MintbaseStore {
         self.burn_triaged(token_ids, env::predecessor_account_id());
     }
 
+    /// Burn expired tokens, freeing their storage on this `Store`. Expired
+    /// tokens are invalid, so anyone may call this function. One burn event
+    /// is emitted per owner.
+    pub fn nft_burn_expired(
+        &mut self,
+        token_ids: Vec<U64>,
+    ) {
+        assert!(!token_ids.is_empty());
+        let mut tokens_per_owner: BTreeMap<AccountId, Vec<U64>> = BTreeMap::new();
+        token_ids.into_iter().for_each(|token_id| {
+            let token = self.nft_token_internal(token_id.into());
+            assert!(token.is_expired(), "token: {} not expired", token_id.0);
+            let owner_id = match token.owner_id {
+                Owner::Account(owner_id) => owner_id,
+                _ => env::panic_str("token not owned by an account"),
+            };
+            tokens_per_owner.entry(owner_id).or_default().push(token_id);
+        });
+        tokens_per_owner
+            .into_iter()
+            .for_each(|(owner_id, token_ids)| self.burn_triaged(token_ids, owner_id));
+    }
+
+    /// Start collecting confirmations of the split owners for burning a
+    /// token that requires a burn quorum. Any previous confirmations are
+    /// discarded.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn propose_burn(
+        &mut self,
+        token_id: U64,
+    ) {
+        near_sdk::assert_one_yocto();
+        let token = self.nft_token_internal(token_id.into());
+        assert!(token.is_pred_owner());
+        assert!(
+            token
+                .split_owners
+                .map(|splits| splits.burn_quorum.is_some())
+                .unwrap_or(false),
+            "token has no burn quorum"
+        );
+        self.burn_confirmations.insert(&token_id.into(), &vec![]);
+    }
+
+    /// Confirm the proposed burn of a token.
+    ///
+    /// Only split owners of the token may call this function.
+    #[payable]
+    pub fn confirm_burn(
+        &mut self,
+        token_id: U64,
+    ) {
+        near_sdk::assert_one_yocto();
+        let token_id: u64 = token_id.into();
+        let pred = env::predecessor_account_id();
+        let token = self.nft_token_internal(token_id);
+        assert!(
+            token
+                .split_owners
+                .map(|splits| splits.split_between.contains_key(&pred))
+                .unwrap_or(false),
+            "not a split owner"
+        );
+        let mut confirmations = self
+            .burn_confirmations
+            .get(&token_id)
+            .expect("no burn proposed");
+        if !confirmations.contains(&pred) {
+            confirmations.push(pred);
+            self.burn_confirmations.insert(&token_id, &confirmations);
+        }
+    }
+
     /// A helper to burn tokens. Necessary to satisfy the `nft_move` method,
     /// where the callback prevents the use of
     /// `env::predecessor_account_id()` to determine whether the owner is the
@@ -42,7 +120,13 @@ impl MintbaseStore {
             let token_id: u64 = token_id.into();
             let token = self.nft_token_internal(token_id);
             assert!(!token.is_loaned());
+            token.assert_not_offered();
             assert_eq!(token.owner_id.to_string(), account_id.to_string());
+            assert!(
+                self.composeables.get(&token_id.to_string()).is_none(),
+                "token has composed children"
+            );
+            self.assert_burn_quorum(&token);
 
             // update the counts on token metadata and royalties stored
             let metadata_id = self.nft_token_internal(token_id).metadata_id;
@@ -52,6 +136,7 @@ impl MintbaseStore {
                     .insert(&metadata_id, &(count - 1, metadata));
             } else {
                 self.token_metadata.remove(&metadata_id);
+                self.metadata_commitments.remove(&metadata_id);
             }
             if let Some(royalty_id) = self.nft_token_internal(token_id).royalty_id {
                 let (count, royalty) = self.token_royalty.get(&royalty_id).unwrap();
@@ -65,6 +150,9 @@ impl MintbaseStore {
 
             set_owned.remove(&token_id);
             self.tokens.remove(&token_id);
+            self.attestations.remove(&token_id);
+            self.burn_confirmations.remove(&token_id);
+            self.gifts.remove(&token_id);
         });
 
         if set_owned.is_empty() {
@@ -91,6 +179,45 @@ impl MintbaseStore {
     }
 
     // -------------------------- view methods -----------------------------
+
+    /// Get the split owners that confirmed the proposed burn of a token.
+    pub fn get_burn_confirmations(
+        &self,
+        token_id: U64,
+    ) -> Option<Vec<AccountId>> {
+        self.burn_confirmations.get(&token_id.into())
+    }
+
     // -------------------------- private methods --------------------------
     // -------------------------- internal methods -------------------------
+
+    /// Panics if the token requires a burn quorum and not enough of its
+    /// current split owners confirmed the burn. Expired tokens are exempt.
+    fn assert_burn_quorum(
+        &self,
+        token: &Token,
+    ) {
+        if token.is_expired() {
+            return;
+        }
+        let splits = match token.split_owners {
+            Some(ref splits) => splits,
+            None => return,
+        };
+        if let Some(burn_quorum) = splits.burn_quorum {
+            let confirmed = self
+                .burn_confirmations
+                .get(&token.id)
+                .unwrap_or_default()
+                .iter()
+                .filter(|account_id| splits.split_between.contains_key(*account_id))
+                .count();
+            assert!(
+                confirmed >= burn_quorum as usize,
+                "burn requires {} split owner confirmations, got {}",
+                burn_quorum,
+                confirmed
+            );
+        }
+    }
 }
@@ -0,0 +1,155 @@
+use mintbase_deps::logging::log_nft_transfer;
+use mintbase_deps::near_sdk::json_types::U64;
+use mintbase_deps::near_sdk::{
+    self,
+    assert_one_yocto,
+    env,
+    near_bindgen,
+    AccountId,
+};
+use mintbase_deps::token::{
+    PendingTransfer,
+    Token,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Require `co_signer` to approve each transfer of the token. While a
+    /// co-signer is set, the token cannot be transferred via `nft_transfer`
+    /// or approved to other accounts, only via `nft_initiate_transfer` and
+    /// `nft_approve_transfer`. The co-signer is removed when the token is
+    /// transferred.
+    ///
+    /// Only the token owner may call this function, and only if no
+    /// co-signer has been set.
+    #[payable]
+    pub fn set_co_signer(
+        &mut self,
+        token_id: U64,
+        co_signer: AccountId,
+    ) {
+        assert_one_yocto();
+        let mut token = self.nft_token_internal(token_id.into());
+        assert!(!token.is_loaned());
+        assert!(token.is_pred_owner());
+        assert!(token.co_signer.is_none(), "co-signer already set");
+        assert_ne!(
+            co_signer,
+            env::predecessor_account_id(),
+            "can't co-sign own token"
+        );
+        token.co_signer = Some(co_signer);
+        self.tokens.insert(&token.id, &token);
+    }
+
+    /// Release the token from requiring co-signed transfers, cancelling any
+    /// pending transfer.
+    ///
+    /// Only the co-signer may call this function.
+    #[payable]
+    pub fn remove_co_signer(
+        &mut self,
+        token_id: U64,
+    ) {
+        assert_one_yocto();
+        let mut token = self.nft_token_internal(token_id.into());
+        self.assert_pred_co_signer(&token);
+        token.co_signer = None;
+        token.pending_transfer = None;
+        self.tokens.insert(&token.id, &token);
+    }
+
+    /// Initiate the transfer of a co-signed token to `receiver_id`, which
+    /// is executed once the co-signer calls `nft_approve_transfer`.
+    /// Replaces any previously initiated transfer. The transfer fee is due
+    /// when initiating, and not refunded if the transfer is cancelled.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn nft_initiate_transfer(
+        &mut self,
+        token_id: U64,
+        receiver_id: AccountId,
+        memo: Option<String>,
+    ) {
+        self.collect_transfer_fee(1, 1);
+        let mut token = self.nft_token_internal(token_id.into());
+        assert!(!token.is_loaned());
+        token.assert_not_frozen();
+        token.assert_not_offered();
+        token.assert_transferable();
+        token.assert_not_expired();
+        assert!(token.is_pred_owner());
+        assert!(token.co_signer.is_some(), "no co-signer");
+        assert_ne!(receiver_id.to_string(), token.owner_id.to_string()); // can't transfer to self
+        token.pending_transfer = Some(PendingTransfer::new(receiver_id, memo));
+        self.tokens.insert(&token.id, &token);
+    }
+
+    /// Approve and execute the transfer initiated via
+    /// `nft_initiate_transfer`.
+    ///
+    /// Only the co-signer may call this function.
+    #[payable]
+    pub fn nft_approve_transfer(
+        &mut self,
+        token_id: U64,
+    ) {
+        assert_one_yocto();
+        let mut token = self.nft_token_internal(token_id.into());
+        self.assert_pred_co_signer(&token);
+        assert!(!token.is_loaned());
+        token.assert_not_frozen();
+        token.assert_not_offered();
+        let pending = token.pending_transfer.take().expect("no pending transfer");
+        self.assert_transfer_policy(&pending.receiver_id);
+        let old_owner = token.owner_id.to_string();
+        self.transfer_internal(
+            &mut token,
+            pending.receiver_id.clone(),
+            true,
+            "nft_approve_transfer",
+        );
+        log_nft_transfer(&pending.receiver_id, token.id, &pending.memo, old_owner);
+    }
+
+    /// Cancel the transfer initiated via `nft_initiate_transfer`.
+    ///
+    /// Only the token owner or the co-signer may call this function.
+    #[payable]
+    pub fn nft_cancel_transfer(
+        &mut self,
+        token_id: U64,
+    ) {
+        assert_one_yocto();
+        let mut token = self.nft_token_internal(token_id.into());
+        assert!(
+            token.is_pred_owner() || token.co_signer == Some(env::predecessor_account_id()),
+            "only owner or co-signer"
+        );
+        assert!(
+            token.pending_transfer.take().is_some(),
+            "no pending transfer"
+        );
+        self.tokens.insert(&token.id, &token);
+    }
+
+    // -------------------------- view methods -----------------------------
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+
+    fn assert_pred_co_signer(
+        &self,
+        token: &Token,
+    ) {
+        assert_eq!(
+            token.co_signer,
+            Some(env::predecessor_account_id()),
+            "not the co-signer"
+        );
+    }
+}
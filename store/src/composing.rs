@@ -0,0 +1,558 @@
+use mintbase_deps::common::TokenKey;
+use mintbase_deps::constants::{
+    gas,
+    MAX_COMPOSE_DEPTH,
+    NO_DEPOSIT,
+};
+use mintbase_deps::interfaces::ext_compose_parent;
+use mintbase_deps::logging::{
+    log_nft_batch_transfer,
+    log_nfts_compose,
+    log_nfts_uncompose,
+};
+use mintbase_deps::near_sdk::json_types::U64;
+use mintbase_deps::near_sdk::{
+    self,
+    assert_one_yocto,
+    env,
+    near_bindgen,
+    AccountId,
+    Promise,
+    PromiseResult,
+};
+use mintbase_deps::serde_json;
+use mintbase_deps::token::{
+    Owner,
+    TokenCompliant,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Compose the token `child_id` into the token `parent_id`, e.g. to
+    /// bundle an avatar with its wearables. The child is then owned by the
+    /// parent, and follows the parent on transfers until it is uncomposed,
+    /// see also `nft_transfer_bundle`. Composed tokens may not be
+    /// transferred or burned, and neither may tokens with composed children
+    /// be burned. The holder may approve a composed token for sale, in which
+    /// case `nft_transfer_payout` uncomposes it.
+    ///
+    /// The caller must hold the parent, i.e. own the token at the root of
+    /// the parent's compose chain, and either own the child or have the
+    /// child owner's approval via `nft_approve_compose`. Composing a token
+    /// into itself or one of its descendants is rejected, as is exceeding
+    /// the `max_compose_depth` of this store with any descendant of the
+    /// child.
+    #[payable]
+    pub fn nft_compose(
+        &mut self,
+        child_id: U64,
+        parent_id: U64,
+    ) {
+        assert_one_yocto();
+        let mut child = self.nft_token_internal(child_id.0);
+        child.assert_movable_by_owner();
+        assert!(
+            child.is_pred_owner() || child.compose_approval == Some(parent_id.0),
+            "composing requires approval of the child owner"
+        );
+        let child_owner = AccountId::new_unchecked(child.owner_id.to_string());
+
+        let (chain, holder) = self.compose_chain(parent_id.0);
+        assert_eq!(holder, env::predecessor_account_id(), "not the holder");
+        assert!(!chain.contains(&child_id.0), "compose cycle");
+        let depth = self
+            .nft_token_internal(parent_id.0)
+            .composeable_stats
+            .local_depth
+            + 1;
+        assert!(
+            depth as u32 + self.compose_height(child_id.0) <= self.max_compose_depth,
+            "compose depth exceeds {}",
+            self.max_compose_depth
+        );
+
+        self.clear_owner_state(&mut child, "nft_compose");
+        self.update_tokens_per_owner(child.id, Some(child_owner), None);
+        child.owner_id = Owner::TokenId(parent_id.0);
+        self.tokens.insert(&child.id, &child);
+        self.update_composed_sets(child_id.0.to_string(), parent_id.0.to_string(), true);
+        self.set_compose_depth(child_id.0, depth);
+        log_nfts_compose(
+            &[child_id],
+            &parent_id.0.to_string(),
+            "t".to_string(),
+            chain.last().copied(),
+            holder.to_string(),
+            depth,
+        );
+    }
+
+    /// Uncompose the token `child_id` from its parent, returning it to the
+    /// caller together with its own composed children.
+    ///
+    /// Only the holder of the child, i.e. the owner of the token at the
+    /// root of its compose chain, may call this function.
+    #[payable]
+    pub fn nft_uncompose(
+        &mut self,
+        child_id: U64,
+    ) {
+        assert_one_yocto();
+        let child = self.nft_token_internal(child_id.0);
+        assert!(
+            matches!(child.owner_id, Owner::TokenId(_)),
+            "token not composed"
+        );
+        self.assert_pred_holder(&child);
+        self.uncompose_to_holder(child_id.0);
+    }
+
+    /// Approve composing the token `child_id` into the token `parent_id` by
+    /// whoever holds the parent when calling `nft_compose`, e.g. to add an
+    /// item to someone else's bundle. Replaces any previous compose
+    /// approval of the child. The approval is cleared when the child is
+    /// transferred or composed.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn nft_approve_compose(
+        &mut self,
+        child_id: U64,
+        parent_id: U64,
+    ) {
+        assert_one_yocto();
+        assert_ne!(child_id.0, parent_id.0, "compose cycle");
+        let mut child = self.nft_token_internal(child_id.0);
+        assert!(child.is_pred_owner());
+        // panics if the parent doesn't exist
+        self.nft_token_internal(parent_id.0);
+        child.compose_approval = Some(parent_id.0);
+        self.tokens.insert(&child.id, &child);
+    }
+
+    /// Revoke the compose approval of the token `child_id`, if any.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn nft_revoke_compose(
+        &mut self,
+        child_id: U64,
+    ) {
+        assert_one_yocto();
+        let mut child = self.nft_token_internal(child_id.0);
+        assert!(child.is_pred_owner());
+        child.compose_approval = None;
+        self.tokens.insert(&child.id, &child);
+    }
+
+    /// Transfer the token together with the tokens composed into it, which
+    /// follow the token to `receiver_id`. Unlike `nft_transfer`, a single
+    /// `nft_transfer` event lists the token and all of its descendants, such
+    /// that indexers can track the holders of composed tokens. The transfer
+    /// fee is due for each token of the bundle.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn nft_transfer_bundle(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: U64,
+        memo: Option<String>,
+    ) {
+        let mut tokens = vec![token_id];
+        tokens.extend(self.compose_descendants(token_id.0).into_iter().map(U64));
+        self.collect_transfer_fee(tokens.len() as u64, tokens.len() as u64);
+        let mut token = self.nft_token_internal(token_id.0);
+        token.assert_movable_by_owner();
+        assert!(token.is_pred_owner());
+        assert_ne!(receiver_id.to_string(), token.owner_id.to_string()); // can't transfer to self
+        self.assert_transfer_policy(&receiver_id);
+
+        let old_owner = token.owner_id.to_string();
+        self.transfer_internal(&mut token, receiver_id.clone(), true, "nft_transfer_bundle");
+        let accounts = vec![receiver_id; tokens.len()];
+        let old_owners = vec![old_owner; tokens.len()];
+        log_nft_batch_transfer(&tokens, &accounts, old_owners, &memo);
+    }
+
+    /// Compose the token `child_id` into the token `parent_id` on the
+    /// contract `parent_contract_id`. The child is locked while the parent
+    /// is queried via `nft_token`, and only composed in `on_cross_compose`
+    /// if the parent exists and is owned by the caller.
+    ///
+    /// The caller must own the child.
+    #[payable]
+    pub fn nft_cross_compose(
+        &mut self,
+        child_id: U64,
+        parent_id: U64,
+        parent_contract_id: AccountId,
+    ) -> Promise {
+        assert_one_yocto();
+        assert_ne!(
+            parent_contract_id,
+            env::current_account_id(),
+            "use nft_compose"
+        );
+        let mut child = self.nft_token_internal(child_id.0);
+        child.assert_movable_by_owner();
+        assert!(child.is_pred_owner());
+        // prevent race condition, temporarily lock-replace owner
+        self.lock_token(&mut child);
+
+        ext_compose_parent::nft_token(
+            parent_id,
+            parent_contract_id.clone(),
+            NO_DEPOSIT,
+            gas::NFT_TOKEN,
+        )
+        .then(store_self::on_cross_compose(
+            env::predecessor_account_id(),
+            child_id,
+            parent_id,
+            parent_contract_id,
+            env::current_account_id(),
+            NO_DEPOSIT,
+            gas::ON_CROSS_COMPOSE,
+        ))
+    }
+
+    /// Uncompose the token `child_id` from its parent on another contract,
+    /// returning it to the caller in `on_cross_uncompose` if the caller
+    /// owns the parent.
+    #[payable]
+    pub fn nft_cross_uncompose(
+        &mut self,
+        child_id: U64,
+    ) -> Promise {
+        assert_one_yocto();
+        let child = self.nft_token_internal(child_id.0);
+        let (parent_id, parent_contract_id) = match child.owner_id {
+            Owner::CrossKey(key) => key.split(),
+            _ => env::panic_str("token not cross-composed"),
+        };
+        let parent_contract_id = AccountId::new_unchecked(parent_contract_id);
+
+        ext_compose_parent::nft_token(
+            U64(parent_id),
+            parent_contract_id.clone(),
+            NO_DEPOSIT,
+            gas::NFT_TOKEN,
+        )
+        .then(store_self::on_cross_uncompose(
+            env::predecessor_account_id(),
+            child_id,
+            U64(parent_id),
+            parent_contract_id,
+            env::current_account_id(),
+            NO_DEPOSIT,
+            gas::ON_CROSS_COMPOSE,
+        ))
+    }
+
+    /// Limit the depth at which tokens may be composed, e.g. to keep the
+    /// gas for resolving the owners of composed tokens low. Tokens that are
+    /// already composed deeper remain composed.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_max_compose_depth(
+        &mut self,
+        max_compose_depth: u32,
+    ) {
+        self.assert_store_owner();
+        assert!(
+            max_compose_depth <= MAX_COMPOSE_DEPTH,
+            "max_compose_depth must be <= {}",
+            MAX_COMPOSE_DEPTH
+        );
+        self.max_compose_depth = max_compose_depth;
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    pub fn get_max_compose_depth(&self) -> u32 {
+        self.max_compose_depth
+    }
+
+    /// Get the number of tokens on this contract that `token_id` is
+    /// composed into, 0 if it is not composed into a token on this
+    /// contract.
+    pub fn nft_token_depth(
+        &self,
+        token_id: U64,
+    ) -> u8 {
+        self.nft_token_internal(token_id.0)
+            .composeable_stats
+            .local_depth
+    }
+
+    /// Get the tokens composed directly into `token_id`, in ascending order
+    /// of their ids. Tokens on other contracts are not included.
+    pub fn nft_token_children(
+        &self,
+        token_id: U64,
+        from_index: Option<String>,
+        limit: Option<usize>,
+    ) -> Vec<TokenCompliant> {
+        let mut child_ids = self.composed_children(token_id.0);
+        child_ids.sort_unstable();
+        child_ids
+            .into_iter()
+            .skip(
+                from_index
+                    .unwrap_or_else(|| "0".to_string())
+                    .parse()
+                    .unwrap(),
+            )
+            .take(limit.unwrap_or(10))
+            .map(|child_id| self.nft_token_compliant_internal(child_id))
+            .collect()
+    }
+
+    /// Get the token at the local root of the compose chain of `token_id`,
+    /// i.e. the token itself if it is not composed into a token on this
+    /// contract. A root that is cross-composed has a `TokenKey` as owner.
+    pub fn nft_token_root(
+        &self,
+        token_id: U64,
+    ) -> TokenCompliant {
+        let mut root_id = token_id.0;
+        while let Owner::TokenId(parent_id) = self.nft_token_internal(root_id).owner_id {
+            root_id = parent_id;
+        }
+        self.nft_token_compliant_internal(root_id)
+    }
+
+    // -------------------------- private methods --------------------------
+
+    #[private]
+    pub fn on_cross_compose(
+        &mut self,
+        owner_id: AccountId,
+        child_id: U64,
+        parent_id: U64,
+        parent_contract_id: AccountId,
+    ) -> bool {
+        let mut child = self.nft_token_internal(child_id.0);
+        if !self.unlock_token(&mut child) {
+            env::log_str(format!("token {} force-unlocked, compose aborted", child_id.0).as_str());
+            return false;
+        }
+        let parent_key = TokenKey::new(parent_id.0, parent_contract_id);
+        if queried_owner_id().as_deref() != Some(owner_id.as_str()) {
+            env::log_str(
+                format!(
+                    "{} not owned by {}, token {} kept",
+                    parent_key, owner_id, child_id.0
+                )
+                .as_str(),
+            );
+            return false;
+        }
+
+        self.clear_owner_state(&mut child, "on_cross_compose");
+        self.update_tokens_per_owner(child.id, Some(owner_id), None);
+        child.owner_id = Owner::CrossKey(parent_key.clone());
+        self.tokens.insert(&child.id, &child);
+        log_nfts_compose(
+            &[child_id],
+            &parent_key.to_string(),
+            "k".to_string(),
+            None,
+            parent_key.to_string(),
+            0,
+        );
+        true
+    }
+
+    #[private]
+    pub fn on_cross_uncompose(
+        &mut self,
+        holder_id: AccountId,
+        child_id: U64,
+        parent_id: U64,
+        parent_contract_id: AccountId,
+    ) -> bool {
+        let mut child = self.nft_token_internal(child_id.0);
+        let parent_key = TokenKey::new(parent_id.0, parent_contract_id);
+        // the child might have been uncomposed while the parent was queried
+        if child.owner_id.to_string() != parent_key.to_string() {
+            env::log_str(format!("token {} not composed into {}", child_id.0, parent_key).as_str());
+            return false;
+        }
+        if queried_owner_id().as_deref() != Some(holder_id.as_str()) {
+            env::log_str(format!("{} not owned by {}", parent_key, holder_id).as_str());
+            return false;
+        }
+
+        self.update_tokens_per_owner(child.id, None, Some(holder_id.clone()));
+        child.owner_id = Owner::Account(holder_id.clone());
+        self.tokens.insert(&child.id, &child);
+        log_nfts_uncompose(&[child_id], holder_id);
+        true
+    }
+
+    // -------------------------- internal methods -------------------------
+
+    /// Walk up from `token_id` to the local root of its compose chain,
+    /// returning the ids of the walked tokens, starting with `token_id`
+    /// and ending with the root, and the account owning the root.
+    pub(crate) fn compose_chain(
+        &self,
+        token_id: u64,
+    ) -> (Vec<u64>, AccountId) {
+        let mut chain = vec![token_id];
+        let mut owner = self.nft_token_internal(token_id).owner_id;
+        for _ in 0..=MAX_COMPOSE_DEPTH {
+            owner = match owner {
+                Owner::Account(account_id) => return (chain, account_id),
+                Owner::TokenId(parent_id) => {
+                    chain.push(parent_id);
+                    self.nft_token_internal(parent_id).owner_id
+                },
+                Owner::CrossKey(_) => env::panic_str("token is cross-composed"),
+                Owner::Lock(_) => env::panic_str("token locked"),
+            };
+        }
+        env::panic_str("token composed too deep")
+    }
+
+    /// Assert that the predecessor holds `token`, i.e. owns it or, if it is
+    /// composed, owns the root of its compose chain.
+    pub(crate) fn assert_pred_holder(
+        &self,
+        token: &Token,
+    ) {
+        match token.owner_id {
+            Owner::TokenId(_) | Owner::CrossKey(_) => assert_eq!(
+                self.compose_chain(token.id).1,
+                env::predecessor_account_id(),
+                "not the holder"
+            ),
+            _ => assert!(token.is_pred_owner()),
+        }
+    }
+
+    /// Uncompose `child_id` from its parent, returning it to its holder
+    /// together with its own composed children. Does nothing if the token
+    /// isn't composed.
+    pub(crate) fn uncompose_to_holder(
+        &mut self,
+        child_id: u64,
+    ) {
+        let mut child = self.nft_token_internal(child_id);
+        let parent_id = match child.owner_id {
+            Owner::TokenId(parent_id) => parent_id,
+            _ => return,
+        };
+        let (_, holder) = self.compose_chain(child_id);
+
+        self.update_composed_sets(child_id.to_string(), parent_id.to_string(), false);
+        self.update_tokens_per_owner(child.id, None, Some(holder.clone()));
+        child.owner_id = Owner::Account(holder.clone());
+        self.tokens.insert(&child.id, &child);
+        self.set_compose_depth(child_id, 0);
+        log_nfts_uncompose(&[U64(child_id)], holder);
+    }
+
+    /// Clear the approvals of the tokens composed into `token_id`, which
+    /// were granted by its previous holder, refunding their storage
+    /// deposits. Used when the holder changes.
+    pub(crate) fn clear_descendant_approvals(
+        &mut self,
+        token_id: u64,
+        method: &str,
+    ) {
+        let mut deposits = Vec::new();
+        for descendant_id in self.compose_descendants(token_id) {
+            let mut descendant = self.nft_token_internal(descendant_id);
+            if !descendant.approvals.is_empty() {
+                deposits.extend(descendant.clear_approvals());
+                self.tokens.insert(&descendant_id, &descendant);
+            }
+        }
+        self.refund_storage_deposits(deposits, method);
+    }
+
+    /// The ids of the tokens on this contract that are composed directly
+    /// into `token_id`.
+    pub(crate) fn composed_children(
+        &self,
+        token_id: u64,
+    ) -> Vec<u64> {
+        self.composeables
+            .get(&token_id.to_string())
+            .map(|children| {
+                children
+                    .iter()
+                    .filter_map(|child| child.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The ids of all tokens on this contract that are composed directly or
+    /// indirectly into `token_id`, each parent preceding its children.
+    pub(crate) fn compose_descendants(
+        &self,
+        token_id: u64,
+    ) -> Vec<u64> {
+        let mut child_ids = self.composed_children(token_id);
+        child_ids.sort_unstable();
+        child_ids
+            .into_iter()
+            .flat_map(|child_id| {
+                let mut descendants = vec![child_id];
+                descendants.extend(self.compose_descendants(child_id));
+                descendants
+            })
+            .collect()
+    }
+
+    /// Number of levels of tokens composed below `token_id`, 0 if it has no
+    /// composed children.
+    fn compose_height(
+        &self,
+        token_id: u64,
+    ) -> u32 {
+        self.composed_children(token_id)
+            .into_iter()
+            .map(|child_id| self.compose_height(child_id) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Set the `local_depth` of `token_id` to `depth`, and that of its
+    /// descendants accordingly.
+    fn set_compose_depth(
+        &mut self,
+        token_id: u64,
+        depth: u8,
+    ) {
+        let mut token = self.nft_token_internal(token_id);
+        token.composeable_stats.local_depth = depth;
+        self.tokens.insert(&token_id, &token);
+        self.composed_children(token_id)
+            .into_iter()
+            .for_each(|child_id| self.set_compose_depth(child_id, depth + 1));
+    }
+}
+
+/// The `owner_id` of the token returned by the preceding `nft_token` query,
+/// `None` if the token does not exist or the query failed.
+fn queried_owner_id() -> Option<String> {
+    match env::promise_result(0) {
+        PromiseResult::Successful(value) => {
+            serde_json::from_slice::<Option<serde_json::Value>>(&value)
+                .ok()
+                .flatten()
+                .and_then(|token| token.get("owner_id")?.as_str().map(String::from))
+        },
+        _ => None,
+    }
+}
@@ -1,24 +1,40 @@
 use std::collections::HashMap;
 use std::convert::TryFrom;
 
+use mintbase_deps::common::{
+    SplitOwners,
+    StoreError,
+    TransferSpec,
+};
 use mintbase_deps::constants::{
     gas,
     NO_DEPOSIT,
+    ONE_YOCTO,
 };
 // contract interface modules
 use mintbase_deps::interfaces::ext_on_transfer;
 // logging functions
 use mintbase_deps::logging::{
     log_nft_batch_transfer,
+    log_nft_force_unlock,
+    log_nft_freeze,
+    log_nft_resolve_transfer,
     log_nft_transfer,
+    log_set_transfer_fee,
+    TransferCallOutcome,
+};
+use mintbase_deps::near_sdk::json_types::{
+    U128,
+    U64,
 };
-use mintbase_deps::near_sdk::json_types::U64;
 use mintbase_deps::near_sdk::{
     self,
     assert_one_yocto,
     env,
+    is_promise_success,
     near_bindgen,
     AccountId,
+    Gas,
     Promise,
     PromiseResult,
 };
@@ -35,6 +51,19 @@ use crate::*;
 impl MintbaseStore {
     // -------------------------- change methods ---------------------------
 
+    /// If a transfer fee is set, accounts other than the store owner must
+    /// attach it instead of one yoctoNEAR, see `set_transfer_fee`.
+    ///
+    /// If `verify_receiver` is set, the token is locked and one yoctoNEAR is
+    /// forwarded to `receiver_id`. The transfer is only
+    /// finalized in `nft_resolve_verified_transfer` if the receiving account
+    /// exists, otherwise the token remains with its current owner. The
+    /// yoctoNEAR sent to a missing account returns to the `Store` and is not
+    /// refunded to the caller, as the refund would cost more than it is
+    /// worth.
+    ///
+    /// Under a transfer policy hook, the token is likewise locked until the
+    /// hook approved the transfer in `on_check_transfer`.
     #[payable]
     pub fn nft_transfer(
         &mut self,
@@ -42,42 +71,59 @@ impl MintbaseStore {
         token_id: U64,
         approval_id: Option<u64>,
         memo: Option<String>,
-    ) {
-        assert_one_yocto();
-        let token_idu64 = token_id.into();
-        let mut token = self.nft_token_internal(token_idu64);
-        let old_owner = token.owner_id.to_string();
-        assert!(!token.is_loaned());
-        if !token.is_pred_owner() {
-            assert!(self.nft_is_approved_internal(
-                &token,
-                env::predecessor_account_id(),
-                approval_id
-            ));
-        }
-
-        self.transfer_internal(&mut token, receiver_id.clone(), true);
-        log_nft_transfer(&receiver_id, token_idu64, &memo, old_owner);
+        verify_receiver: Option<bool>,
+    ) -> Option<Promise> {
+        self.collect_transfer_fee(1, 1);
+        self.nft_transfer_internal(
+            receiver_id,
+            token_id,
+            approval_id,
+            memo,
+            verify_receiver,
+            "nft_transfer",
+        )
     }
 
+    /// `gas_for_receiver` overrides the gas forwarded to `nft_on_transfer`,
+    /// e.g. for receivers that do more work, up to `NFT_ON_TRANSFER_MAX`.
+    /// The caller must attach enough gas to cover it.
+    ///
+    /// Like for `nft_transfer`, a transfer fee may be required instead of
+    /// one yoctoNEAR.
     #[payable]
     pub fn nft_transfer_call(
         &mut self,
         receiver_id: AccountId,
         token_id: U64,
         approval_id: Option<u64>,
+        memo: Option<String>,
         msg: String,
+        gas_for_receiver: Option<U64>,
     ) -> Promise {
-        assert_one_yocto();
+        self.collect_transfer_fee(1, 1);
+        let gas_for_receiver = gas_for_receiver
+            .map(|gas| Gas(gas.0))
+            .unwrap_or(gas::NFT_ON_TRANSFER);
+        assert!(
+            gas_for_receiver <= gas::NFT_ON_TRANSFER_MAX,
+            "gas_for_receiver: {} must be <= {}",
+            gas_for_receiver.0,
+            gas::NFT_ON_TRANSFER_MAX.0
+        );
         let token_idu64 = token_id.into();
         let mut token = self.nft_token_internal(token_idu64);
-        assert!(!token.is_loaned());
+        token.assert_movable_by_owner();
         let pred = env::predecessor_account_id();
         if !token.is_pred_owner() {
             // check if pred has an approval
             let approval_id: Option<u64> = approval_id;
+            // operators don't rely on an approval that might have expired
+            if !self.is_operator_internal(&token.owner_id.to_string(), &pred) {
+                token.assert_approval_not_expired(&pred);
+            }
             assert!(self.nft_is_approved_internal(&token, pred.clone(), approval_id));
         }
+        self.assert_transfer_policy(&receiver_id);
         // prevent race condition, temporarily lock-replace owner
         let owner_id = AccountId::new_unchecked(token.owner_id.to_string());
         self.lock_token(&mut token);
@@ -89,25 +135,30 @@ impl MintbaseStore {
             msg,
             receiver_id.clone(),
             NO_DEPOSIT,
-            gas::NFT_TRANSFER_CALL,
+            gas_for_receiver,
         )
         .then(store_self::nft_resolve_transfer(
             owner_id,
             receiver_id,
             token_id.0.to_string(),
             None,
+            memo,
             env::current_account_id(),
             NO_DEPOSIT,
-            gas::NFT_TRANSFER_CALL,
+            gas::RESOLVE_TRANSFER_CALL,
         ))
     }
 
     // -------------------------- view methods -----------------------------
 
+    /// Expired tokens are treated as invalid, and thus return `None`.
     pub fn nft_token(
         &self,
         token_id: U64,
     ) -> Option<TokenCompliant> {
+        if self.nft_token_internal(token_id.0).is_expired() {
+            return None;
+        }
         Some(self.nft_token_compliant_internal(token_id.0))
     }
 
@@ -121,6 +172,7 @@ impl MintbaseStore {
         token_id: String,
         // NOTE: might borsh::maybestd::collections::HashMap be more appropriate?
         approved_account_ids: Option<HashMap<AccountId, u64>>,
+        memo: Option<String>,
     ) -> bool {
         let l = format!(
             "owner_id={} receiver_id={} token_id={} approved_ids={:?} pred={}",
@@ -133,25 +185,76 @@ impl MintbaseStore {
         env::log_str(l.as_str());
         let token_id_u64 = token_id.parse::<u64>().unwrap();
         let mut token = self.nft_token_internal(token_id_u64);
-        self.unlock_token(&mut token);
+        if !self.unlock_token(&mut token) {
+            env::log_str(
+                format!("token {} force-unlocked, transfer aborted", token_id_u64).as_str(),
+            );
+            return false;
+        }
         assert_eq!(env::promise_results_count(), 1);
-        // Get whether token should be returned
-        let must_revert = match env::promise_result(0) {
+        // Only an explicit `false` lets the receiver keep the token, anything
+        // else returns it to `owner_id`
+        let outcome = match env::promise_result(0) {
             PromiseResult::NotReady => unreachable!(),
             PromiseResult::Successful(value) => {
-                if let Ok(yes_or_no) = near_sdk::serde_json::from_slice::<bool>(&value) {
-                    yes_or_no
-                } else {
-                    true
+                match near_sdk::serde_json::from_slice::<bool>(&value) {
+                    Ok(false) => TransferCallOutcome::Kept,
+                    Ok(true) => TransferCallOutcome::Returned,
+                    Err(_) => TransferCallOutcome::Malformed,
                 }
             },
-            PromiseResult::Failed => true,
+            PromiseResult::Failed => TransferCallOutcome::Failed,
         };
-        if !must_revert {
+        let transferred = matches!(outcome, TransferCallOutcome::Kept);
+        if transferred {
+            self.transfer_internal(
+                &mut token,
+                receiver_id.clone(),
+                true,
+                "nft_resolve_transfer",
+            );
+            log_nft_transfer(&receiver_id, token_id_u64, &memo, owner_id.to_string());
+        }
+        log_nft_resolve_transfer(token_id_u64, &owner_id, &receiver_id, outcome, transferred);
+        transferred
+    }
+
+    /// Finalize an `nft_transfer` with `verify_receiver` set. If the
+    /// yoctoNEAR transfer to `receiver_id` failed, the account does not
+    /// exist and the token is unlocked for `owner_id` again. The forwarded
+    /// yoctoNEAR then returns to the `Store`.
+    ///
+    /// Returns true if the token was transferred to `receiver_id`.
+    #[private]
+    pub fn nft_resolve_verified_transfer(
+        &mut self,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: U64,
+        memo: Option<String>,
+    ) -> bool {
+        let mut token = self.nft_token_internal(token_id.0);
+        if !self.unlock_token(&mut token) {
+            env::log_str(format!("token {} force-unlocked, transfer aborted", token_id.0).as_str());
+            return false;
+        }
+        if is_promise_success() {
+            self.transfer_internal(
+                &mut token,
+                receiver_id.clone(),
+                true,
+                "nft_resolve_verified_transfer",
+            );
+            log_nft_transfer(&receiver_id, token_id.0, &memo, owner_id.to_string());
             true
         } else {
-            self.transfer_internal(&mut token, receiver_id.clone(), true);
-            log_nft_transfer(&receiver_id, token_id_u64, &None, owner_id.to_string());
+            env::log_str(
+                format!(
+                    "receiver {} does not exist, token {} kept by {}",
+                    receiver_id, token_id.0, owner_id
+                )
+                .as_str(),
+            );
             false
         }
     }
@@ -162,13 +265,20 @@ impl MintbaseStore {
 impl MintbaseStore {
     // -------------------------- change methods ---------------------------
 
+    /// Transfer many tokens of the caller at once, each to its own
+    /// receiver, e.g. to airdrop tokens from a vault account. Emits a single
+    /// `nft_transfer` event, listing the tokens of each receiver together,
+    /// each with the given `memo`. The transfer fee is due per token.
+    ///
+    /// Only the token owner may call this function.
     #[payable]
     pub fn nft_batch_transfer(
         &mut self,
         token_ids: Vec<(U64, AccountId)>,
+        memo: Option<String>,
     ) {
-        near_sdk::assert_one_yocto();
         assert!(!token_ids.is_empty());
+        self.collect_transfer_fee(token_ids.len() as u64, token_ids.len() as u64);
         let pred = env::predecessor_account_id();
         let mut set_owned = self.tokens_per_owner.get(&pred).expect("none owned");
         let (tokens, accounts, old_owners) = token_ids
@@ -177,10 +287,11 @@ impl MintbaseStore {
                 let token_idu64 = token_id.into();
                 let mut token = self.nft_token_internal(token_idu64);
                 let old_owner = token.owner_id.to_string();
-                assert!(!token.is_loaned());
+                token.assert_movable_by_owner();
                 assert!(token.is_pred_owner());
                 assert_ne!(account_id.to_string(), token.owner_id.to_string()); // can't transfer to self
-                self.transfer_internal(&mut token, account_id.clone(), false);
+                self.assert_transfer_policy(&account_id);
+                self.transfer_internal(&mut token, account_id.clone(), false, "nft_batch_transfer");
                 set_owned.remove(&token_idu64);
                 (token_id, account_id, old_owner)
             })
@@ -191,19 +302,464 @@ impl MintbaseStore {
                 acc
             });
         self.tokens_per_owner.insert(&pred, &set_owned);
-        log_nft_batch_transfer(&tokens, &accounts, old_owners);
+        log_nft_batch_transfer(&tokens, &accounts, old_owners, &memo);
+    }
+
+    /// Attempt each of `transfers` independently, such that a failing
+    /// transfer doesn't abort the others, e.g. for custodial wallets moving
+    /// many tokens at once. The outcome of each transfer is reported in the
+    /// same order as `transfers`. The batch as a whole still aborts if it
+    /// runs out of gas. The transfer fee is due for each of `transfers`, and
+    /// refunded for those that fail.
+    #[payable]
+    pub fn nft_batch_transfer_checked(
+        &mut self,
+        transfers: Vec<TransferSpec>,
+        memo: Option<String>,
+    ) -> Vec<Result<(), StoreError>> {
+        let max_tokens = transfers.len() as u64;
+        let mut tokens = vec![];
+        let mut accounts = vec![];
+        let mut old_owners = vec![];
+        let results = transfers
+            .into_iter()
+            .map(|transfer| {
+                let mut token = self.check_transfer(&transfer)?;
+                old_owners.push(token.owner_id.to_string());
+                self.transfer_internal(
+                    &mut token,
+                    transfer.receiver_id.clone(),
+                    true,
+                    "nft_batch_transfer_checked",
+                );
+                tokens.push(transfer.token_id);
+                accounts.push(transfer.receiver_id);
+                Ok(())
+            })
+            .collect();
+        self.collect_transfer_fee(max_tokens, tokens.len() as u64);
+        if !tokens.is_empty() {
+            log_nft_batch_transfer(&tokens, &accounts, old_owners, &memo);
+        }
+        results
+    }
+
+    /// Transfer many tokens of the caller to `receiver_id` at once, and
+    /// notify the receiver with a single `nft_on_batch_transfer` call, e.g.
+    /// to deposit tokens into staking or bundling contracts. The receiver
+    /// decides for each token whether to keep it, see
+    /// `nft_resolve_batch_transfer`. The transfer fee is due per token.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn nft_batch_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_ids: Vec<U64>,
+        memo: Option<String>,
+        msg: String,
+    ) -> Promise {
+        assert!(!token_ids.is_empty());
+        self.collect_transfer_fee(token_ids.len() as u64, token_ids.len() as u64);
+        let pred = env::predecessor_account_id();
+        assert_ne!(receiver_id, pred); // can't transfer to self
+        self.assert_transfer_policy(&receiver_id);
+        token_ids.iter().for_each(|token_id| {
+            let mut token = self.nft_token_internal(token_id.0);
+            // also rejects duplicate tokens, which are locked by now
+            token.assert_movable_by_owner();
+            assert!(token.is_pred_owner());
+            // prevent race condition, temporarily lock-replace owner
+            self.lock_token(&mut token);
+        });
+
+        ext_on_transfer::nft_on_batch_transfer(
+            pred.clone(),
+            pred.clone(),
+            token_ids.clone(),
+            msg,
+            receiver_id.clone(),
+            NO_DEPOSIT,
+            gas::NFT_ON_BATCH_TRANSFER,
+        )
+        .then(store_self::nft_resolve_batch_transfer(
+            pred,
+            receiver_id,
+            token_ids,
+            memo,
+            env::current_account_id(),
+            NO_DEPOSIT,
+            gas::RESOLVE_BATCH_TRANSFER,
+        ))
+    }
+
+    /// Transfer the tokens of the caller to `receiver_id`, e.g. when
+    /// rotating to a new account. Up to `limit` tokens are examined per
+    /// call, in ascending order starting at the `from_index`th owned token.
+    /// Tokens that may not be transferred, e.g. because they are loaned or
+    /// frozen, are skipped and remain with the caller. The transfer fee is
+    /// due for `limit` tokens, and refunded for those not transferred.
+    ///
+    /// Returns the `from_index` for the next call, or `None` if all tokens
+    /// have been examined. As transferred tokens are no longer owned by the
+    /// caller, this is the number of skipped tokens so far.
+    #[payable]
+    pub fn nft_transfer_all(
+        &mut self,
+        receiver_id: AccountId,
+        limit: u32,
+        from_index: Option<U64>,
+        memo: Option<String>,
+    ) -> Option<U64> {
+        assert!(limit > 0);
+        assert!(limit <= 50); // upper gas limit
+        let pred = env::predecessor_account_id();
+        let from_index = from_index.map(|index| index.0).unwrap_or(0);
+        let token_ids = self
+            .tokens_per_owner
+            .get(&pred)
+            .map(|owned| {
+                owned
+                    .iter_from(from_index)
+                    .take(limit as usize)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let mut tokens = vec![];
+        let mut skipped = 0;
+        token_ids.into_iter().for_each(|token_id| {
+            let transfer = TransferSpec {
+                token_id: U64(token_id),
+                receiver_id: receiver_id.clone(),
+                approval_id: None,
+            };
+            match self.check_transfer(&transfer) {
+                Ok(mut token) => {
+                    self.transfer_internal(
+                        &mut token,
+                        receiver_id.clone(),
+                        true,
+                        "nft_transfer_all",
+                    );
+                    tokens.push(transfer.token_id);
+                },
+                Err(_) => skipped += 1,
+            }
+        });
+        self.collect_transfer_fee(limit as u64, tokens.len() as u64);
+        if !tokens.is_empty() {
+            let accounts = vec![receiver_id; tokens.len()];
+            let old_owners = vec![pred.to_string(); tokens.len()];
+            log_nft_batch_transfer(&tokens, &accounts, old_owners, &memo);
+        }
+
+        let next_index = from_index + skipped;
+        let remaining = self
+            .tokens_per_owner
+            .get(&pred)
+            .map(|owned| owned.len())
+            .unwrap_or(0);
+        (next_index < remaining).then_some(U64(next_index))
+    }
+
+    /// Freeze a token into "vault mode": neither the owner nor any approved
+    /// account may transfer it, and no new approvals may be granted, until
+    /// the owner calls `unfreeze_token`. This protects e.g. tokens in cold
+    /// storage in case a hot key is compromised. The freeze is shown as
+    /// `frozen` by `nft_token` and emitted as `nft_freeze` event.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn freeze_token(
+        &mut self,
+        token_id: U64,
+    ) {
+        self.set_token_frozen(token_id.into(), true);
+    }
+
+    /// Lift the freeze set by `freeze_token`, emitting an `nft_unfreeze`
+    /// event.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn unfreeze_token(
+        &mut self,
+        token_id: U64,
+    ) {
+        self.set_token_frozen(token_id.into(), false);
+    }
+
+    /// Same as `freeze_token`.
+    #[payable]
+    pub fn freeze_my_token(
+        &mut self,
+        token_id: U64,
+    ) {
+        self.set_token_frozen(token_id.into(), true);
+    }
+
+    /// Same as `unfreeze_token`.
+    #[payable]
+    pub fn unfreeze_my_token(
+        &mut self,
+        token_id: U64,
+    ) {
+        self.set_token_frozen(token_id.into(), false);
+    }
+
+    /// Unlock a token that has been locked for longer than the lock timeout,
+    /// e.g. because the callback releasing it never resolved. A callback
+    /// that resolves afterwards leaves the token with its owner.
+    ///
+    /// Only the account for which the token is locked may call this
+    /// function.
+    #[payable]
+    pub fn force_unlock(
+        &mut self,
+        token_id: U64,
+    ) {
+        assert_one_yocto();
+        let mut token = self.nft_token_internal(token_id.into());
+        match token.owner_id {
+            Owner::Lock(ref account_id) => assert_eq!(
+                *account_id,
+                env::predecessor_account_id(),
+                "not locked for caller"
+            ),
+            _ => env::panic_str("token not locked"),
+        }
+        let timeout_ns = self.lock_timeout_minutes * 60 * 10u64.pow(9);
+        let locked_at = token.locked_at.as_ref().map(|t| t.0).unwrap_or(0);
+        assert!(
+            env::block_timestamp() >= locked_at + timeout_ns,
+            "lock timeout not reached"
+        );
+        self.unlock_token(&mut token);
+        log_nft_force_unlock(token.id, &env::predecessor_account_id());
+    }
+
+    /// Set the number of minutes after which locked tokens may be unlocked
+    /// via `force_unlock`.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_lock_timeout(
+        &mut self,
+        minutes: u64,
+    ) {
+        self.assert_store_owner();
+        assert!(minutes > 0, "lock timeout cannot be zero");
+        self.lock_timeout_minutes = minutes;
+    }
+
+    /// Set the fee in yoctoNEAR per token that accounts other than the store
+    /// owner must attach when transferring tokens to other accounts, i.e. to
+    /// `nft_transfer`, `nft_transfer_call`, the batch transfer methods,
+    /// `nft_transfer_all`, `nft_transfer_bundle`, `gift_token`,
+    /// `nft_offer_transfer` and `nft_initiate_transfer`. The fee is
+    /// forwarded to the store owner, and not refunded if the transfer is
+    /// aborted, declined or the token is returned. Setting the fee to zero
+    /// disables it.
+    ///
+    /// Exempt are sales via markets, i.e. `nft_transfer_payout`, as well as
+    /// composing, leasing and migrating tokens. Claiming gifts, accepting
+    /// offers and co-signing transfers are free, as the fee was paid when
+    /// they were initiated.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_transfer_fee(
+        &mut self,
+        transfer_fee: U128,
+    ) {
+        self.assert_store_owner();
+        self.transfer_fee = transfer_fee.into();
+        log_set_transfer_fee(transfer_fee);
     }
 
     // -------------------------- view methods -----------------------------
 
+    pub fn get_lock_timeout(&self) -> u64 {
+        self.lock_timeout_minutes
+    }
+
+    /// Get the fee in yoctoNEAR per transfer.
+    pub fn get_transfer_fee(&self) -> U128 {
+        self.transfer_fee.into()
+    }
+
     // -------------------------- private methods --------------------------
 
+    #[private]
+    pub fn nft_resolve_batch_transfer(
+        &mut self,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        token_ids: Vec<U64>,
+        memo: Option<String>,
+    ) -> Vec<bool> {
+        assert_eq!(env::promise_results_count(), 1);
+        // Only an explicit `false` lets the receiver keep a token, anything
+        // else returns it to `owner_id`. Unless the receiver responded with
+        // one boolean per token, all tokens are returned.
+        let outcomes = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => {
+                match near_sdk::serde_json::from_slice::<Vec<bool>>(&value) {
+                    Ok(returns) if returns.len() == token_ids.len() => returns
+                        .into_iter()
+                        .map(|returned| {
+                            if returned {
+                                TransferCallOutcome::Returned
+                            } else {
+                                TransferCallOutcome::Kept
+                            }
+                        })
+                        .collect(),
+                    _ => vec![TransferCallOutcome::Malformed; token_ids.len()],
+                }
+            },
+            PromiseResult::Failed => vec![TransferCallOutcome::Failed; token_ids.len()],
+        };
+
+        let mut kept = vec![];
+        let results = token_ids
+            .iter()
+            .zip(outcomes)
+            .map(|(token_id, outcome)| {
+                let mut token = self.nft_token_internal(token_id.0);
+                if !self.unlock_token(&mut token) {
+                    env::log_str(
+                        format!("token {} force-unlocked, transfer aborted", token_id.0).as_str(),
+                    );
+                    return false;
+                }
+                let transferred = matches!(outcome, TransferCallOutcome::Kept);
+                if transferred {
+                    self.transfer_internal(
+                        &mut token,
+                        receiver_id.clone(),
+                        true,
+                        "nft_resolve_batch_transfer",
+                    );
+                    kept.push(*token_id);
+                }
+                log_nft_resolve_transfer(token_id.0, &owner_id, &receiver_id, outcome, transferred);
+                transferred
+            })
+            .collect();
+        if !kept.is_empty() {
+            let accounts = vec![receiver_id; kept.len()];
+            let old_owners = vec![owner_id.to_string(); kept.len()];
+            log_nft_batch_transfer(&kept, &accounts, old_owners, &memo);
+        }
+        results
+    }
+
     // -------------------------- internal methods -------------------------
 
+    /// `nft_transfer` without the transfer fee, which is also used to
+    /// transfer tokens sold via `nft_transfer_payout`. Refunds are logged as
+    /// `method`.
+    pub(crate) fn nft_transfer_internal(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: U64,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        verify_receiver: Option<bool>,
+        method: &str,
+    ) -> Option<Promise> {
+        let token_idu64 = token_id.into();
+        let mut token = self.nft_token_internal(token_idu64);
+        token.assert_movable_by_owner();
+        let old_owner = token.owner_id.to_string();
+        let pred = env::predecessor_account_id();
+        if !token.is_pred_owner() {
+            // operators don't rely on an approval that might have expired
+            if !self.is_operator_internal(&old_owner, &pred) {
+                token.assert_approval_not_expired(&pred);
+            }
+            assert!(self.nft_is_approved_internal(&token, pred, approval_id));
+        }
+        // composed tokens move with their parent unless sold, see
+        // `nft_transfer_payout`
+        assert!(
+            matches!(token.owner_id, Owner::Account(_)),
+            "token is composed"
+        );
+
+        if let Some(hook_id) = self.transfer_policy_hook() {
+            assert!(
+                !verify_receiver.unwrap_or(false),
+                "verify_receiver not supported by transfer policy hook"
+            );
+            return Some(self.transfer_via_policy_hook(&mut token, hook_id, receiver_id, memo));
+        }
+        self.assert_transfer_policy(&receiver_id);
+
+        if verify_receiver.unwrap_or(false) {
+            // prevent race condition, temporarily lock-replace owner
+            let owner_id = AccountId::new_unchecked(old_owner);
+            self.lock_token(&mut token);
+
+            // transfers to non-existing accounts fail, refunding the yocto to
+            // the store
+            Promise::new(receiver_id.clone())
+                .transfer(ONE_YOCTO)
+                .then(store_self::nft_resolve_verified_transfer(
+                    owner_id,
+                    receiver_id,
+                    token_id,
+                    memo,
+                    env::current_account_id(),
+                    NO_DEPOSIT,
+                    gas::RESOLVE_TRANSFER,
+                ))
+                .into()
+        } else {
+            self.transfer_internal(&mut token, receiver_id.clone(), true, method);
+            log_nft_transfer(&receiver_id, token_idu64, &memo, old_owner);
+            None
+        }
+    }
+
+    /// Assert that the transfer fee for `max_tokens` tokens is attached, and
+    /// forward the fee for the `num_tokens` tokens that were transferred to
+    /// the store owner, refunding the rest to the caller. Without a transfer
+    /// fee, or if the caller is the store owner, exactly one yoctoNEAR must
+    /// be attached instead.
+    pub(crate) fn collect_transfer_fee(
+        &self,
+        max_tokens: u64,
+        num_tokens: u64,
+    ) {
+        if self.transfer_fee == 0 || env::predecessor_account_id() == self.owner_id {
+            assert_one_yocto();
+            return;
+        }
+        let max_fee = self.transfer_fee * max_tokens as u128;
+        assert!(
+            env::attached_deposit() == max_fee,
+            "attached: {}; transfer fee: {}",
+            env::attached_deposit(),
+            max_fee
+        );
+        if num_tokens > 0 {
+            Promise::new(self.owner_id.clone()).transfer(self.transfer_fee * num_tokens as u128);
+        }
+        if num_tokens < max_tokens {
+            Promise::new(env::predecessor_account_id())
+                .transfer(self.transfer_fee * (max_tokens - num_tokens) as u128);
+        }
+    }
+
     /// Set the owner of `token` to `to` and clear the approvals on the
-    /// token. Update the `tokens_per_owner` sets. `remove_prior` is an
-    /// optimization on batch removal, in particular useful for batch sending
-    /// of tokens.
+    /// token and its composed descendants, refunding deposits taken for the
+    /// cleared state as `method`. Update the `tokens_per_owner` sets.
+    /// `remove_prior` is an optimization on batch removal, in particular
+    /// useful for batch sending of tokens.
     ///
     /// If remove prior is true, expect that the token is not composed, and
     /// remove the token owner from self.tokens_per_owner.
@@ -212,19 +768,102 @@ impl MintbaseStore {
         token: &mut Token,
         to: AccountId,
         remove_prior: bool,
+        method: &str,
     ) {
         let update_set = if remove_prior {
             Some(AccountId::try_from(token.owner_id.to_string()).unwrap())
         } else {
             None
         };
-        token.split_owners = None;
+        self.clear_owner_state(token, method);
+        self.clear_descendant_approvals(token.id, method);
         self.update_tokens_per_owner(token.id, update_set, Some(to.clone()));
         token.owner_id = Owner::Account(to);
-        token.approvals.clear();
         self.tokens.insert(&token.id, token);
     }
 
+    /// Clear the state of `token` that was set by or for its current owner,
+    /// i.e. approvals, co-signing, offers, leases, payout overrides and
+    /// compose approvals, as well as split owners that do not survive
+    /// transfers (see `SplitOwners::survives_transfer`). The storage deposits
+    /// recorded for approvals and split owners are refunded as `method`. Used
+    /// when the owner changes.
+    pub(crate) fn clear_owner_state(
+        &self,
+        token: &mut Token,
+        method: &str,
+    ) {
+        let mut deposits = token.clear_approvals();
+        if !token
+            .split_owners
+            .as_ref()
+            .map(SplitOwners::survives_transfer)
+            .unwrap_or(false)
+        {
+            deposits.extend(token.split_owners.take().and_then(|splits| splits.deposit));
+        }
+        token.co_signer = None;
+        token.pending_transfer = None;
+        token.transfer_offer = None;
+        token.lease = None;
+        token.payout_override = None;
+        token.compose_approval = None;
+        self.refund_storage_deposits(deposits, method);
+    }
+
+    /// Get the token of `transfer` if the predecessor may transfer it,
+    /// otherwise the reason why it may not.
+    fn check_transfer(
+        &self,
+        transfer: &TransferSpec,
+    ) -> Result<Token, StoreError> {
+        let token = self
+            .tokens
+            .get(&transfer.token_id.into())
+            .ok_or(StoreError::TokenNotFound)?;
+        token.check_movable_by_owner()?;
+        // composed tokens move with their parent
+        if !matches!(token.owner_id, Owner::Account(_)) {
+            return Err(StoreError::TokenLocked);
+        }
+        if !token.is_pred_owner()
+            && !self
+                .is_operator_internal(&token.owner_id.to_string(), &env::predecessor_account_id())
+        {
+            let approved = transfer
+                .approval_id
+                .map(|approval_id| {
+                    token.approvals.get(&env::predecessor_account_id()) == Some(&approval_id)
+                })
+                .unwrap_or(false);
+            if !approved {
+                return Err(StoreError::NotApproved);
+            }
+            if token.is_approval_expired(&env::predecessor_account_id()) {
+                return Err(StoreError::ApprovalExpired);
+            }
+        }
+        if transfer.receiver_id.to_string() == token.owner_id.to_string() {
+            return Err(StoreError::TransferToSelf);
+        }
+        self.check_transfer_policy(&transfer.receiver_id)?;
+        Ok(token)
+    }
+
+    fn set_token_frozen(
+        &mut self,
+        token_id: u64,
+        frozen: bool,
+    ) {
+        assert_one_yocto();
+        let mut token = self.nft_token_internal(token_id);
+        assert!(token.is_pred_owner());
+        assert_ne!(token.frozen, frozen);
+        token.frozen = frozen;
+        self.tokens.insert(&token_id, &token);
+        log_nft_freeze(token_id, &env::predecessor_account_id(), frozen);
+    }
+
     // TODO: documentation
     pub(crate) fn nft_token_internal(
         &self,
@@ -258,7 +897,12 @@ impl MintbaseStore {
                     extra: metadata.extra,
                     reference: metadata.reference,
                     reference_hash: metadata.reference_hash,
+                    media_size_bytes: metadata.media_size_bytes,
+                    media_width: metadata.media_width,
+                    media_height: metadata.media_height,
+                    animation_url: metadata.animation_url,
                 };
+                let edition_number = x.edition_number();
                 TokenCompliant {
                     token_id: format!("{}", x.id),
                     owner_id: x.owner_id,
@@ -270,6 +914,17 @@ impl MintbaseStore {
                     loan: x.loan,
                     composeable_stats: x.composeable_stats,
                     origin_key: x.origin_key,
+                    frozen: x.frozen,
+                    co_signer: x.co_signer,
+                    pending_transfer: x.pending_transfer,
+                    payout_override: x.payout_override,
+                    soulbound: x.soulbound,
+                    edition_number,
+                    expires_at: x.expires_at,
+                    transfer_offer: x.transfer_offer,
+                    lease: x.lease,
+                    locked_at: x.locked_at,
+                    compose_approval: x.compose_approval,
                 }
             })
             .unwrap_or_else(|| panic!("token: {} doesn't exist", token_id))
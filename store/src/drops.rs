@@ -0,0 +1,75 @@
+use mintbase_deps::common::{
+    MintWindow,
+    NearTime,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    near_bindgen,
+    AccountId,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Only allow minting between `mint_start` and `mint_end`. If `minter`
+    /// is set, the window only applies to that minter and takes precedence
+    /// over the window of the `Store`. Unsetting both bounds removes the
+    /// window.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_mint_window(
+        &mut self,
+        minter: Option<AccountId>,
+        mint_start: Option<NearTime>,
+        mint_end: Option<NearTime>,
+    ) {
+        self.assert_store_owner();
+        let window = match (mint_start, mint_end) {
+            (None, None) => None,
+            (mint_start, mint_end) => Some(MintWindow::new(mint_start, mint_end)),
+        };
+        match (minter, window) {
+            (None, window) => self.mint_window = window,
+            (Some(minter), Some(window)) => {
+                self.minter_mint_windows.insert(&minter, &window);
+            },
+            (Some(minter), None) => {
+                self.minter_mint_windows.remove(&minter);
+            },
+        }
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the window within which `minter` may mint, or the window of the
+    /// `Store` if `minter` is unset.
+    pub fn get_mint_window(
+        &self,
+        minter: Option<AccountId>,
+    ) -> Option<MintWindow> {
+        match minter {
+            Some(minter) => self
+                .minter_mint_windows
+                .get(&minter)
+                .or_else(|| self.mint_window.clone()),
+            None => self.mint_window.clone(),
+        }
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+
+    /// Panics if `minter_id` may not mint at the current block timestamp.
+    pub(crate) fn assert_mint_window_open(
+        &self,
+        minter_id: &AccountId,
+    ) {
+        if let Some(window) = self.get_mint_window(Some(minter_id.clone())) {
+            window.assert_open();
+        }
+    }
+}
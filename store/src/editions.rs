@@ -0,0 +1,212 @@
+use mintbase_deps::common::{
+    Activity,
+    NearTime,
+    OpenEdition,
+    Royalty,
+    RoyaltyArgs,
+    TokenMetadata,
+};
+use mintbase_deps::logging::{
+    log_storage_deposit,
+    StorageDepositAction,
+};
+use mintbase_deps::near_sdk::json_types::{
+    U128,
+    U64,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    Balance,
+    Promise,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Register an open edition, of which anyone may mint copies via
+    /// `nft_mint_edition` for `price` yoctoNEAR each, until `mint_end` if
+    /// given. Copies are minted on behalf of the store owner. Returns the
+    /// id of the edition.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn register_open_edition(
+        &mut self,
+        metadata: TokenMetadata,
+        royalty_args: Option<RoyaltyArgs>,
+        price: U128,
+        mint_end: Option<NearTime>,
+    ) -> U64 {
+        self.assert_store_owner();
+        self.assert_royalty_within_cap(&royalty_args);
+        let (metadata, _) = TokenMetadata::from_with_size(metadata, 1);
+        let edition_id = self.open_editions_registered;
+        let edition = OpenEdition {
+            edition_id,
+            metadata,
+            royalty: royalty_args.map(Royalty::new),
+            price,
+            mint_end,
+            minted: 0,
+            lookup_id: None,
+        };
+        self.open_editions.insert(&edition_id, &edition);
+        self.open_editions_registered += 1;
+        edition_id.into()
+    }
+
+    /// End an open edition, such that no further copies may be minted.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn end_open_edition(
+        &mut self,
+        edition_id: U64,
+    ) {
+        self.assert_store_owner();
+        let mut edition = self.get_open_edition_internal(edition_id.into());
+        assert!(edition.is_open(), "edition ended");
+        edition.mint_end = Some(NearTime(env::block_timestamp()));
+        self.open_editions.insert(&edition.edition_id, &edition);
+    }
+
+    /// Mint a copy of an open edition to the caller.
+    ///
+    /// The attached deposit must cover the edition price, which is paid to
+    /// the store owner, and the storage of the minted token. Any excess is
+    /// refunded.
+    #[payable]
+    pub fn nft_mint_edition(
+        &mut self,
+        edition_id: U64,
+    ) {
+        let mut edition = self.get_open_edition_internal(edition_id.into());
+        assert!(edition.is_open(), "edition ended");
+        let owner_id = env::predecessor_account_id();
+        let minter_id = self.owner_id.clone();
+        self.assert_not_banned_from_minting(&owner_id);
+        self.assert_mint_window_open(&minter_id);
+        self.record_activity(Activity::Mint, 1);
+
+        // copies share the metadata and royalty stored with the first copy,
+        // unless all copies have been burned or the copy count is exhausted
+        let lookup_id = edition.lookup_id.filter(|&lookup_id| {
+            self.token_metadata
+                .get(&lookup_id)
+                .map(|(count, _)| count < u16::MAX)
+                .unwrap_or(false)
+        });
+        let (md_size, roy_len) = match lookup_id {
+            Some(_) => (0, 0),
+            None => {
+                let (_, md_size) = TokenMetadata::from_with_size(edition.metadata.clone(), 1);
+                let roy_len = edition
+                    .royalty
+                    .as_ref()
+                    .map(|royalty| royalty.split_between.len() as u32)
+                    .unwrap_or(0);
+                (md_size, roy_len)
+            },
+        };
+        let price: Balance = edition.price.into();
+        let storage_cost = self.storage_cost_to_mint(1, 1, md_size, roy_len, 1);
+        let required = price + storage_cost;
+        let attached_deposit = env::attached_deposit();
+        assert!(
+            attached_deposit >= required,
+            "attached: {}; need: {}",
+            attached_deposit,
+            required
+        );
+
+        let lookup_id = match lookup_id {
+            Some(lookup_id) => {
+                self.add_edition_copy(lookup_id);
+                lookup_id
+            },
+            None => {
+                let lookup_id = self.tokens_minted;
+                let (metadata, _) = TokenMetadata::from_with_size(edition.metadata.clone(), 1);
+                self.token_metadata.insert(&lookup_id, &(1, metadata));
+                if let Some(ref royalty) = edition.royalty {
+                    self.token_royalty.insert(&lookup_id, &(1, royalty.clone()));
+                }
+                lookup_id
+            },
+        };
+        let royalty_id = edition.royalty.as_ref().map(|_| lookup_id);
+        edition.minted += 1;
+        edition.lookup_id = Some(lookup_id);
+        self.open_editions.insert(&edition.edition_id, &edition);
+        self.mint_copy(
+            owner_id.clone(),
+            minter_id.clone(),
+            lookup_id,
+            royalty_id,
+            Some(edition.minted),
+        );
+
+        if price > 0 {
+            Promise::new(minter_id).transfer(price);
+        }
+        log_storage_deposit(
+            StorageDepositAction::Taken,
+            &owner_id,
+            storage_cost,
+            "nft_mint_edition",
+        );
+        let refund = attached_deposit - required;
+        if refund > 0 {
+            log_storage_deposit(
+                StorageDepositAction::Refunded,
+                &owner_id,
+                refund,
+                "nft_mint_edition",
+            );
+            Promise::new(owner_id).transfer(refund);
+        }
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get an open edition, including the number of copies minted so far.
+    pub fn get_open_edition(
+        &self,
+        edition_id: U64,
+    ) -> Option<OpenEdition> {
+        self.open_editions.get(&edition_id.into())
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+
+    /// Count another copy of the edition whose metadata and royalty are
+    /// stored under `lookup_id`.
+    fn add_edition_copy(
+        &mut self,
+        lookup_id: u64,
+    ) {
+        let (count, mut metadata) = self.token_metadata.get(&lookup_id).unwrap();
+        // burning copies lowers the count, but not the copies minted
+        metadata.copies = Some(metadata.copies.unwrap_or(1).saturating_add(1));
+        self.token_metadata
+            .insert(&lookup_id, &(count + 1, metadata));
+        if let Some((count, royalty)) = self.token_royalty.get(&lookup_id) {
+            self.token_royalty.insert(&lookup_id, &(count + 1, royalty));
+        }
+    }
+
+    fn get_open_edition_internal(
+        &self,
+        edition_id: u64,
+    ) -> OpenEdition {
+        self.open_editions
+            .get(&edition_id)
+            .expect("no such edition")
+    }
+}
@@ -51,8 +51,7 @@ impl MintbaseStore {
         self.tokens_per_owner
             .get(&account_id)
             .expect("no tokens")
-            .iter()
-            .skip(
+            .iter_from(
                 from_index
                     .unwrap_or_else(|| "0".to_string())
                     .parse()
@@ -66,4 +65,52 @@ impl MintbaseStore {
 
 // ------------------ non-standardized enumeration methods ------------------ //
 #[near_bindgen]
-impl MintbaseStore {}
+impl MintbaseStore {
+    // -------------------------- view methods -----------------------------
+
+    /// Check whether `account_id` owns any token minted with one of the
+    /// metadata `lookup_ids`, e.g. for token-gating.
+    ///
+    /// A `lookup_id` is the id of the first token minted with it, thus owned
+    /// tokens with lower ids are skipped without being loaded.
+    pub fn owns_any(
+        &self,
+        account_id: AccountId,
+        lookup_ids: Vec<u64>,
+    ) -> bool {
+        let min_lookup_id = match lookup_ids.iter().min() {
+            Some(&min_lookup_id) => min_lookup_id,
+            None => return false,
+        };
+        self.tokens_per_owner
+            .get(&account_id)
+            .map(|owned| {
+                owned
+                    .iter()
+                    .filter(|&token_id| token_id >= min_lookup_id)
+                    .any(|token_id| {
+                        lookup_ids.contains(&self.nft_token_internal(token_id).metadata_id)
+                    })
+            })
+            .unwrap_or(false)
+    }
+
+    /// Count the tokens minted with metadata `lookup_id` that `account_id`
+    /// owns.
+    pub fn owned_count_for_metadata(
+        &self,
+        account_id: AccountId,
+        lookup_id: u64,
+    ) -> u64 {
+        self.tokens_per_owner
+            .get(&account_id)
+            .map(|owned| {
+                owned
+                    .iter()
+                    .filter(|&token_id| token_id >= lookup_id)
+                    .filter(|&token_id| self.nft_token_internal(token_id).metadata_id == lookup_id)
+                    .count() as u64
+            })
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,140 @@
+use mintbase_deps::common::{
+    Gift,
+    GiftClaim,
+};
+use mintbase_deps::constants::MAX_GIFT_MEMO_LEN;
+use mintbase_deps::logging::log_nft_transfer;
+use mintbase_deps::near_sdk::json_types::{
+    Base64VecU8,
+    U64,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    assert_one_yocto,
+    env,
+    near_bindgen,
+    AccountId,
+    PublicKey,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Gift the token to whoever holds the private key of `claim_pk`, e.g.
+    /// people that don't have a NEAR account yet. The token is transferred
+    /// to the `Store` itself and held in escrow until it is claimed via
+    /// `claim_gift`, or reclaimed via `cancel_gift`. The transfer fee is due
+    /// when gifting, and not refunded if the gift is cancelled. The storage
+    /// of the gift is covered by the `Store`, so `memo` may be at most 256
+    /// bytes long.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn gift_token(
+        &mut self,
+        token_id: U64,
+        claim_pk: PublicKey,
+        memo: Option<String>,
+    ) {
+        self.collect_transfer_fee(1, 1);
+        // curve type 0 is ed25519
+        assert_eq!(claim_pk.as_bytes()[0], 0, "claim_pk must be ed25519");
+        assert!(
+            memo.as_ref().map_or(0, String::len) <= MAX_GIFT_MEMO_LEN,
+            "memo longer than {} bytes",
+            MAX_GIFT_MEMO_LEN
+        );
+        let mut token = self.nft_token_internal(token_id.0);
+        token.assert_movable_by_owner();
+        assert!(token.is_pred_owner());
+
+        let sender_id = env::predecessor_account_id();
+        let store_id = env::current_account_id();
+        self.transfer_internal(&mut token, store_id.clone(), true, "gift_token");
+        log_nft_transfer(&store_id, token_id.0, &memo, sender_id.to_string());
+        self.gifts.insert(
+            &token_id.0,
+            &Gift {
+                sender_id,
+                claim_pk,
+                memo,
+            },
+        );
+    }
+
+    /// Claim a gifted token for `receiver_id`. `signature` must be the
+    /// ed25519 signature of the `GiftClaim` message by the gift's claim
+    /// key. Anyone may submit the claim, e.g. a relayer on behalf of an
+    /// account that cannot pay for gas yet. Expired tokens may not be
+    /// claimed, but the sender may still cancel the gift.
+    pub fn claim_gift(
+        &mut self,
+        token_id: U64,
+        receiver_id: AccountId,
+        signature: Base64VecU8,
+    ) {
+        let gift = self.gifts.get(&token_id.0).expect("no gift");
+        let claim = GiftClaim {
+            store_id: env::current_account_id(),
+            token_id,
+            receiver_id: receiver_id.clone(),
+        };
+        assert!(
+            claim.verify(&gift.claim_pk, &signature.0),
+            "invalid signature"
+        );
+        self.assert_transfer_policy(&receiver_id);
+        let mut token = self.nft_token_internal(token_id.0);
+        assert!(!token.is_expired(), "token expired");
+        self.gifts.remove(&token_id.0);
+
+        self.transfer_internal(&mut token, receiver_id.clone(), true, "claim_gift");
+        log_nft_transfer(
+            &receiver_id,
+            token_id.0,
+            &gift.memo,
+            env::current_account_id().to_string(),
+        );
+    }
+
+    /// Return a gifted token that has not been claimed yet to its sender,
+    /// e.g. if the claim link was lost.
+    ///
+    /// Only the sender of the gift may call this function.
+    #[payable]
+    pub fn cancel_gift(
+        &mut self,
+        token_id: U64,
+    ) {
+        assert_one_yocto();
+        let gift = self.gifts.get(&token_id.0).expect("no gift");
+        assert_eq!(
+            gift.sender_id,
+            env::predecessor_account_id(),
+            "not the sender"
+        );
+        self.gifts.remove(&token_id.0);
+
+        let mut token = self.nft_token_internal(token_id.0);
+        self.transfer_internal(&mut token, gift.sender_id.clone(), true, "cancel_gift");
+        log_nft_transfer(
+            &gift.sender_id,
+            token_id.0,
+            &gift.memo,
+            env::current_account_id().to_string(),
+        );
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the gift that holds the token in escrow, if any.
+    pub fn get_gift(
+        &self,
+        token_id: U64,
+    ) -> Option<Gift> {
+        self.gifts.get(&token_id.0)
+    }
+}
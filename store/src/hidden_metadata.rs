@@ -0,0 +1,141 @@
+use mintbase_deps::common::{
+    BatchRoyaltyArgs,
+    MetadataCommitment,
+    RoyaltyArgs,
+    SplitBetweenUnparsed,
+    TokenMetadata,
+};
+use mintbase_deps::logging::log_nft_metadata_update;
+use mintbase_deps::near_sdk::json_types::{
+    Base64VecU8,
+    U64,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    assert_one_yocto,
+    env,
+    near_bindgen,
+    AccountId,
+    Balance,
+};
+use mintbase_deps::serde_json;
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Mint `num_to_mint` copies of a token with `placeholder` metadata, for
+    /// drops whose content is revealed after minting. The minter commits to
+    /// the real metadata with `metadata_hash`, as returned by
+    /// `hash_metadata`, and may later reveal it via `reveal_metadata`.
+    ///
+    /// The same restrictions as for `nft_batch_mint` apply, except that
+    /// minting on behalf of another minter is not supported.
+    #[payable]
+    pub fn nft_batch_mint_hidden(
+        &mut self,
+        owner_id: AccountId,
+        placeholder: TokenMetadata,
+        num_to_mint: u64,
+        metadata_hash: Base64VecU8,
+        royalty_args: Option<RoyaltyArgs>,
+        split_owners: Option<SplitBetweenUnparsed>,
+    ) {
+        assert_eq!(metadata_hash.0.len(), 32, "metadata hash must be sha256");
+        // the first token id is the `lookup_id` of the minted tokens
+        let (first_token_id, last_token_id) = self.nft_batch_mint(
+            owner_id,
+            placeholder,
+            num_to_mint,
+            royalty_args.map(BatchRoyaltyArgs::Shared),
+            split_owners,
+            None,
+            None,
+            None,
+        );
+        self.metadata_commitments.insert(
+            &first_token_id.0,
+            &MetadataCommitment {
+                minter: env::predecessor_account_id(),
+                metadata_hash,
+                first_token_id: first_token_id.0,
+                last_token_id: last_token_id.0,
+            },
+        );
+    }
+
+    /// Replace the placeholder metadata of the tokens minted via
+    /// `nft_batch_mint_hidden` with `real_metadata`, which must match the
+    /// committed hash. Emits an `nft_metadata_update` event for the tokens.
+    /// Additional storage is covered by the unused balance of the `Store`,
+    /// as for minting.
+    ///
+    /// Only the minter of the tokens may call this function.
+    #[payable]
+    pub fn reveal_metadata(
+        &mut self,
+        lookup_id: U64,
+        real_metadata: TokenMetadata,
+    ) {
+        assert_one_yocto();
+        let lookup_id: u64 = lookup_id.into();
+        let commitment = self
+            .metadata_commitments
+            .get(&lookup_id)
+            .expect("no hidden metadata");
+        assert_eq!(
+            commitment.minter,
+            env::predecessor_account_id(),
+            "caller not the minter"
+        );
+        assert!(
+            self.hash_metadata(real_metadata.clone()) == commitment.metadata_hash,
+            "metadata does not match commitment"
+        );
+
+        let covered_storage = env::account_balance()
+            - (env::storage_usage() as u128 * self.storage_costs().storage_price_per_byte);
+        let (count, placeholder) = self.token_metadata.get(&lookup_id).unwrap();
+        let placeholder_size = serde_json::to_vec(&placeholder).unwrap().len() as u64;
+        let copies = placeholder.copies.unwrap_or(count) as u64;
+        let (metadata, md_size) = TokenMetadata::from_with_size(real_metadata, copies);
+        let additional_bytes = md_size.saturating_sub(placeholder_size);
+        let expected_storage_consumption: Balance =
+            additional_bytes as u128 * self.storage_costs().storage_price_per_byte;
+        assert!(
+            covered_storage >= expected_storage_consumption,
+            "covered: {}; need: {}",
+            covered_storage,
+            expected_storage_consumption
+        );
+
+        self.token_metadata.insert(&lookup_id, &(count, metadata));
+        self.metadata_commitments.remove(&lookup_id);
+        log_nft_metadata_update(commitment.first_token_id, commitment.last_token_id);
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the commitment to the hidden metadata of the tokens with
+    /// `lookup_id`, if their metadata has not been revealed yet.
+    pub fn get_metadata_commitment(
+        &self,
+        lookup_id: U64,
+    ) -> Option<MetadataCommitment> {
+        self.metadata_commitments.get(&lookup_id.into())
+    }
+
+    /// The sha256 hash of `metadata` to commit to in `nft_batch_mint_hidden`,
+    /// computed over its JSON serialization.
+    pub fn hash_metadata(
+        &self,
+        metadata: TokenMetadata,
+    ) -> Base64VecU8 {
+        env::sha256(&serde_json::to_vec(&metadata).unwrap()).into()
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+}
@@ -0,0 +1,59 @@
+use mintbase_deps::common::{
+    EventJournal,
+    JournaledEvent,
+};
+use mintbase_deps::constants::MAX_EVENT_JOURNAL_CAPACITY;
+use mintbase_deps::near_sdk::{
+    self,
+    near_bindgen,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Keep the most recent `capacity` events emitted by this `Store`, such
+    /// that they can be queried via `events_since`. A `capacity` of zero
+    /// disables the journal. The journal is disabled by default.
+    ///
+    /// Only the store owner may call this function.
+    ///
+    /// This method increases storage costs of the contract by about
+    /// `capacity` times the size of an event.
+    #[payable]
+    pub fn set_event_journal_capacity(
+        &mut self,
+        capacity: u64,
+    ) {
+        self.assert_store_owner();
+        assert!(
+            capacity <= MAX_EVENT_JOURNAL_CAPACITY,
+            "capacity must be <= {}",
+            MAX_EVENT_JOURNAL_CAPACITY
+        );
+        EventJournal::configure(capacity);
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the sequence numbers and capacity of the event journal.
+    pub fn get_event_journal(&self) -> Option<EventJournal> {
+        EventJournal::load()
+    }
+
+    /// Get up to `limit` of the journaled events, starting at sequence
+    /// number `seq`. Events that have been pruned from the journal are
+    /// skipped, so a gap between `seq` and the first returned event means
+    /// that events have been missed.
+    pub fn events_since(
+        &self,
+        seq: u64,
+        limit: u64,
+    ) -> Vec<JournaledEvent> {
+        EventJournal::load()
+            .map(|journal| journal.events_since(seq, limit))
+            .unwrap_or_default()
+    }
+}
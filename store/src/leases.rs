@@ -0,0 +1,86 @@
+use mintbase_deps::common::NearTime;
+use mintbase_deps::logging::{
+    log_nft_lease,
+    log_nft_return_lease,
+};
+use mintbase_deps::near_sdk::json_types::U64;
+use mintbase_deps::near_sdk::{
+    self,
+    assert_one_yocto,
+    env,
+    near_bindgen,
+    AccountId,
+};
+use mintbase_deps::token::Lease;
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Lease the token to `borrower_id` until `expires_at`, e.g. a game item
+    /// or membership. The borrower is reported by `nft_token_user`, but may
+    /// not transfer or burn the token. Until the lease expires, the token
+    /// is treated as loaned, thus the owner may neither transfer, approve,
+    /// nor burn it.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn nft_lease(
+        &mut self,
+        token_id: U64,
+        borrower_id: AccountId,
+        expires_at: NearTime,
+    ) {
+        assert_one_yocto();
+        let mut token = self.nft_token_internal(token_id.into());
+        assert!(!token.is_loaned());
+        token.assert_not_frozen();
+        token.assert_not_offered();
+        token.assert_not_expired();
+        assert!(token.is_pred_owner());
+        assert_ne!(borrower_id.to_string(), token.owner_id.to_string()); // can't lease to self
+        let lease = Lease::new(borrower_id, expires_at);
+        log_nft_lease(token.id, &token.owner_id.to_string(), &lease);
+        token.lease = Some(lease);
+        self.tokens.insert(&token.id, &token);
+    }
+
+    /// End the lease before it expires, returning the use of the token to
+    /// its owner.
+    ///
+    /// Only the borrower may call this function.
+    #[payable]
+    pub fn nft_return_lease(
+        &mut self,
+        token_id: U64,
+    ) {
+        assert_one_yocto();
+        let mut token = self.nft_token_internal(token_id.into());
+        assert!(token.is_leased(), "token not leased");
+        let lease = token.lease.take().unwrap();
+        assert_eq!(
+            lease.borrower_id,
+            env::predecessor_account_id(),
+            "not the borrower"
+        );
+        log_nft_return_lease(token.id, &token.owner_id.to_string(), &lease);
+        self.tokens.insert(&token.id, &token);
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the account that may currently use the token, i.e. the borrower
+    /// of an unexpired lease. Returns `None` if the token is not leased.
+    pub fn nft_token_user(
+        &self,
+        token_id: U64,
+    ) -> Option<AccountId> {
+        let token = self.nft_token_internal(token_id.into());
+        if !token.is_leased() {
+            return None;
+        }
+        token.lease.map(|lease| lease.borrower_id)
+    }
+}
@@ -0,0 +1,65 @@
+use mintbase_deps::common::NFTContractMetadata;
+use mintbase_deps::near_sdk::borsh::{
+    self,
+    BorshDeserialize,
+    BorshSerialize,
+};
+use mintbase_deps::near_sdk::collections::{
+    LookupMap,
+    UnorderedSet,
+};
+use mintbase_deps::near_sdk::{
+    env,
+    AccountId,
+};
+
+use crate::*;
+
+/// The state layout of stores deployed before tokens were sharded into
+/// pages and the store settings were introduced. Tokens, metadata and
+/// owned-token sets of these stores remain in their previous layout, and
+/// are upgraded lazily.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldMintbaseStore {
+    pub minters: UnorderedSet<AccountId>,
+    pub metadata: NFTContractMetadata,
+    pub token_metadata: LookupMap<u64, (u16, LegacyTokenMetadata)>,
+    pub token_royalty: LookupMap<u64, (u16, Royalty)>,
+    pub tokens: LookupMap<u64, LegacyToken>,
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<u64>>,
+    pub composeables: LookupMap<String, UnorderedSet<String>>,
+    pub tokens_minted: u64,
+    pub tokens_burned: u64,
+    pub num_approved: u64,
+    pub owner_id: AccountId,
+    pub storage_costs: OldStorageCosts,
+    pub allow_moves: bool,
+}
+
+/// The layout of `StorageCosts` stored by `OldMintbaseStore`.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldStorageCosts {
+    pub storage_price_per_byte: u128,
+    pub common: u128,
+    pub token: u128,
+}
+
+impl MintbaseStore {
+    /// Internal
+    /// Migrate the state of `old`, filling in the defaults of all fields
+    /// that have been added since.
+    pub(crate) fn from_old(
+        old: OldMintbaseStore,
+        metadata: NFTContractMetadata,
+    ) -> Self {
+        let mut store = Self::initial_state(metadata, old.owner_id, old.minters, old.tokens_minted);
+        store.tokens_minted = old.tokens_minted;
+        store.tokens_burned = old.tokens_burned;
+        store.num_approved = old.num_approved;
+        store.allow_moves = old.allow_moves;
+        // the price was fixed at the protocol price unless overridden
+        store.storage_price_override = Some(old.storage_costs.storage_price_per_byte)
+            .filter(|&price| price != env::storage_byte_cost());
+        store
+    }
+}
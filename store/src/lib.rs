@@ -1,13 +1,49 @@
+use mintbase_deps::common::time::now;
 use mintbase_deps::common::{
+    ActivityWindow,
+    Attestation,
+    ClaimableBalance,
+    FeeHookConfig,
+    Gift,
+    LegacyTokenMetadata,
+    MetadataCommitment,
+    MethodRequirement,
+    MethodRequirements,
+    MigrationCertificate,
+    MintBan,
+    MintBatch,
+    MintPool,
+    MintSession,
+    MintWindow,
+    MinterApplication,
     NFTContractMetadata,
+    NearTime,
+    OpenEdition,
+    OwnedTokens,
+    Payout,
+    RateLimits,
     Royalty,
+    RoyaltyArgs,
+    StoreCloneArgs,
+    StoreCloneConfig,
+    StoreCloneInclude,
+    StoreRole,
+    SupportedStandard,
     TokenMetadata,
     TokenMetadataCompliant,
+    TokenReservation,
+    TokensPerOwner,
+    TransferPolicy,
+    UpgradingMap,
+    VestingSchedule,
 };
 use mintbase_deps::constants::{
+    gas,
     storage_stake,
     StorageCosts,
-    YOCTO_PER_BYTE,
+    MAX_COMPOSE_DEPTH,
+    MINTER_APPLICATION_DEPOSIT,
+    ONE_YOCTO,
 };
 use mintbase_deps::near_sdk::borsh::{
     self,
@@ -16,6 +52,8 @@ use mintbase_deps::near_sdk::borsh::{
 };
 use mintbase_deps::near_sdk::collections::{
     LookupMap,
+    LookupSet,
+    UnorderedMap,
     UnorderedSet,
 };
 use mintbase_deps::near_sdk::json_types::{
@@ -29,29 +67,89 @@ use mintbase_deps::near_sdk::{
     ext_contract,
     near_bindgen,
     AccountId,
+    Balance,
+    Gas,
+    PublicKey,
     StorageUsage,
 };
 use mintbase_deps::token::{
+    LegacyToken,
     Owner,
     Token,
 };
 
+/// Implementing allowlist-gated minting for accounts that aren't minters.
+mod allowlist;
 /// Implementing approval management as [described in the Nomicon](https://nomicon.io/Standards/NonFungibleToken/ApprovalManagement).
 mod approvals;
+/// Implementing provenance attestations by registered third parties.
+mod attestations;
 /// Implementing any methods related to burning.
 mod burning;
+/// Implementing co-signed transfers of tokens.
+mod co_signing;
+/// Implementing composition of tokens into other tokens on this store.
+mod composing;
 /// Implementing core functionality of an NFT contract as [described in the Nomicon](https://nomicon.io/Standards/NonFungibleToken/Core).
 mod core;
+/// Implementing scheduled minting windows for drops.
+mod drops;
+/// Implementing open editions that anyone may mint copies of.
+mod editions;
 /// Implementing enumeration as [described in the Nomicon](https://nomicon.io/Standards/NonFungibleToken/Enumeration).
 mod enumeration;
+/// Implementing gifts of tokens that are claimed via a one-time key.
+mod gifts;
+/// Implementing blind box drops with hidden metadata that is revealed later.
+mod hidden_metadata;
+/// Implementing a bounded journal of recent events.
+mod journal;
+/// Implementing leases that grant the use of a token for a limited time.
+mod leases;
+/// Implementing the migration of state stored by previous versions of the
+/// store.
+mod legacy;
+/// Implementing store-initiated listings on registered markets.
+mod markets;
 /// Implementing metadata as [described in the Nomicon](https://nomicon.io/Standards/NonFungibleToken/Metadata).
 mod metadata;
+/// Implementing the migration of tokens between stores.
+mod migration;
+/// Implementing delegated minting on behalf of minters.
+mod mint_delegates;
+/// Implementing generative drops that assign pooled metadata pseudo-randomly.
+mod mint_pools;
+/// Implementing self-service applications to become a minter.
+mod minter_applications;
 /// Implementing any methods related to minting.
 mod minting;
+/// Implementing minting bans for abusive accounts.
+mod moderation;
+/// Implementing account-level operators that may transfer all tokens of an
+/// owner.
+mod operators;
 /// Implementing any methods related to store ownership.
 mod ownership;
 /// Implementing payouts as [described in the Nomicon](https://nomicon.io/Standards/NonFungibleToken/Payout).
 mod payout;
+/// Implementing per-account rate limits on store activity.
+mod rate_limiting;
+/// Implementing token id reservations for coordinated drops.
+mod reservations;
+/// Implementing a vault from which payout receivers claim their revenue.
+mod revenue;
+/// Implementing granular roles for minters and metadata editors.
+mod roles;
+/// Implementing royalty resolution via an external royalty registry.
+mod royalty_registry;
+/// Implementing resumable minting of more tokens than fit in a single call.
+mod sessions;
+/// Implementing two-step transfers that the receiver must accept.
+mod transfer_offers;
+/// Implementing compliance restrictions on the receivers of transfers.
+mod transfer_policy;
+/// Implementing lazy and relayed minting via payloads signed by minters.
+mod vouchers;
 
 // ----------------------------- smart contract ----------------------------- //
 
@@ -69,7 +167,9 @@ pub struct MintbaseStore {
     /// Token. The key is generated from `tokens_minted`. The map keeps count
     /// of how many copies of this token remain, so that the element may be
     /// dropped when the number reaches zero (ie, when tokens are burnt).
-    pub token_metadata: LookupMap<u64, (u16, TokenMetadata)>,
+    /// Metadata stored before media hints were introduced is upgraded when
+    /// read.
+    pub token_metadata: UpgradingMap<(u16, TokenMetadata), (u16, LegacyTokenMetadata)>,
     /// If a Minter mints more than one token at a time, all tokens will
     /// share the same `Royalty`. It's more storage-efficient to store that
     /// `Royalty` once, rather than to copy the data on each Token. The key
@@ -78,10 +178,15 @@ pub struct MintbaseStore {
     /// the number reaches zero (ie, when tokens are burnt).
     pub token_royalty: LookupMap<u64, (u16, Royalty)>,
     /// Tokens this Store has minted, excluding those that have been burned.
-    pub tokens: LookupMap<u64, Token>,
+    /// Tokens minted before the `Token` layout was extended are upgraded
+    /// when read, and stored in the current layout once written again.
+    pub tokens: UpgradingMap<Token, LegacyToken>,
     /// A mapping from each user to the tokens owned by that user. The owner
-    /// of the token is also stored on the token itself.
-    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<u64>>,
+    /// of the token is also stored on the token itself. The owned tokens are
+    /// sharded into pages, such that accounts holding many tokens remain
+    /// cheap to update and paginate. Tokens owned before the pages were
+    /// introduced are moved into them via `migrate_owned_tokens`.
+    pub tokens_per_owner: TokensPerOwner,
     /// A map from a token_id of a token on THIS contract to a set of tokens,
     /// that may be on ANY contract. If the owned-token is on this contract,
     /// the id will have format "<u64>". If the token is on another contract,
@@ -97,13 +202,141 @@ pub struct MintbaseStore {
     pub num_approved: u64,
     /// The owner of the Contract.
     pub owner_id: AccountId,
-    /// The Near-denominated price-per-byte of storage, and associated
-    /// contract storage costs. As of April 2021, the price per bytes is set
-    /// to 10^19, but this may change in the future, thus this
-    /// future-proofing field.
-    pub storage_costs: StorageCosts,
+    /// If set, storage costs are computed from this Near-denominated
+    /// price-per-byte, instead of the price-per-byte of the protocol.
+    pub storage_price_override: Option<Balance>,
     /// If false, disallow users to call `nft_move`.
     pub allow_moves: bool,
+    /// A record of each `nft_batch_mint` call, keyed by its deterministic
+    /// `mint_id`.
+    pub mint_batches: LookupMap<String, MintBatch>,
+    /// Accounts that are allowed to attach attestations to tokens on this
+    /// Store.
+    pub attestors: UnorderedSet<AccountId>,
+    /// Attestations attached to each token, bounded by
+    /// `MAX_ATTESTATIONS_PER_TOKEN`.
+    pub attestations: LookupMap<u64, Vec<Attestation>>,
+    /// Royalty and split receivers whose payouts vest according to a
+    /// schedule set by the store owner.
+    pub payout_vesting: LookupMap<AccountId, VestingSchedule>,
+    /// Certificates of tokens that have been burned on this `Store` to be
+    /// migrated to another `Store`.
+    pub migration_certificates: LookupMap<u64, MigrationCertificate>,
+    /// Origin keys of the tokens that have been migrated to this `Store`,
+    /// preventing a certificate from being claimed twice.
+    pub migrated_origins: LookupSet<String>,
+    /// If set, `nft_transfer_payout` queries this contract for additional
+    /// fee legs.
+    pub fee_hook: Option<FeeHookConfig>,
+    /// Split owners that confirmed burning a token which requires a burn
+    /// quorum.
+    pub burn_confirmations: LookupMap<u64, Vec<AccountId>>,
+    /// Per-account limits on minting and approving.
+    pub rate_limits: RateLimits,
+    /// Recent activities of each rate-limited account.
+    pub activity: LookupMap<AccountId, ActivityWindow>,
+    /// Public keys with which minters sign their `MintVoucher`s.
+    pub voucher_keys: LookupMap<AccountId, PublicKey>,
+    /// Redemption keys of all `MintVoucher`s that have been redeemed and
+    /// `MintRequest`s that have been used, preventing replays.
+    pub redeemed_vouchers: LookupSet<String>,
+    /// Number of tokens that each capped minter may still mint. Minters
+    /// without an entry may mint without limit.
+    pub minter_allowances: LookupMap<AccountId, u64>,
+    /// Fee per minted token that minters other than the store owner must
+    /// attach, and which is forwarded to the store owner.
+    pub minting_fee: Balance,
+    /// If set, minting is only allowed within this window.
+    pub mint_window: Option<MintWindow>,
+    /// Windows within which specific minters may mint, taking precedence
+    /// over `mint_window`.
+    pub minter_mint_windows: LookupMap<AccountId, MintWindow>,
+    /// Accounts that have been banned from minting, including lifted bans.
+    pub mint_bans: LookupMap<AccountId, MintBan>,
+    /// Accounts that may mint without being minters.
+    pub mint_allowlist: UnorderedSet<AccountId>,
+    /// Open editions registered by the store owner.
+    pub open_editions: LookupMap<u64, OpenEdition>,
+    /// The number of open editions registered on this `Store`. Used to
+    /// generate edition ids.
+    pub open_editions_registered: u64,
+    /// Unfinished mint sessions, keyed by their `mint_id`. The token ids of
+    /// a session are reserved, but its tokens don't exist until minted.
+    pub mint_sessions: LookupMap<String, MintSession>,
+    /// Markets on which tokens may be listed via `list_on_market`.
+    pub markets: UnorderedSet<AccountId>,
+    /// Roles granted via `grant_role`. Minters without a minting role have
+    /// been granted via `grant_minter`, and may mint with royalties.
+    pub roles: UnorderedMap<AccountId, Vec<StoreRole>>,
+    /// Pending applications to become a minter.
+    pub minter_applications: UnorderedMap<AccountId, MinterApplication>,
+    /// Expiries of time-limited minter grants. Expired minters remain in
+    /// `minters` until swept, but may no longer mint.
+    pub minter_expiries: UnorderedMap<AccountId, NearTime>,
+    /// Number of hours that newly granted minters need to wait before they
+    /// may mint. Zero if there is no cooling-off period.
+    pub minter_cooling_off_hours: u64,
+    /// End of the cooling-off period of recently granted minters.
+    pub minter_mintable_from: LookupMap<AccountId, NearTime>,
+    /// Accounts that may mint on behalf of a minter.
+    pub mint_delegates: LookupMap<AccountId, Vec<AccountId>>,
+    /// Royalty registry to which royalty resolution is deferred, if any.
+    pub royalty_registry: Option<AccountId>,
+    /// Royalties retrieved from a royalty registry, keyed by lookup id and
+    /// tagged with the registry that they were retrieved from.
+    pub registry_royalties: LookupMap<u64, (AccountId, Royalty)>,
+    /// Commitments to the hidden metadata of tokens minted via
+    /// `nft_batch_mint_hidden`, keyed by lookup id, until revealed.
+    pub metadata_commitments: LookupMap<u64, MetadataCommitment>,
+    /// Royalty applied to tokens minted without `royalty_args`, if any.
+    pub default_royalty: Option<RoyaltyArgs>,
+    /// Token id ranges reserved via `reserve_token_range`, keyed by their
+    /// first token id, until all of their tokens have been minted.
+    pub token_reservations: LookupMap<u64, TokenReservation>,
+    /// Mint pools registered by the store owner.
+    pub mint_pools: LookupMap<u64, MintPool>,
+    /// The metadata entries of each mint pool that haven't been minted yet,
+    /// keyed by pool id and index.
+    pub mint_pool_entries: LookupMap<(u64, u64), TokenMetadata>,
+    /// The number of mint pools registered on this `Store`. Used to generate
+    /// pool ids.
+    pub mint_pools_registered: u64,
+    /// Whether mint events embed the full metadata of the minted tokens,
+    /// such that indexers don't need to fetch the metadata reference.
+    pub mint_event_metadata: bool,
+    /// The `mint_id` of each `MintBatch`, keyed by its first token id.
+    pub mint_batch_ids: LookupMap<u64, String>,
+    /// Maximum royalty percentage in basis points with which tokens may be
+    /// minted, if capped.
+    pub max_royalty_bps: Option<u32>,
+    /// Shares of a payout below this amount are folded into the owner's
+    /// cut.
+    pub payout_dust_threshold: Balance,
+    /// Revenue deposited for payout receivers, withdrawn via
+    /// `claim_revenue`.
+    pub revenue_vault: LookupMap<AccountId, ClaimableBalance>,
+    /// Whether payouts honor the royalties of composed children.
+    pub aggregate_child_royalties: bool,
+    /// The operators of each owner, which may transfer all of the owner's
+    /// tokens without per-token approvals, with the storage deposit paid for
+    /// each of them.
+    pub operators: LookupMap<AccountId, Vec<(AccountId, Balance)>>,
+    /// Restrictions on the receivers of transfers, if any.
+    pub transfer_policy: Option<TransferPolicy>,
+    /// The accounts referred to by list transfer policies.
+    pub transfer_policy_list: LookupSet<AccountId>,
+    /// Number of minutes after which a locked token may be unlocked via
+    /// `force_unlock`, e.g. if the callback releasing it never resolved.
+    pub lock_timeout_minutes: u64,
+    /// Tokens held in escrow until claimed via `claim_gift`.
+    pub gifts: LookupMap<u64, Gift>,
+    /// Fee that accounts other than the store owner must attach to
+    /// `nft_transfer` and `nft_transfer_call`, and which is forwarded to the
+    /// store owner. Disabled if zero.
+    pub transfer_fee: Balance,
+    /// Maximum depth at which tokens may be composed via `nft_compose`,
+    /// at most `MAX_COMPOSE_DEPTH`.
+    pub max_compose_depth: u32,
 }
 
 impl Default for MintbaseStore {
@@ -116,34 +349,72 @@ impl Default for MintbaseStore {
 impl MintbaseStore {
     /// Create a new `Store`. `new` validates the `store_description`.
     ///
-    /// The `Store` is initialized with the owner as a `minter`.
+    /// The `Store` is initialized with the owner as a `minter`. If the `Store`
+    /// is a clone, the `clone_args` provide minters and configuration of the
+    /// original `Store`.
     #[init]
     pub fn new(
         metadata: NFTContractMetadata,
         owner_id: AccountId,
+        clone_args: Option<StoreCloneArgs>,
     ) -> Self {
         assert!(!env::state_exists(), "Already, initialized");
         let mut minters = UnorderedSet::new(b"a".to_vec());
         minters.insert(&owner_id);
+        let mut store = Self::initial_state(metadata, owner_id, minters, 0);
 
-        Self {
-            minters,
-            metadata,
-            token_metadata: LookupMap::new(b"b".to_vec()),
-            token_royalty: LookupMap::new(b"c".to_vec()),
-            tokens: LookupMap::new(b"d".to_vec()),
-            tokens_per_owner: LookupMap::new(b"e".to_vec()),
-            composeables: LookupMap::new(b"f".to_vec()),
-            tokens_minted: 0,
-            tokens_burned: 0,
-            num_approved: 0,
-            owner_id,
-            storage_costs: StorageCosts::new(YOCTO_PER_BYTE), // 10^19
-            allow_moves: true,
+        if let Some(clone_args) = clone_args {
+            for minter in clone_args.minters.unwrap_or_default() {
+                store.minters.insert(&minter);
+            }
+            if let Some(config) = clone_args.config {
+                store.allow_moves = config.allow_moves;
+                for attestor in config.attestors {
+                    store.attestors.insert(&attestor);
+                }
+                store.minting_fee = config.minting_fee.into();
+                store.mint_window = config.mint_window;
+                store.rate_limits = config.rate_limits;
+                store.default_royalty = config.default_royalty;
+                store.max_royalty_bps = config.max_royalty_bps;
+                store.transfer_fee = config.transfer_fee.into();
+                store.transfer_policy = config.transfer_policy;
+                store.payout_dust_threshold = config.payout_dust_threshold.into();
+                store.max_compose_depth = config.max_compose_depth;
+                store.minter_cooling_off_hours = config.minter_cooling_off_hours;
+                store.lock_timeout_minutes = config.lock_timeout_minutes;
+                store.mint_event_metadata = config.mint_event_metadata;
+                store.aggregate_child_royalties = config.aggregate_child_royalties;
+                store.fee_hook = config.fee_hook;
+                store.royalty_registry = config.royalty_registry;
+            }
         }
+
+        store
     }
 
     // -------------------------- change methods ---------------------------
+
+    /// Move up to `limit` of the tokens that `account_id` owned before
+    /// owned tokens were sharded into pages, returning whether all of them
+    /// have been moved. Until then, those tokens are listed after the
+    /// others, and not in ascending order.
+    ///
+    /// Anyone may call this function.
+    pub fn migrate_owned_tokens(
+        &mut self,
+        account_id: AccountId,
+        limit: u32,
+    ) -> bool {
+        let mut owned_set = match self.tokens_per_owner.get(&account_id) {
+            Some(owned_set) => owned_set,
+            None => return true,
+        };
+        let done = owned_set.migrate_legacy(limit as u64);
+        self.tokens_per_owner.insert(&account_id, &owned_set);
+        done
+    }
+
     // -------------------------- view methods -----------------------------
 
     /// Get the holder of the token. The token may be owned by:
@@ -165,22 +436,24 @@ impl MintbaseStore {
         }
     }
 
-    /// A non-indexed implementation. `from_index` and `limit are removed, so as
-    /// to support the:
-    ///
-    /// `tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>`
-    ///
-    /// type. They may be used in an implementation if the type is instead:
-    ///
-    /// `tokens_per_owner: LookupMap<AccountId, Vector<TokenId>>`
+    /// Get the ids of the tokens owned by `account_id` in ascending order.
+    /// Unless `limit` is given, all owned token ids are returned.
     pub fn nft_tokens_for_owner_set(
         &self,
         account_id: AccountId,
+        from_index: Option<String>,
+        limit: Option<usize>,
     ) -> Vec<u64> {
         self.tokens_per_owner
             .get(&account_id)
             .expect("no tokens")
-            .iter()
+            .iter_from(
+                from_index
+                    .unwrap_or_else(|| "0".to_string())
+                    .parse()
+                    .unwrap(),
+            )
+            .take(limit.unwrap_or(usize::MAX))
             .collect()
     }
 
@@ -195,21 +468,411 @@ impl MintbaseStore {
             .0
     }
 
+    /// Get deposit and gas requirements of the payable methods on this
+    /// `Store`, derived from its current storage costs.
+    ///
+    /// Minting consumes the storage deposited on the `Store` itself, whose
+    /// unused balance must cover:
+    ///
+    /// ```text
+    /// num_owners * (storage_common + storage_mint_batch)
+    ///     + metadata_bytes * storage_price_per_byte
+    ///     + num_royalties * storage_common
+    ///     + num_to_mint * (storage_token + num_splits * storage_common)
+    /// ```
+    pub fn get_method_requirements(&self) -> MethodRequirements {
+        let storage_costs = self.storage_costs();
+        let common = storage_costs.common;
+        let one_yocto = |method_name: &str, recommended_gas: Gas| {
+            MethodRequirement::new(method_name, ONE_YOCTO, 0, None, recommended_gas)
+        };
+        // the store owner only attaches one yoctoNEAR instead of the fee
+        let transfer_fee = |method_name: &str, deposit_unit: Option<&str>, recommended_gas: Gas| {
+            let (base_deposit, deposit_per_unit) = match (self.transfer_fee, deposit_unit) {
+                (0, _) => (ONE_YOCTO, 0),
+                (fee, None) => (fee, 0),
+                (fee, Some(_)) => (0, fee),
+            };
+            let deposit_unit = deposit_unit.filter(|_| deposit_per_unit > 0);
+            MethodRequirement::new(
+                method_name,
+                base_deposit,
+                deposit_per_unit,
+                deposit_unit,
+                recommended_gas,
+            )
+        };
+        let methods = vec![
+            // the deposit must additionally cover the minting fee
+            one_yocto("nft_batch_mint", gas::NFT_BATCH_MINT),
+            one_yocto("nft_batch_mint_distinct", gas::NFT_BATCH_MINT),
+            one_yocto("nft_batch_mint_multi", gas::NFT_BATCH_MINT),
+            one_yocto("nft_batch_mint_hidden", gas::NFT_BATCH_MINT),
+            one_yocto("start_mint_session", gas::NFT_BATCH_MINT),
+            one_yocto("continue_mint_session", gas::NFT_BATCH_MINT),
+            one_yocto("update_token_royalty", gas::NFT_BATCH_MINT),
+            one_yocto("reserve_token_range", gas::BASE_CALL),
+            one_yocto("nft_mint_reserved", gas::NFT_BATCH_MINT),
+            one_yocto("set_voucher_key", gas::BASE_CALL),
+            MethodRequirement::new("approve_mint_delegate", common + 1, 0, None, gas::BASE_CALL),
+            one_yocto("revoke_mint_delegate", gas::BASE_CALL),
+            MethodRequirement::new("approve_operator", common + 1, 0, None, gas::BASE_CALL),
+            one_yocto("revoke_operator", gas::BASE_CALL),
+            // the deposit must additionally cover the voucher price, the
+            // minting fee and the storage of the minted token
+            one_yocto("nft_redeem_voucher", gas::NFT_REDEEM_VOUCHER),
+            // the deposit must additionally cover the edition price and the
+            // storage of the minted token
+            one_yocto("nft_mint_edition", gas::NFT_MINT_EDITION),
+            // the deposit must additionally cover the pool price and the
+            // storage of the minted token
+            one_yocto("nft_mint_from_pool", gas::NFT_MINT_FROM_POOL),
+            // the deposit must cover the minting fee and the storage of the
+            // minted tokens
+            MethodRequirement::new("nft_mint_signed", 0, 0, None, gas::NFT_MINT_SIGNED),
+            // covers the optional receiver verification
+            transfer_fee("nft_transfer", None, gas::BASE_CALL + gas::RESOLVE_TRANSFER),
+            // a `gas_for_receiver` above the default must be added on top
+            transfer_fee(
+                "nft_transfer_call",
+                None,
+                gas::BASE_CALL + gas::NFT_ON_TRANSFER + gas::RESOLVE_TRANSFER_CALL,
+            ),
+            transfer_fee("nft_batch_transfer", Some("token_ids"), gas::BASE_CALL),
+            transfer_fee(
+                "nft_batch_transfer_checked",
+                Some("transfers"),
+                gas::BASE_CALL,
+            ),
+            transfer_fee("nft_transfer_all", Some("limit"), gas::BASE_CALL),
+            one_yocto("nft_compose", gas::BASE_CALL),
+            one_yocto("nft_uncompose", gas::BASE_CALL),
+            one_yocto("nft_approve_compose", gas::BASE_CALL),
+            one_yocto("nft_revoke_compose", gas::BASE_CALL),
+            // the fee is due for each token of the bundle
+            transfer_fee("nft_transfer_bundle", None, gas::BASE_CALL),
+            one_yocto(
+                "nft_cross_compose",
+                gas::BASE_CALL + gas::NFT_TOKEN + gas::ON_CROSS_COMPOSE,
+            ),
+            one_yocto(
+                "nft_cross_uncompose",
+                gas::BASE_CALL + gas::NFT_TOKEN + gas::ON_CROSS_COMPOSE,
+            ),
+            transfer_fee("gift_token", None, gas::BASE_CALL),
+            one_yocto("cancel_gift", gas::BASE_CALL),
+            transfer_fee(
+                "nft_batch_transfer_call",
+                Some("token_ids"),
+                gas::BASE_CALL + gas::NFT_ON_BATCH_TRANSFER + gas::RESOLVE_BATCH_TRANSFER,
+            ),
+            // covers the optional fee hook
+            one_yocto(
+                "nft_transfer_payout",
+                gas::NFT_TRANSFER_PAYOUT + gas::NFT_FEE_LEGS + gas::ON_FEE_LEGS,
+            ),
+            MethodRequirement::new(
+                "nft_approve",
+                common + 1,
+                0,
+                None,
+                gas::BASE_CALL + gas::NFT_ON_APPROVE,
+            ),
+            MethodRequirement::new(
+                "nft_batch_approve",
+                1,
+                common,
+                Some("token_ids"),
+                gas::BASE_CALL + gas::NFT_BATCH_APPROVE,
+            ),
+            MethodRequirement::new(
+                "list_on_market",
+                1,
+                common,
+                Some("token_ids"),
+                gas::BASE_CALL + gas::NFT_BATCH_APPROVE,
+            ),
+            // the deposit must additionally cover the minting fee
+            MethodRequirement::new(
+                "nft_batch_mint_and_list",
+                1,
+                common,
+                Some("num_to_mint"),
+                gas::NFT_BATCH_MINT_AND_LIST,
+            ),
+            one_yocto("nft_revoke", gas::BASE_CALL),
+            one_yocto("nft_revoke_all", gas::BASE_CALL),
+            MethodRequirement::new(
+                "set_split_owners",
+                0,
+                common,
+                Some("token_ids * split_between"),
+                gas::BASE_CALL,
+            ),
+            one_yocto("clear_split_owners", gas::BASE_CALL),
+            MethodRequirement::new(
+                "replace_split_owners",
+                1,
+                common,
+                Some("token_ids * split_between"),
+                gas::BASE_CALL,
+            ),
+            // the deposit must cover the deposited revenue and the storage
+            // of new vault entries
+            MethodRequirement::new("deposit_revenue", 0, 0, None, gas::BASE_CALL),
+            MethodRequirement::new("claim_revenue", 0, 0, None, gas::BASE_CALL),
+            MethodRequirement::new(
+                "set_payout_override",
+                1,
+                common,
+                Some("split_between"),
+                gas::BASE_CALL,
+            ),
+            one_yocto("nft_batch_burn", gas::BASE_CALL),
+            one_yocto("propose_burn", gas::BASE_CALL),
+            one_yocto("confirm_burn", gas::BASE_CALL),
+            one_yocto("freeze_token", gas::BASE_CALL),
+            one_yocto("unfreeze_token", gas::BASE_CALL),
+            one_yocto("freeze_my_token", gas::BASE_CALL),
+            one_yocto("unfreeze_my_token", gas::BASE_CALL),
+            one_yocto("set_co_signer", gas::BASE_CALL),
+            one_yocto("remove_co_signer", gas::BASE_CALL),
+            transfer_fee("nft_initiate_transfer", None, gas::BASE_CALL),
+            one_yocto("nft_approve_transfer", gas::BASE_CALL),
+            one_yocto("nft_cancel_transfer", gas::BASE_CALL),
+            transfer_fee("nft_offer_transfer", None, gas::BASE_CALL),
+            one_yocto("nft_lease", gas::BASE_CALL),
+            one_yocto("force_unlock", gas::BASE_CALL),
+            one_yocto("set_lock_timeout", gas::BASE_CALL),
+            one_yocto("set_transfer_fee", gas::BASE_CALL),
+            one_yocto("set_max_compose_depth", gas::BASE_CALL),
+            one_yocto("nft_return_lease", gas::BASE_CALL),
+            one_yocto("nft_accept_transfer", gas::BASE_CALL),
+            one_yocto("nft_cancel_transfer_offer", gas::BASE_CALL),
+            one_yocto("nft_migrate", gas::BASE_CALL),
+            one_yocto("reveal_metadata", gas::BASE_CALL),
+            one_yocto(
+                "refresh_registry_royalty",
+                gas::BASE_CALL + gas::GET_REGISTRY_ROYALTY + gas::ON_REGISTRY_ROYALTY,
+            ),
+            MethodRequirement::new("attest", storage_costs.attestation, 0, None, gas::BASE_CALL),
+            MethodRequirement::new(
+                "apply_for_minter",
+                MINTER_APPLICATION_DEPOSIT,
+                0,
+                None,
+                gas::BASE_CALL,
+            ),
+            // store owner methods
+            one_yocto("grant_minter", gas::BASE_CALL),
+            one_yocto("revoke_minter", gas::BASE_CALL),
+            one_yocto("batch_change_minters", gas::BASE_CALL),
+            one_yocto("grant_minter_until", gas::BASE_CALL),
+            one_yocto("rotate_minter", gas::BASE_CALL),
+            one_yocto("grant_role", gas::BASE_CALL),
+            one_yocto("revoke_role", gas::BASE_CALL),
+            one_yocto("approve_minter_application", gas::BASE_CALL),
+            one_yocto("reject_minter_application", gas::BASE_CALL),
+            one_yocto("add_to_mint_allowlist", gas::BASE_CALL),
+            one_yocto("remove_from_mint_allowlist", gas::BASE_CALL),
+            one_yocto("transfer_store_ownership", gas::BASE_CALL),
+            one_yocto("withdraw_excess_storage_deposits", gas::BASE_CALL),
+            one_yocto("set_storage_price_per_byte", gas::BASE_CALL),
+            one_yocto("set_allow_moves", gas::BASE_CALL),
+            one_yocto("set_base_uri", gas::BASE_CALL),
+            one_yocto("set_icon_base64", gas::BASE_CALL),
+            one_yocto("grant_attestor", gas::BASE_CALL),
+            one_yocto("revoke_attestor", gas::BASE_CALL),
+            one_yocto("set_payout_vesting", gas::BASE_CALL),
+            one_yocto("set_fee_hook", gas::BASE_CALL),
+            one_yocto("set_transfer_policy", gas::BASE_CALL),
+            one_yocto("update_transfer_policy_list", gas::BASE_CALL),
+            one_yocto("set_royalty_registry", gas::BASE_CALL),
+            one_yocto("set_rate_limits", gas::BASE_CALL),
+            one_yocto("set_event_journal_capacity", gas::BASE_CALL),
+            one_yocto("set_minting_fee", gas::BASE_CALL),
+            one_yocto("set_default_royalty", gas::BASE_CALL),
+            one_yocto("set_mint_event_metadata", gas::BASE_CALL),
+            one_yocto("set_max_royalty_bps", gas::BASE_CALL),
+            one_yocto("set_payout_dust_threshold", gas::BASE_CALL),
+            one_yocto("set_aggregate_child_royalties", gas::BASE_CALL),
+            one_yocto("set_minter_cooling_off", gas::BASE_CALL),
+            one_yocto("set_mint_window", gas::BASE_CALL),
+            one_yocto("ban_from_minting", gas::BASE_CALL),
+            one_yocto("unban_from_minting", gas::BASE_CALL),
+            one_yocto("register_open_edition", gas::BASE_CALL),
+            one_yocto("end_open_edition", gas::BASE_CALL),
+            one_yocto("register_mint_pool", gas::BASE_CALL),
+            one_yocto("add_to_mint_pool", gas::BASE_CALL),
+            one_yocto("end_mint_pool", gas::BASE_CALL),
+            one_yocto("add_market", gas::BASE_CALL),
+            one_yocto("remove_market", gas::BASE_CALL),
+        ];
+
+        MethodRequirements {
+            storage_price_per_byte: storage_costs.storage_price_per_byte.into(),
+            storage_common: common.into(),
+            storage_token: storage_costs.token.into(),
+            storage_mint_batch: storage_costs.mint_batch.into(),
+            methods,
+        }
+    }
+
+    /// The NEPs and their versions implemented by this `Store`.
+    pub fn supported_standards(&self) -> Vec<SupportedStandard> {
+        vec![
+            // core
+            SupportedStandard::new("nep171", "1.0.0"),
+            // metadata
+            SupportedStandard::new("nep177", "1.0.0"),
+            // approval management
+            SupportedStandard::new("nep178", "1.0.0"),
+            // enumeration
+            SupportedStandard::new("nep181", "1.0.0"),
+            // royalties and payouts
+            SupportedStandard::new("nep199", "2.0.0"),
+            // events
+            SupportedStandard::new("nep297", "1.0.0"),
+        ]
+    }
+
+    /// State of this `Store` that the factory copies when cloning it. The
+    /// owner is always included, so the factory can check that the clone is
+    /// requested by the owner of this `Store`.
+    pub fn get_clone_args(
+        &self,
+        include: StoreCloneInclude,
+    ) -> StoreCloneArgs {
+        let minters = include.minters.then(|| self.minters.to_vec());
+        let config = include.config.then(|| StoreCloneConfig {
+            allow_moves: self.allow_moves,
+            attestors: self.attestors.to_vec(),
+            minting_fee: self.minting_fee.into(),
+            mint_window: self.mint_window.clone(),
+            rate_limits: self.rate_limits.clone(),
+            default_royalty: self.default_royalty.clone(),
+            max_royalty_bps: self.max_royalty_bps,
+            transfer_fee: self.transfer_fee.into(),
+            transfer_policy: self
+                .transfer_policy
+                .clone()
+                .filter(|policy| matches!(policy, TransferPolicy::Hook { .. })),
+            payout_dust_threshold: self.payout_dust_threshold.into(),
+            max_compose_depth: self.max_compose_depth,
+            minter_cooling_off_hours: self.minter_cooling_off_hours,
+            lock_timeout_minutes: self.lock_timeout_minutes,
+            mint_event_metadata: self.mint_event_metadata,
+            aggregate_child_royalties: self.aggregate_child_royalties,
+            fee_hook: self.fee_hook.clone(),
+            royalty_registry: self.royalty_registry.clone(),
+        });
+
+        StoreCloneArgs {
+            owner_id: self.owner_id.clone(),
+            minters,
+            config,
+        }
+    }
+
     // -------------------------- private methods --------------------------
 
     /// Contract metadata and methods in the API may be updated. All other
-    /// elements of the state should be copied over. This method may only be
-    /// called by the holder of the Store public key, in this case the
-    /// Factory.
+    /// elements of the state should be copied over. Stores deployed before
+    /// the current state layout are migrated from `OldMintbaseStore`. This
+    /// method may only be called by the holder of the Store public key, in
+    /// this case the Factory.
     #[private]
     #[init(ignore_state)]
     pub fn migrate(metadata: NFTContractMetadata) -> Self {
-        let old = env::state_read().expect("ohno ohno state");
-        Self { metadata, ..old }
+        let state = env::storage_read(b"STATE").expect("ohno ohno state");
+        match Self::try_from_slice(&state) {
+            Ok(old) => Self { metadata, ..old },
+            Err(_) => {
+                let old =
+                    legacy::OldMintbaseStore::try_from_slice(&state).expect("unknown state layout");
+                Self::from_old(old, metadata)
+            },
+        }
     }
 
     // -------------------------- internal methods -------------------------
 
+    /// Internal
+    /// The state of a `Store` without tokens, with all settings at their
+    /// defaults. Tokens and metadata with ids below `legacy_below` may be
+    /// stored in their previous layout.
+    pub(crate) fn initial_state(
+        metadata: NFTContractMetadata,
+        owner_id: AccountId,
+        minters: UnorderedSet<AccountId>,
+        legacy_below: u64,
+    ) -> Self {
+        Self {
+            minters,
+            metadata,
+            token_metadata: UpgradingMap::new(b"R".to_vec(), b"b".to_vec(), legacy_below),
+            token_royalty: LookupMap::new(b"c".to_vec()),
+            tokens: UpgradingMap::new(b"Q".to_vec(), b"d".to_vec(), legacy_below),
+            tokens_per_owner: TokensPerOwner::new(b"O".to_vec(), b"e".to_vec(), b"P".to_vec()),
+            composeables: LookupMap::new(b"f".to_vec()),
+            tokens_minted: 0,
+            tokens_burned: 0,
+            num_approved: 0,
+            owner_id,
+            storage_price_override: None,
+            allow_moves: true,
+            mint_batches: LookupMap::new(b"g".to_vec()),
+            attestors: UnorderedSet::new(b"i".to_vec()),
+            attestations: LookupMap::new(b"k".to_vec()),
+            payout_vesting: LookupMap::new(b"l".to_vec()),
+            migration_certificates: LookupMap::new(b"m".to_vec()),
+            migrated_origins: LookupSet::new(b"n".to_vec()),
+            fee_hook: None,
+            burn_confirmations: LookupMap::new(b"o".to_vec()),
+            rate_limits: RateLimits::default(),
+            activity: LookupMap::new(b"p".to_vec()),
+            voucher_keys: LookupMap::new(b"q".to_vec()),
+            redeemed_vouchers: LookupSet::new(b"r".to_vec()),
+            minter_allowances: LookupMap::new(b"s".to_vec()),
+            minting_fee: 0,
+            mint_window: None,
+            minter_mint_windows: LookupMap::new(b"t".to_vec()),
+            mint_bans: LookupMap::new(b"u".to_vec()),
+            mint_allowlist: UnorderedSet::new(b"v".to_vec()),
+            open_editions: LookupMap::new(b"w".to_vec()),
+            open_editions_registered: 0,
+            mint_sessions: LookupMap::new(b"x".to_vec()),
+            markets: UnorderedSet::new(b"y".to_vec()),
+            roles: UnorderedMap::new(b"z".to_vec()),
+            minter_applications: UnorderedMap::new(b"A".to_vec()),
+            minter_expiries: UnorderedMap::new(b"B".to_vec()),
+            minter_cooling_off_hours: 0,
+            minter_mintable_from: LookupMap::new(b"C".to_vec()),
+            mint_delegates: LookupMap::new(b"D".to_vec()),
+            royalty_registry: None,
+            registry_royalties: LookupMap::new(b"E".to_vec()),
+            metadata_commitments: LookupMap::new(b"F".to_vec()),
+            default_royalty: None,
+            token_reservations: LookupMap::new(b"G".to_vec()),
+            mint_pools: LookupMap::new(b"H".to_vec()),
+            mint_pool_entries: LookupMap::new(b"I".to_vec()),
+            mint_pools_registered: 0,
+            mint_event_metadata: false,
+            mint_batch_ids: LookupMap::new(b"J".to_vec()),
+            max_royalty_bps: None,
+            payout_dust_threshold: 0,
+            revenue_vault: LookupMap::new(b"K".to_vec()),
+            aggregate_child_royalties: false,
+            operators: LookupMap::new(b"L".to_vec()),
+            transfer_policy: None,
+            transfer_policy_list: LookupSet::new(b"M".to_vec()),
+            lock_timeout_minutes: 60,
+            gifts: LookupMap::new(b"N".to_vec()),
+            transfer_fee: 0,
+            max_compose_depth: MAX_COMPOSE_DEPTH,
+        }
+    }
+
     /// If allow_moves is false, disallow token owners from calling
     /// `nft_move` on this contract, AND on other contracts targetting this
     /// contract. `nft_move` allows the user to burn a token they own on one
@@ -255,57 +918,51 @@ impl MintbaseStore {
         }
     }
 
-    // TODO: unused, deprecated?
-    // /// Internal
-    // /// update the set of tokens composed underneath parent. If insert is
-    // /// true, insert token_id; if false, try to remove it.
-    // fn update_composed_sets(
-    //     &mut self,
-    //     child: String,
-    //     parent: String,
-    //     insert: bool,
-    // ) {
-    //     let mut set = self.get_or_new_composed(parent.to_string());
-    //     if insert {
-    //         set.insert(&child);
-    //     } else {
-    //         set.remove(&child);
-    //     }
-    //     if set.is_empty() {
-    //         self.composeables.remove(&parent);
-    //     } else {
-    //         self.composeables.insert(&parent, &set);
-    //     }
-    // }
-
-    // TODO: unused, deprecated?
-    // /// Internal
-    // /// update the set of tokens composed underneath parent. If insert is
-    // /// true, insert token_id; if false, try to remove it.
-    // pub(crate) fn get_or_new_composed(
-    //     &mut self,
-    //     parent: String,
-    // ) -> UnorderedSet<String> {
-    //     self.composeables.get(&parent).unwrap_or_else(|| {
-    //         let mut prefix: Vec<u8> = vec![b'h'];
-    //         prefix.extend_from_slice(parent.to_string().as_bytes());
-    //         UnorderedSet::new(prefix)
-    //     })
-    // }
+    /// Internal
+    /// update the set of tokens composed underneath parent. If insert is
+    /// true, insert token_id; if false, try to remove it.
+    pub(crate) fn update_composed_sets(
+        &mut self,
+        child: String,
+        parent: String,
+        insert: bool,
+    ) {
+        let mut set = self.get_or_new_composed(parent.to_string());
+        if insert {
+            set.insert(&child);
+        } else {
+            set.remove(&child);
+        }
+        if set.is_empty() {
+            self.composeables.remove(&parent);
+        } else {
+            self.composeables.insert(&parent, &set);
+        }
+    }
+
+    /// Internal
+    /// get the set of tokens composed underneath parent, or a new empty set
+    /// if there are none.
+    pub(crate) fn get_or_new_composed(
+        &mut self,
+        parent: String,
+    ) -> UnorderedSet<String> {
+        self.composeables.get(&parent).unwrap_or_else(|| {
+            let mut prefix: Vec<u8> = vec![b'h'];
+            prefix.extend_from_slice(parent.to_string().as_bytes());
+            UnorderedSet::new(prefix)
+        })
+    }
 
     /// If an account_id has never owned tokens on this store, we must
-    /// construct an `UnorderedSet` for them. If they have owned tokens on
+    /// construct an `OwnedTokens` for them. If they have owned tokens on
     /// this store, get that set.
     /// Internal
     pub(crate) fn get_or_make_new_owner_set(
         &self,
         account_id: &AccountId,
-    ) -> UnorderedSet<u64> {
-        self.tokens_per_owner.get(account_id).unwrap_or_else(|| {
-            let mut prefix: Vec<u8> = vec![b'j'];
-            prefix.extend_from_slice(account_id.as_bytes());
-            UnorderedSet::new(prefix)
-        })
+    ) -> OwnedTokens {
+        self.tokens_per_owner.get_or_new(account_id)
     }
 
     /// Internal
@@ -315,18 +972,24 @@ impl MintbaseStore {
     ) {
         if let Owner::Account(ref s) = token.owner_id {
             token.owner_id = Owner::Lock(s.clone());
+            token.locked_at = Some(now());
             self.tokens.insert(&token.id, token);
         }
     }
 
-    /// Internal
+    /// Internal. Returns false if the token was not locked, e.g. because it
+    /// was unlocked via `force_unlock` before the callback resolved.
     fn unlock_token(
         &mut self,
         token: &mut Token,
-    ) {
+    ) -> bool {
         if let Owner::Lock(ref s) = token.owner_id {
             token.owner_id = Owner::Account(s.clone());
+            token.locked_at = None;
             self.tokens.insert(&token.id, token);
+            true
+        } else {
+            false
         }
     }
 }
@@ -369,5 +1032,128 @@ pub trait NonFungibleResolveTransfer {
         receiver_id: AccountId,
         token_id: String,
         approved_account_ids: Option<Vec<String>>,
+        memo: Option<String>,
+    );
+
+    /// Finalize an `nft_batch_transfer_call`. Each token is transferred to
+    /// `receiver_id` only if `nft_on_batch_transfer` returned `false` for
+    /// it. If the receiver failed or did not return one boolean per token,
+    /// all tokens remain with `owner_id`.
+    ///
+    /// Returns for each token whether it was transferred to `receiver_id`.
+    #[private]
+    fn nft_resolve_batch_transfer(
+        &mut self,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        token_ids: Vec<U64>,
+        memo: Option<String>,
+    ) -> Vec<bool>;
+
+    /// Finalize an `nft_transfer` that requested verification of
+    /// `receiver_id`. The preceding promise forwards the attached yoctoNEAR
+    /// to `receiver_id`, which fails if that account does not exist.
+    ///
+    /// Requirements:
+    /// * Contract MUST forbid calls to this function by any account except self
+    /// * If the preceding promise failed, the token MUST remain with `owner_id`
+    ///
+    /// Returns true if token was successfully transferred to `receiver_id`.
+    #[private]
+    fn nft_resolve_verified_transfer(
+        &mut self,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: U64,
+        memo: Option<String>,
+    ) -> bool;
+
+    /// Finalize an `nft_transfer` under a transfer policy hook. The token
+    /// is transferred to `receiver_id` only if the hook returned `true`,
+    /// otherwise it remains with `owner_id`.
+    ///
+    /// Returns true if token was successfully transferred to `receiver_id`.
+    #[private]
+    fn on_check_transfer(
+        &mut self,
+        hook_id: AccountId,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: U64,
+        memo: Option<String>,
+    ) -> bool;
+
+    /// Finalize an `nft_cross_compose`. The child is composed into the parent
+    /// only if the parent exists and is owned by `owner_id`, otherwise it is
+    /// unlocked for `owner_id` again.
+    ///
+    /// Returns true if the child was composed.
+    #[private]
+    fn on_cross_compose(
+        &mut self,
+        owner_id: AccountId,
+        child_id: U64,
+        parent_id: U64,
+        parent_contract_id: AccountId,
+    ) -> bool;
+
+    /// Finalize an `nft_cross_uncompose`. The child is returned to
+    /// `holder_id` only if it is still composed into the parent, and the
+    /// parent is owned by `holder_id`.
+    ///
+    /// Returns true if the child was uncomposed.
+    #[private]
+    fn on_cross_uncompose(
+        &mut self,
+        holder_id: AccountId,
+        child_id: U64,
+        parent_id: U64,
+        parent_contract_id: AccountId,
+    ) -> bool;
+
+    /// Finalize an `nft_transfer_payout` by adding the fee legs returned by
+    /// the fee hook to `payout`. If the fee hook failed, exceeded its cap, or
+    /// the fee legs would exceed `max_len_payout`, `payout` is returned
+    /// unchanged.
+    #[private]
+    fn on_fee_legs(
+        &mut self,
+        token_id: U64,
+        payout: Payout,
+        balance: U128,
+        max_len_payout: u32,
+        memo: Option<String>,
+    ) -> Payout;
+
+    /// Finalize an `nft_claim_migration` by re-minting the token described
+    /// by the `MigrationCertificate` retrieved from `origin_store`.
+    #[private]
+    fn on_migration_certificate(
+        &mut self,
+        origin_store: AccountId,
+        token_id: U64,
+    );
+
+    /// Finalize a `refresh_registry_royalty` by caching the royalty that
+    /// `registry_id` returned for `lookup_id`. If the registry has no
+    /// royalty for `lookup_id`, the cached royalty is removed.
+    #[private]
+    fn on_registry_royalty(
+        &mut self,
+        registry_id: AccountId,
+        lookup_id: U64,
+    );
+
+    /// Finalize an `nft_batch_mint_and_list`. If the market rejected the
+    /// listing, the approvals of `market_id` are revoked and `deposit` is
+    /// refunded to `owner_id`.
+    #[private]
+    fn on_mint_and_list(
+        &mut self,
+        token_ids: Vec<U64>,
+        approval_ids: Vec<U64>,
+        market_id: AccountId,
+        owner_id: AccountId,
+        deposit: U128,
     );
 }
@@ -0,0 +1,244 @@
+// the code generated for `nft_batch_mint_and_list` takes all of its arguments
+#![allow(clippy::too_many_arguments)]
+
+use mintbase_deps::common::{
+    Activity,
+    BatchRoyaltyArgs,
+    ListingIntent,
+    RoyaltyArgs,
+    SplitBetweenUnparsed,
+    TokenMetadata,
+};
+use mintbase_deps::constants::{
+    gas,
+    NO_DEPOSIT,
+};
+use mintbase_deps::interfaces::ext_on_approve;
+use mintbase_deps::logging::{
+    log_add_market,
+    log_batch_approve,
+    log_remove_market,
+    log_revoke,
+    log_storage_deposit,
+    StorageDepositAction,
+};
+use mintbase_deps::near_sdk::json_types::{
+    U128,
+    U64,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    AccountId,
+    Promise,
+    PromiseResult,
+};
+use mintbase_deps::serde_json;
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// List `token_ids` on `market_id` for `price` each, in a single call.
+    /// The tokens are approved for the market, which is notified via
+    /// `nft_on_batch_approve` with a `ListingIntent` as `msg`. If `ft` is
+    /// given, `price` is denominated in that fungible token instead of NEAR.
+    ///
+    /// The same requirements as for `nft_batch_approve` apply, and
+    /// `market_id` must have been registered via `add_market`.
+    #[payable]
+    pub fn list_on_market(
+        &mut self,
+        token_ids: Vec<U64>,
+        market_id: AccountId,
+        price: U128,
+        ft: Option<AccountId>,
+    ) -> Promise {
+        assert!(
+            self.markets.contains(&market_id),
+            "{} not a registered market",
+            market_id
+        );
+        let msg = serde_json::to_string(&ListingIntent {
+            price,
+            ft_contract: ft,
+            autotransfer: true,
+        })
+        .unwrap();
+        self.nft_batch_approve(token_ids, market_id, Some(msg), None)
+            .unwrap()
+    }
+
+    /// Mint `num_to_mint` tokens to the caller and list them on `market_id`
+    /// for `price` each, in a single transaction. Minting works as in
+    /// `nft_batch_mint`, and listing as in `list_on_market`. If the market
+    /// rejects the listing, the approvals are rolled back and the deposit
+    /// forwarded to the market is refunded, but the tokens remain minted.
+    ///
+    /// The deposit must cover the minting fee and the storage of the
+    /// approvals, any remainder is forwarded to the market. Because of gas
+    /// limits, this method may mint at most 70 tokens per call.
+    #[payable]
+    pub fn nft_batch_mint_and_list(
+        &mut self,
+        metadata: TokenMetadata,
+        num_to_mint: u64,
+        royalty_args: Option<RoyaltyArgs>,
+        split_owners: Option<SplitBetweenUnparsed>,
+        market_id: AccountId,
+        price: U128,
+        ft: Option<AccountId>,
+    ) -> Promise {
+        assert!(num_to_mint <= 70); // same limit as `nft_batch_approve`
+        assert!(
+            self.markets.contains(&market_id),
+            "{} not a registered market",
+            market_id
+        );
+        let owner_id = env::predecessor_account_id();
+        let storage_cost = self.storage_costs().common;
+        let approval_storage = storage_cost * num_to_mint as u128;
+        let required_deposit = self.minting_fee_for(&owner_id, num_to_mint) + approval_storage;
+        assert!(
+            env::attached_deposit() > required_deposit,
+            "deposit less than: {}",
+            required_deposit
+        );
+
+        let (first_token_id, last_token_id) = self.nft_batch_mint(
+            owner_id.clone(),
+            metadata,
+            num_to_mint,
+            royalty_args.map(BatchRoyaltyArgs::Shared),
+            split_owners,
+            None,
+            None,
+            None,
+        );
+        let token_ids: Vec<U64> = (first_token_id.0..=last_token_id.0).map(U64).collect();
+
+        self.record_activity(Activity::Approval, num_to_mint as u32);
+        let approval_ids: Vec<U64> = token_ids
+            .iter()
+            .map(|&token_id| {
+                self.approve_internal(token_id.into(), &market_id, None, storage_cost)
+                    .into()
+            })
+            .collect();
+        log_batch_approve(&token_ids, &approval_ids, &market_id);
+        log_storage_deposit(
+            StorageDepositAction::Taken,
+            &owner_id,
+            approval_storage,
+            "nft_batch_mint_and_list",
+        );
+
+        let msg = serde_json::to_string(&ListingIntent {
+            price,
+            ft_contract: ft,
+            autotransfer: true,
+        })
+        .unwrap();
+        let forwarded_deposit = env::attached_deposit() - required_deposit;
+        ext_on_approve::nft_on_batch_approve(
+            token_ids.clone(),
+            approval_ids.clone(),
+            owner_id.clone(),
+            msg,
+            market_id.clone(),
+            forwarded_deposit,
+            gas::NFT_BATCH_APPROVE,
+        )
+        .then(store_self::on_mint_and_list(
+            token_ids,
+            approval_ids,
+            market_id,
+            owner_id,
+            forwarded_deposit.into(),
+            env::current_account_id(),
+            NO_DEPOSIT,
+            gas::ON_MINT_AND_LIST,
+        ))
+    }
+
+    /// Register `market_id` as market on which tokens may be listed via
+    /// `list_on_market`.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn add_market(
+        &mut self,
+        market_id: AccountId,
+    ) {
+        self.assert_store_owner();
+        // does nothing if market_id is already registered
+        if self.markets.insert(&market_id) {
+            log_add_market(&market_id);
+        }
+    }
+
+    /// Remove `market_id` from the registered markets. Existing approvals
+    /// for the market are kept.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn remove_market(
+        &mut self,
+        market_id: AccountId,
+    ) {
+        self.assert_store_owner();
+        if !self.markets.remove(&market_id) {
+            env::panic_str("not a registered market")
+        } else {
+            log_remove_market(&market_id);
+        }
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Lists all markets on which tokens may be listed via `list_on_market`.
+    pub fn list_markets(&self) -> Vec<AccountId> {
+        self.markets.iter().collect()
+    }
+
+    // -------------------------- private methods --------------------------
+
+    #[private]
+    pub fn on_mint_and_list(
+        &mut self,
+        token_ids: Vec<U64>,
+        approval_ids: Vec<U64>,
+        market_id: AccountId,
+        owner_id: AccountId,
+        deposit: U128,
+    ) {
+        if let PromiseResult::Successful(_) = env::promise_result(0) {
+            return;
+        }
+
+        // only roll back approvals that haven't been replaced in the meantime
+        let mut deposits = Vec::new();
+        token_ids
+            .into_iter()
+            .zip(approval_ids)
+            .for_each(|(token_id, approval_id)| {
+                let token_id: u64 = token_id.into();
+                let mut token = self.nft_token_internal(token_id);
+                if token.approvals.get(&market_id) == Some(&approval_id.into()) {
+                    deposits.extend(token.remove_approval(&market_id));
+                    self.tokens.insert(&token_id, &token);
+                    log_revoke(token_id, &market_id);
+                }
+            });
+        self.refund_storage_deposits(deposits, "on_mint_and_list");
+        // the failed listing returned the deposit to this store
+        if deposit.0 > 0 {
+            Promise::new(owner_id).transfer(deposit.0);
+        }
+    }
+
+    // -------------------------- internal methods -------------------------
+}
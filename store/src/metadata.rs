@@ -1,13 +1,17 @@
 use mintbase_deps::common::{
     NFTContractMetadata,
     NonFungibleContractMetadata,
+    StoreRole,
     TokenMetadata,
 };
 use mintbase_deps::logging::{
     log_set_base_uri,
     log_set_icon_base64,
 };
-use mintbase_deps::near_sdk::json_types::U64;
+use mintbase_deps::near_sdk::json_types::{
+    Base64VecU8,
+    U64,
+};
 use mintbase_deps::near_sdk::{
     self,
     env,
@@ -34,13 +38,13 @@ impl MintbaseStore {
     /// owner to be responsible for making sure their `Store` location is
     /// maintained by their preferred storage provider.
     ///
-    /// Only the `Store` owner may call this function.
+    /// Only the `Store` owner and metadata editors may call this function.
     #[payable]
     pub fn set_base_uri(
         &mut self,
         base_uri: String,
     ) {
-        self.assert_store_owner();
+        self.assert_store_owner_or_role(StoreRole::MetadataEditor);
         assert!(base_uri.len() <= 100);
         log_set_base_uri(&base_uri);
         self.metadata.base_uri = Some(base_uri);
@@ -48,13 +52,13 @@ impl MintbaseStore {
 
     /// `icon_base64` is best understood as the `Store` logo/icon.
     ///
-    /// Only the store owner may call this function.
+    /// Only the store owner and metadata editors may call this function.
     #[payable]
     pub fn set_icon_base64(
         &mut self,
         icon: Option<String>,
     ) {
-        self.assert_store_owner();
+        self.assert_store_owner_or_role(StoreRole::MetadataEditor);
         assert!(icon.as_ref().map(|b| b.len() <= 100).unwrap_or(true));
         log_set_icon_base64(&icon);
         self.metadata.icon = icon;
@@ -78,6 +82,29 @@ impl MintbaseStore {
             .1
     }
 
+    /// Check caller-supplied hashes against the `media_hash` or
+    /// `reference_hash` stored for each token, e.g. to audit content
+    /// integrity. An entry is `true` if its hash matches either of the
+    /// stored hashes, and `false` if it doesn't or the token doesn't exist.
+    pub fn verify_media_hashes(
+        &self,
+        entries: Vec<(U64, Base64VecU8)>,
+    ) -> Vec<bool> {
+        entries
+            .into_iter()
+            .map(|(token_id, hash)| {
+                self.tokens
+                    .get(&token_id.into())
+                    .and_then(|token| self.token_metadata.get(&token.metadata_id))
+                    .map(|(_, metadata)| {
+                        metadata.media_hash.as_ref() == Some(&hash)
+                            || metadata.reference_hash.as_ref() == Some(&hash)
+                    })
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
     /// The Token URI is generated to index the token on whatever distributed
     /// storage platform this `Store` uses. Mintbase publishes token data on
     /// Arweave. `Store` owners may opt to use their own storage platform.
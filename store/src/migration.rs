@@ -0,0 +1,247 @@
+use mintbase_deps::common::{
+    MigrationCertificate,
+    MintBatch,
+    TokenKey,
+    TokenMetadata,
+};
+use mintbase_deps::constants::{
+    gas,
+    NO_DEPOSIT,
+};
+use mintbase_deps::interfaces::ext_migration_origin;
+use mintbase_deps::logging::{
+    log_nft_batch_mint,
+    log_nft_migrate,
+    log_on_move,
+    log_storage_deposit,
+    StorageDepositAction,
+};
+use mintbase_deps::near_sdk::json_types::U64;
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    AccountId,
+    Balance,
+    Promise,
+    PromiseResult,
+};
+use mintbase_deps::serde_json;
+use mintbase_deps::token::Token;
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Burn a token to migrate it to `target_store`. A
+    /// `MigrationCertificate` is kept on this `Store` and emitted as event,
+    /// such that the token can be claimed on `target_store` via
+    /// `nft_claim_migration`.
+    ///
+    /// Only the token owner may call this function, and only if moves are
+    /// allowed on this `Store`.
+    #[payable]
+    pub fn nft_migrate(
+        &mut self,
+        token_id: U64,
+        target_store: AccountId,
+    ) {
+        near_sdk::assert_one_yocto();
+        assert!(self.allow_moves, "moves not allowed");
+        assert_ne!(target_store, env::current_account_id());
+        let token = self.nft_token_internal(token_id.into());
+        token.assert_movable_by_owner();
+        assert!(token.is_pred_owner());
+
+        let metadata = self.token_metadata.get(&token.metadata_id).unwrap().1;
+        let royalty = token
+            .royalty_id
+            .map(|royalty_id| self.token_royalty.get(&royalty_id).unwrap().1);
+        let certificate = MigrationCertificate {
+            origin_key: TokenKey::new(token_id.into(), env::current_account_id()),
+            target_store,
+            owner_id: env::predecessor_account_id(),
+            minter: token.minter,
+            metadata,
+            royalty,
+            block_height: env::block_height(),
+        };
+
+        self.burn_triaged(vec![token_id], env::predecessor_account_id());
+        self.migration_certificates
+            .insert(&token_id.into(), &certificate);
+        log_nft_migrate(&certificate);
+    }
+
+    /// Re-mint a token that has been migrated from `origin_store` to this
+    /// `Store`. The `MigrationCertificate` is retrieved from `origin_store`,
+    /// and the token is minted to the owner named therein, with its
+    /// `origin_key` set.
+    ///
+    /// `origin_store` must be a minter on this `Store`, and moves must be
+    /// allowed on this `Store`.
+    pub fn nft_claim_migration(
+        &mut self,
+        origin_store: AccountId,
+        token_id: U64,
+    ) -> Promise {
+        assert!(self.allow_moves, "moves not allowed");
+        assert!(
+            self.minters.contains(&origin_store),
+            "{} not a minter",
+            origin_store
+        );
+
+        ext_migration_origin::get_migration_certificate(
+            token_id,
+            origin_store.clone(),
+            NO_DEPOSIT,
+            gas::GET_MIGRATION_CERTIFICATE,
+        )
+        .then(store_self::on_migration_certificate(
+            origin_store,
+            token_id,
+            env::current_account_id(),
+            NO_DEPOSIT,
+            gas::ON_MIGRATION_CERTIFICATE,
+        ))
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the `MigrationCertificate` of a token that has been migrated from
+    /// this `Store`.
+    pub fn get_migration_certificate(
+        &self,
+        token_id: U64,
+    ) -> Option<MigrationCertificate> {
+        self.migration_certificates.get(&token_id.into())
+    }
+
+    // -------------------------- private methods --------------------------
+
+    #[private]
+    pub fn on_migration_certificate(
+        &mut self,
+        origin_store: AccountId,
+        token_id: U64,
+    ) {
+        let certificate = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                serde_json::from_slice::<Option<MigrationCertificate>>(&value)
+                    .ok()
+                    .flatten()
+            },
+            _ => None,
+        }
+        .expect("no migration certificate");
+
+        let origin_key = TokenKey::new(token_id.into(), origin_store.clone());
+        assert_eq!(
+            certificate.origin_key.to_string(),
+            origin_key.to_string(),
+            "certificate for another token"
+        );
+        assert_eq!(
+            certificate.target_store,
+            env::current_account_id(),
+            "certificate for another store"
+        );
+        assert!(
+            self.migrated_origins.insert(&origin_key.to_string()),
+            "token already migrated"
+        );
+
+        self.mint_migrated_token(origin_store, certificate);
+    }
+
+    // -------------------------- internal methods -------------------------
+
+    /// Mint a single token as described by `certificate`, keeping track of it
+    /// in the same way as `nft_batch_mint`.
+    fn mint_migrated_token(
+        &mut self,
+        origin_store: AccountId,
+        certificate: MigrationCertificate,
+    ) {
+        let covered_storage = env::account_balance()
+            - (env::storage_usage() as u128 * self.storage_costs().storage_price_per_byte);
+        let (metadata, md_size) = TokenMetadata::from_with_size(certificate.metadata, 1);
+        let roy_len = certificate
+            .royalty
+            .as_ref()
+            .map(|royalty| royalty.split_between.len() as u32)
+            .unwrap_or(0);
+        let expected_storage_consumption: Balance =
+            self.storage_cost_to_mint(1, 1, md_size, roy_len, 1);
+        assert!(
+            covered_storage >= expected_storage_consumption,
+            "covered: {}; need: {}",
+            covered_storage,
+            expected_storage_consumption
+        );
+
+        let owner_id = certificate.owner_id;
+        let mut owned_set = self.get_or_make_new_owner_set(&owner_id);
+        let lookup_id: u64 = self.tokens_minted;
+        let royalty_id = certificate.royalty.as_ref().map(|royalty| {
+            self.token_royalty.insert(&lookup_id, &(1, royalty.clone()));
+            lookup_id
+        });
+        let meta_ref = metadata.reference.as_ref().map(|s| s.to_string());
+        let meta_extra = metadata.extra.as_ref().map(|s| s.to_string());
+        let media_hints = metadata.media_hints();
+        let event_metadata = self.mint_event_metadata.then(|| metadata.clone());
+        self.token_metadata.insert(&lookup_id, &(1, metadata));
+
+        let token_id = self.tokens_minted;
+        let mut token = Token::new(
+            owner_id.clone(),
+            token_id,
+            lookup_id,
+            royalty_id,
+            None,
+            certificate.minter.clone(),
+        );
+        token.origin_key = Some(certificate.origin_key.clone());
+        owned_set.insert(&token_id);
+        self.tokens.insert(&token_id, &token);
+        self.tokens_minted += 1;
+        self.tokens_per_owner.insert(&owner_id, &owned_set);
+
+        let mint_id = MintBatch::generate_id(&origin_store, lookup_id);
+        self.insert_mint_batch(MintBatch {
+            mint_id: mint_id.clone(),
+            minter: origin_store,
+            owner_id: owner_id.clone(),
+            first_token_id: token_id,
+            last_token_id: token_id,
+            metadata_id: lookup_id,
+            royalty_id,
+            block_height: env::block_height(),
+        });
+
+        log_nft_batch_mint(
+            token_id,
+            token_id,
+            certificate.minter.as_ref(),
+            owner_id.as_ref(),
+            &certificate.royalty,
+            &None,
+            &meta_ref,
+            &meta_extra,
+            &mint_id,
+            &media_hints,
+            event_metadata.as_ref(),
+        );
+        log_on_move(token_id.into(), &certificate.origin_key.to_string());
+        log_storage_deposit(
+            StorageDepositAction::Consumed,
+            &owner_id,
+            expected_storage_consumption,
+            "nft_claim_migration",
+        );
+    }
+}
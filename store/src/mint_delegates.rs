@@ -0,0 +1,117 @@
+use mintbase_deps::logging::{
+    log_approve_mint_delegate,
+    log_revoke_mint_delegate,
+    log_storage_deposit,
+    StorageDepositAction,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    AccountId,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Allow `delegate_id` to mint on behalf of the calling minter, e.g. a
+    /// hot wallet that batches the mints of a platform's users. Tokens
+    /// minted by a delegate record the delegating minter as their minter,
+    /// and are subject to the minter's restrictions.
+    ///
+    /// Only minters may call this function. The attached deposit must
+    /// cover the storage of the delegation.
+    #[payable]
+    pub fn approve_mint_delegate(
+        &mut self,
+        delegate_id: AccountId,
+    ) {
+        assert!(env::attached_deposit() > self.storage_costs().common);
+        let minter_id = env::predecessor_account_id();
+        assert!(
+            self.minters.contains(&minter_id),
+            "{} not a minter",
+            minter_id
+        );
+        assert_ne!(minter_id, delegate_id, "can't delegate to self");
+        let mut delegates = self.mint_delegates.get(&minter_id).unwrap_or_default();
+        if delegates.contains(&delegate_id) {
+            return;
+        }
+        delegates.push(delegate_id.clone());
+        self.mint_delegates.insert(&minter_id, &delegates);
+        log_approve_mint_delegate(&minter_id, &delegate_id);
+        log_storage_deposit(
+            StorageDepositAction::Taken,
+            &minter_id,
+            env::attached_deposit(),
+            "approve_mint_delegate",
+        );
+    }
+
+    /// Revoke the permission of `delegate_id` to mint on behalf of the
+    /// calling minter.
+    #[payable]
+    pub fn revoke_mint_delegate(
+        &mut self,
+        delegate_id: AccountId,
+    ) {
+        near_sdk::assert_one_yocto();
+        let minter_id = env::predecessor_account_id();
+        let mut delegates = self.mint_delegates.get(&minter_id).unwrap_or_default();
+        assert!(
+            delegates.contains(&delegate_id),
+            "{} not a delegate",
+            delegate_id
+        );
+        delegates.retain(|other| *other != delegate_id);
+        if delegates.is_empty() {
+            self.mint_delegates.remove(&minter_id);
+        } else {
+            self.mint_delegates.insert(&minter_id, &delegates);
+        }
+        log_revoke_mint_delegate(&minter_id, &delegate_id);
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// List the accounts that may mint on behalf of `minter_id`.
+    pub fn get_mint_delegates(
+        &self,
+        minter_id: AccountId,
+    ) -> Vec<AccountId> {
+        self.mint_delegates.get(&minter_id).unwrap_or_default()
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+
+    /// The minter under whose name the predecessor mints. Unless
+    /// `on_behalf_of` is given, this is the predecessor itself. Otherwise,
+    /// the predecessor must be a delegate of `on_behalf_of`, and must not be
+    /// banned from minting itself.
+    pub(crate) fn resolve_minter(
+        &self,
+        on_behalf_of: Option<AccountId>,
+    ) -> AccountId {
+        let predecessor = env::predecessor_account_id();
+        let minter_id = match on_behalf_of {
+            Some(minter_id) if minter_id != predecessor => minter_id,
+            _ => return predecessor,
+        };
+        assert!(
+            self.mint_delegates
+                .get(&minter_id)
+                .map(|delegates| delegates.contains(&predecessor))
+                .unwrap_or(false),
+            "{} not a delegate of {}",
+            predecessor,
+            minter_id
+        );
+        self.assert_not_banned_from_minting(&predecessor);
+        minter_id
+    }
+}
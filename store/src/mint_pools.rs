@@ -0,0 +1,236 @@
+use mintbase_deps::common::{
+    Activity,
+    MintPool,
+    NearTime,
+    Royalty,
+    RoyaltyArgs,
+    TokenMetadata,
+};
+use mintbase_deps::logging::{
+    log_storage_deposit,
+    StorageDepositAction,
+};
+use mintbase_deps::near_sdk::json_types::{
+    U128,
+    U64,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    Balance,
+    Promise,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Register a mint pool for a generative drop, from which anyone may
+    /// mint via `nft_mint_from_pool` for `price` yoctoNEAR per token, until
+    /// `mint_end` if given. Metadata entries are added to the pool via
+    /// `add_to_mint_pool`. Returns the id of the pool.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn register_mint_pool(
+        &mut self,
+        royalty_args: Option<RoyaltyArgs>,
+        price: U128,
+        mint_end: Option<NearTime>,
+    ) -> U64 {
+        self.assert_store_owner();
+        self.assert_royalty_within_cap(&royalty_args);
+        let pool_id = self.mint_pools_registered;
+        let pool = MintPool {
+            pool_id,
+            royalty: royalty_args.map(Royalty::new),
+            price,
+            mint_end,
+            remaining: 0,
+            minted: 0,
+        };
+        self.mint_pools.insert(&pool_id, &pool);
+        self.mint_pools_registered += 1;
+        pool_id.into()
+    }
+
+    /// Add metadata entries to a mint pool, at most 50 per call. The storage
+    /// of the entries must be covered.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn add_to_mint_pool(
+        &mut self,
+        pool_id: U64,
+        metadata: Vec<TokenMetadata>,
+    ) {
+        self.assert_store_owner();
+        assert!(!metadata.is_empty());
+        assert!(metadata.len() <= 50);
+        let mut pool = self.get_mint_pool_internal(pool_id.into());
+        assert!(pool.is_open(), "pool closed");
+
+        let covered_storage = env::account_balance()
+            - (env::storage_usage() as u128 * self.storage_costs().storage_price_per_byte);
+        let (metadata, md_sizes): (Vec<_>, Vec<_>) = metadata
+            .into_iter()
+            .map(|metadata| TokenMetadata::from_with_size(metadata, 1))
+            .unzip();
+        let expected_storage_consumption: Balance = metadata.len() as u128
+            * self.storage_costs().common
+            + md_sizes.iter().sum::<u64>() as u128 * self.storage_costs().storage_price_per_byte;
+        assert!(
+            covered_storage >= expected_storage_consumption,
+            "covered: {}; need: {}",
+            covered_storage,
+            expected_storage_consumption
+        );
+
+        metadata.into_iter().for_each(|metadata| {
+            self.mint_pool_entries
+                .insert(&(pool.pool_id, pool.remaining), &metadata);
+            pool.remaining += 1;
+        });
+        self.mint_pools.insert(&pool.pool_id, &pool);
+        log_storage_deposit(
+            StorageDepositAction::Consumed,
+            &self.owner_id,
+            expected_storage_consumption,
+            "add_to_mint_pool",
+        );
+    }
+
+    /// End a mint pool, such that no further tokens may be minted from it.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn end_mint_pool(
+        &mut self,
+        pool_id: U64,
+    ) {
+        self.assert_store_owner();
+        let mut pool = self.get_mint_pool_internal(pool_id.into());
+        assert!(pool.is_open(), "pool closed");
+        pool.mint_end = Some(NearTime(env::block_timestamp()));
+        self.mint_pools.insert(&pool.pool_id, &pool);
+    }
+
+    /// Mint a token from a mint pool to the caller. The metadata of the
+    /// token is picked pseudo-randomly from the remaining entries of the
+    /// pool, such that rare entries can't be cherry-picked.
+    ///
+    /// The attached deposit must cover the pool price, which is paid to
+    /// the store owner, and the storage of the minted token. Any excess is
+    /// refunded.
+    #[payable]
+    pub fn nft_mint_from_pool(
+        &mut self,
+        pool_id: U64,
+    ) {
+        let mut pool = self.get_mint_pool_internal(pool_id.into());
+        assert!(pool.is_open(), "pool closed");
+        assert!(pool.remaining > 0, "pool exhausted");
+        let owner_id = env::predecessor_account_id();
+        let minter_id = self.owner_id.clone();
+        self.assert_not_banned_from_minting(&owner_id);
+        self.assert_mint_window_open(&minter_id);
+        self.record_activity(Activity::Mint, 1);
+
+        // Consume the picked entry by moving the last entry into its place
+        let index = self.random_pool_index(&pool);
+        let last_index = pool.remaining - 1;
+        let metadata = self
+            .mint_pool_entries
+            .remove(&(pool.pool_id, last_index))
+            .unwrap();
+        let metadata = if index == last_index {
+            metadata
+        } else {
+            self.mint_pool_entries
+                .insert(&(pool.pool_id, index), &metadata)
+                .unwrap()
+        };
+        pool.remaining -= 1;
+        pool.minted += 1;
+        self.mint_pools.insert(&pool.pool_id, &pool);
+
+        let (metadata, md_size) = TokenMetadata::from_with_size(metadata, 1);
+        let roy_len = pool
+            .royalty
+            .as_ref()
+            .map(|royalty| royalty.split_between.len() as u32)
+            .unwrap_or(0);
+        let price: Balance = pool.price.into();
+        let storage_cost = self.storage_cost_to_mint(1, 1, md_size, roy_len, 1);
+        let required = price + storage_cost;
+        let attached_deposit = env::attached_deposit();
+        assert!(
+            attached_deposit >= required,
+            "attached: {}; need: {}",
+            attached_deposit,
+            required
+        );
+
+        self.mint_single_token(owner_id.clone(), minter_id.clone(), metadata, pool.royalty);
+
+        if price > 0 {
+            Promise::new(minter_id).transfer(price);
+        }
+        log_storage_deposit(
+            StorageDepositAction::Taken,
+            &owner_id,
+            storage_cost,
+            "nft_mint_from_pool",
+        );
+        let refund = attached_deposit - required;
+        if refund > 0 {
+            log_storage_deposit(
+                StorageDepositAction::Refunded,
+                &owner_id,
+                refund,
+                "nft_mint_from_pool",
+            );
+            Promise::new(owner_id).transfer(refund);
+        }
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get a mint pool, including the number of entries that remain to be
+    /// minted. The entries themselves are not revealed.
+    pub fn get_mint_pool(
+        &self,
+        pool_id: U64,
+    ) -> Option<MintPool> {
+        self.mint_pools.get(&pool_id.into())
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+
+    fn get_mint_pool_internal(
+        &self,
+        pool_id: u64,
+    ) -> MintPool {
+        self.mint_pools.get(&pool_id).expect("no such pool")
+    }
+
+    /// Pick the index of the entry to mint next from `pool`. The index is
+    /// derived from `env::random_seed`, which can't be predicted by the
+    /// caller, and from the number of tokens minted so far, such that mints
+    /// within the same block get different entries.
+    fn random_pool_index(
+        &self,
+        pool: &MintPool,
+    ) -> u64 {
+        let mut seed = env::random_seed();
+        seed.extend_from_slice(&pool.minted.to_le_bytes());
+        let hash = env::sha256(&seed);
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&hash[..8]);
+        u64::from_le_bytes(bytes) % pool.remaining
+    }
+}
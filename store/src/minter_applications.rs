@@ -0,0 +1,133 @@
+use mintbase_deps::common::MinterApplication;
+use mintbase_deps::constants::MINTER_APPLICATION_DEPOSIT;
+use mintbase_deps::logging::{
+    log_apply_for_minter,
+    log_approve_minter_application,
+    log_grant_minter,
+    log_reject_minter_application,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    AccountId,
+    Promise,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Apply to become a minter on this `Store`. `profile_reference` refers
+    /// to an off-chain profile for the store owner to review. The attached
+    /// deposit must be at least `MINTER_APPLICATION_DEPOSIT`, and is held
+    /// until the application has been approved or rejected.
+    #[payable]
+    pub fn apply_for_minter(
+        &mut self,
+        profile_reference: String,
+    ) {
+        let account_id = env::predecessor_account_id();
+        let deposit = env::attached_deposit();
+        assert!(
+            deposit >= MINTER_APPLICATION_DEPOSIT,
+            "deposit must be at least {}",
+            MINTER_APPLICATION_DEPOSIT
+        );
+        assert!(profile_reference.len() <= 100);
+        assert!(
+            !self.minters.contains(&account_id),
+            "{} already a minter",
+            account_id
+        );
+        self.assert_not_banned_from_minting(&account_id);
+        assert!(
+            self.minter_applications.get(&account_id).is_none(),
+            "{} already applied",
+            account_id
+        );
+
+        log_apply_for_minter(&account_id, &profile_reference);
+        self.minter_applications.insert(
+            &account_id,
+            &MinterApplication::new(account_id.clone(), profile_reference, deposit),
+        );
+    }
+
+    /// Approve the pending application of `account_id`, granting minting
+    /// privileges and refunding the application deposit.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn approve_minter_application(
+        &mut self,
+        account_id: AccountId,
+    ) {
+        self.assert_store_owner();
+        let application = self
+            .minter_applications
+            .remove(&account_id)
+            .expect("no application");
+        if self.minters.insert(&account_id) {
+            self.start_minter_cooling_off(&account_id);
+            log_grant_minter(&account_id);
+        }
+        log_approve_minter_application(&account_id);
+        Promise::new(account_id).transfer(application.deposit.0);
+    }
+
+    /// Reject the pending application of `account_id`. If `slash` is true,
+    /// the application deposit is paid to the store owner instead of being
+    /// refunded, e.g. for spam.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn reject_minter_application(
+        &mut self,
+        account_id: AccountId,
+        slash: bool,
+    ) {
+        self.assert_store_owner();
+        let application = self
+            .minter_applications
+            .remove(&account_id)
+            .expect("no application");
+        log_reject_minter_application(&account_id, slash);
+        let receiver_id = if slash {
+            self.owner_id.clone()
+        } else {
+            account_id
+        };
+        Promise::new(receiver_id).transfer(application.deposit.0);
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the pending minter application of `account_id`.
+    pub fn get_minter_application(
+        &self,
+        account_id: AccountId,
+    ) -> Option<MinterApplication> {
+        self.minter_applications.get(&account_id)
+    }
+
+    /// List the pending minter applications.
+    pub fn list_minter_applications(
+        &self,
+        from_index: Option<String>,
+        limit: Option<usize>,
+    ) -> Vec<MinterApplication> {
+        self.minter_applications
+            .values()
+            .skip(
+                from_index
+                    .unwrap_or_else(|| "0".to_string())
+                    .parse()
+                    .unwrap(),
+            )
+            .take(limit.unwrap_or(10))
+            .collect()
+    }
+}
@@ -1,16 +1,40 @@
+// the code generated for `nft_batch_mint` takes all of its arguments
+#![allow(clippy::too_many_arguments)]
+
+use std::collections::BTreeMap;
+
 use mintbase_deps::common::{
-    NewSplitOwner,
+    Activity,
+    BatchRoyaltyArgs,
+    MintBatch,
+    MinterQuota,
+    NearTime,
     Royalty,
     RoyaltyArgs,
     SplitBetweenUnparsed,
     SplitOwners,
+    StoreRole,
+    TimeUnit,
     TokenMetadata,
 };
 use mintbase_deps::constants::MAX_LEN_PAYOUT;
 use mintbase_deps::logging::{
+    log_change_minters,
     log_grant_minter,
+    log_grant_minter_until,
+    log_minter_grant_lapsed,
     log_nft_batch_mint,
     log_revoke_minter,
+    log_set_default_royalty,
+    log_set_minter_cooling_off,
+    log_set_minting_fee,
+    log_storage_deposit,
+    log_update_token_royalty,
+    StorageDepositAction,
+};
+use mintbase_deps::near_sdk::json_types::{
+    U128,
+    U64,
 };
 use mintbase_deps::near_sdk::{
     self,
@@ -18,6 +42,7 @@ use mintbase_deps::near_sdk::{
     near_bindgen,
     AccountId,
     Balance,
+    Promise,
 };
 use mintbase_deps::token::Token;
 
@@ -31,57 +56,130 @@ impl MintbaseStore {
     /// a token.
     ///
     /// Restrictions:
-    /// - Only minters may call this function.
+    /// - Only minters and accounts on the mint allowlist may call this function.
+    /// - Delegates may mint on behalf of a minter via `on_behalf_of`.
+    /// - Tokens minted with `transferable` set to false are soulbound: they may
+    ///   not be transferred or approved, but may be burned.
+    /// - Tokens minted with `expires_at` become invalid at that time, after
+    ///   which anyone may burn them via `nft_burn_expired`.
     /// - `owner_id` must be a valid Near address.
     /// - Because of logging limits, this method may mint at most 99 tokens per call.
     /// - 1.0 >= `royalty_f` >= 0.0. `royalty_f` is ignored if `royalty` is `None`.
     /// - If a `royalty` is provided, percentages **must** be non-negative and add to one.
     /// - The maximum length of the royalty mapping is 50.
+    /// - `royalty_args` may map copy indices to royalties instead, e.g. for a
+    ///   collaboration piece inside an edition. The other copies get the
+    ///   default royalty, if any.
     ///
     /// This method is the most significant increase of storage costs on this
     /// contract. Minters are expected to manage their own storage costs.
+    ///
+    /// Returns the first and last minted token id, such that contracts
+    /// calling this method may chain follow-up actions on the tokens.
     #[payable]
     pub fn nft_batch_mint(
         &mut self,
         owner_id: AccountId,
         metadata: TokenMetadata,
         num_to_mint: u64,
-        royalty_args: Option<RoyaltyArgs>,
+        royalty_args: Option<BatchRoyaltyArgs>,
         split_owners: Option<SplitBetweenUnparsed>,
-    ) {
+        on_behalf_of: Option<AccountId>,
+        transferable: Option<bool>,
+        expires_at: Option<NearTime>,
+    ) -> (U64, U64) {
         assert!(num_to_mint > 0);
         assert!(num_to_mint <= 125); // upper gas limit
         assert!(env::attached_deposit() >= 1);
-        let minter_id = env::predecessor_account_id();
+        let minter_id = self.resolve_minter(on_behalf_of);
+        let sets_payout = royalty_args.is_some() || split_owners.is_some();
+        let (royalty_args, royalty_overrides) = BatchRoyaltyArgs::split(royalty_args);
+        royalty_overrides
+            .values()
+            .for_each(|royalty_args| self.assert_royalty_within_cap(&Some(royalty_args.clone())));
+        self.admit_mint(&minter_id, num_to_mint);
+        let (royalty_args, minting_fee) =
+            self.prepare_mint(&minter_id, num_to_mint, royalty_args, sets_payout);
         assert!(
-            self.minters.contains(&minter_id),
-            "{} not a minter",
-            minter_id.as_ref()
+            env::attached_deposit() >= minting_fee,
+            "attached: {}; minting fee: {}",
+            env::attached_deposit(),
+            minting_fee
+        );
+
+        let first_token_id = self.tokens_minted;
+        self.tokens_minted += num_to_mint;
+        self.mint_batch_internal(
+            minter_id,
+            owner_id,
+            first_token_id,
+            metadata,
+            num_to_mint,
+            royalty_args,
+            royalty_overrides,
+            split_owners,
+            transferable,
+            expires_at,
+            minting_fee,
+            "nft_batch_mint",
+        )
+    }
+
+    /// Mint one token for each entry of `metadata`, such that every token
+    /// has its own `TokenMetadata`, e.g. for generative collections. Royalty
+    /// and split owners are shared by all minted tokens.
+    ///
+    /// The same restrictions as for `nft_batch_mint` apply, except that
+    /// this method may mint at most 50 tokens per call, as each token emits
+    /// its own mint event. The storage costs are computed from the size of
+    /// each metadata entry.
+    ///
+    /// Returns the minted token ids, in the order of `metadata`.
+    #[payable]
+    pub fn nft_batch_mint_distinct(
+        &mut self,
+        owner_id: AccountId,
+        metadata: Vec<TokenMetadata>,
+        royalty_args: Option<RoyaltyArgs>,
+        split_owners: Option<SplitBetweenUnparsed>,
+        on_behalf_of: Option<AccountId>,
+        transferable: Option<bool>,
+        expires_at: Option<NearTime>,
+    ) -> Vec<U64> {
+        let num_to_mint = metadata.len() as u64;
+        assert!(num_to_mint > 0);
+        assert!(num_to_mint <= 50); // upper log limit
+        assert!(env::attached_deposit() >= 1);
+        Self::assert_expiry_in_future(&expires_at);
+        let minter_id = self.resolve_minter(on_behalf_of);
+        let sets_payout = royalty_args.is_some() || split_owners.is_some();
+        self.admit_mint(&minter_id, num_to_mint);
+        let (royalty_args, minting_fee) =
+            self.prepare_mint(&minter_id, num_to_mint, royalty_args, sets_payout);
+        assert!(
+            env::attached_deposit() >= minting_fee,
+            "attached: {}; minting fee: {}",
+            env::attached_deposit(),
+            minting_fee
         );
 
-        // Calculating storage consuption upfront saves gas if the transaction
-        // were to fail later.
         let covered_storage = env::account_balance()
-            - (env::storage_usage() as u128 * self.storage_costs.storage_price_per_byte);
-        let (metadata, md_size) = TokenMetadata::from_with_size(metadata, num_to_mint);
+            - (env::storage_usage() as u128 * self.storage_costs().storage_price_per_byte);
+        let (metadata, md_sizes): (Vec<_>, Vec<_>) = metadata
+            .into_iter()
+            .map(|metadata| TokenMetadata::from_with_size(metadata, 1))
+            .unzip();
         let roy_len = royalty_args
             .as_ref()
-            .map(|pre_roy| {
-                let len = pre_roy.split_between.len();
-                len as u32
-            })
+            .map(|pre_roy| pre_roy.split_between.len() as u32)
             .unwrap_or(0);
         let split_len = split_owners
             .as_ref()
-            .map(|pre_split| {
-                let len = pre_split.len();
-                len as u32
-            })
-            // if there is no split map, there still is an owner, thus default to 1
+            .map(|pre_split| pre_split.len() as u32)
             .unwrap_or(1);
         assert!(roy_len + split_len <= MAX_LEN_PAYOUT);
         let expected_storage_consumption: Balance =
-            self.storage_cost_to_mint(num_to_mint, md_size, roy_len, split_len);
+            self.storage_cost_to_mint(1, num_to_mint, md_sizes.iter().sum(), roy_len, split_len);
         assert!(
             covered_storage >= expected_storage_consumption,
             "covered: {}; need: {}",
@@ -89,57 +187,421 @@ impl MintbaseStore {
             expected_storage_consumption
         );
 
-        let checked_royalty = royalty_args.map(Royalty::new);
-        let checked_split = split_owners.map(SplitOwners::new);
+        let checked_royalty = royalty_args.map(Self::checked_royalty);
+        let checked_split = split_owners.map(Self::checked_split_owners);
 
         let mut owned_set = self.get_or_make_new_owner_set(&owner_id);
 
-        // Lookup Id is used by the token to lookup Royalty and Metadata fields on
-        // the contract (to avoid unnecessary duplication)
-        let lookup_id: u64 = self.tokens_minted;
+        // The royalty is shared, the metadata of each token is looked up by
+        // its own token id.
+        let first_token_id: u64 = self.tokens_minted;
         let royalty_id = checked_royalty.clone().map(|royalty| {
             self.token_royalty
-                .insert(&lookup_id, &(num_to_mint as u16, royalty));
-            lookup_id
+                .insert(&first_token_id, &(num_to_mint as u16, royalty));
+            first_token_id
         });
+        let mint_id = MintBatch::generate_id(&minter_id, first_token_id);
 
-        let meta_ref = metadata.reference.as_ref().map(|s| s.to_string());
-        let meta_extra = metadata.extra.as_ref().map(|s| s.to_string());
-        self.token_metadata
-            .insert(&lookup_id, &(num_to_mint as u16, metadata));
-
-        // Mint em up hot n fresh with a side of vegan bacon
-        (0..num_to_mint).for_each(|i| {
-            let token_id = self.tokens_minted + i;
-            let token = Token::new(
+        metadata.into_iter().enumerate().for_each(|(i, metadata)| {
+            let token_id = first_token_id + i as u64;
+            let meta_ref = metadata.reference.as_ref().map(|s| s.to_string());
+            let meta_extra = metadata.extra.as_ref().map(|s| s.to_string());
+            let media_hints = metadata.media_hints();
+            self.token_metadata.insert(&token_id, &(1, metadata));
+            let mut token = Token::new(
                 owner_id.clone(),
                 token_id,
-                lookup_id,
+                token_id,
                 royalty_id,
                 checked_split.clone(),
                 minter_id.clone(),
             );
+            token.soulbound = !transferable.unwrap_or(true);
+            token.expires_at = expires_at.clone();
             owned_set.insert(&token_id);
             self.tokens.insert(&token_id, &token);
+            log_nft_batch_mint(
+                token_id,
+                token_id,
+                minter_id.as_ref(),
+                owner_id.as_ref(),
+                &checked_royalty,
+                &checked_split,
+                &meta_ref,
+                &meta_extra,
+                &mint_id,
+                &media_hints,
+                // embedding the metadata in every event would exceed the log limits
+                None,
+            );
         });
         self.tokens_minted += num_to_mint;
         self.tokens_per_owner.insert(&owner_id, &owned_set);
+        log_storage_deposit(
+            StorageDepositAction::Consumed,
+            &minter_id,
+            expected_storage_consumption,
+            "nft_batch_mint_distinct",
+        );
 
-        let minted = self.tokens_minted;
-        log_nft_batch_mint(
-            minted - num_to_mint,
-            minted - 1,
-            minter_id.as_ref(),
-            owner_id.as_ref(),
-            &checked_royalty,
-            &checked_split,
-            &meta_ref,
-            &meta_extra,
+        self.insert_mint_batch(MintBatch {
+            mint_id: mint_id.clone(),
+            minter: minter_id,
+            owner_id,
+            first_token_id,
+            last_token_id: first_token_id + num_to_mint - 1,
+            metadata_id: first_token_id,
+            royalty_id,
+            block_height: env::block_height(),
+        });
+        self.pay_minting_fee(minting_fee);
+        (first_token_id..first_token_id + num_to_mint)
+            .map(U64)
+            .collect()
+    }
+
+    /// Mint copies of a token to each of `recipients`, given as pairs of
+    /// owner and number of copies, e.g. to airdrop an edition. All copies
+    /// share `metadata`, royalty and split owners, and each recipient gets
+    /// its own `MintBatch` and mint event.
+    ///
+    /// The same restrictions as for `nft_batch_mint` apply to the total
+    /// number of copies. Because of logging limits, there may be at most 50
+    /// recipients per call.
+    ///
+    /// Returns the first and last minted token id. The tokens of each
+    /// recipient follow those of the previous recipient.
+    #[payable]
+    pub fn nft_batch_mint_multi(
+        &mut self,
+        recipients: Vec<(AccountId, u64)>,
+        metadata: TokenMetadata,
+        royalty_args: Option<RoyaltyArgs>,
+        split_owners: Option<SplitBetweenUnparsed>,
+        on_behalf_of: Option<AccountId>,
+        transferable: Option<bool>,
+        expires_at: Option<NearTime>,
+    ) -> (U64, U64) {
+        let num_owners = recipients.len() as u64;
+        assert!(num_owners > 0);
+        assert!(num_owners <= 50); // upper log limit
+        assert!(recipients.iter().all(|(_, n)| *n > 0));
+        let num_to_mint: u64 = recipients.iter().map(|(_, n)| n).sum();
+        assert!(num_to_mint <= 125); // upper gas limit
+        assert!(env::attached_deposit() >= 1);
+        Self::assert_expiry_in_future(&expires_at);
+        let minter_id = self.resolve_minter(on_behalf_of);
+        let sets_payout = royalty_args.is_some() || split_owners.is_some();
+        self.admit_mint(&minter_id, num_to_mint);
+        let (royalty_args, minting_fee) =
+            self.prepare_mint(&minter_id, num_to_mint, royalty_args, sets_payout);
+        assert!(
+            env::attached_deposit() >= minting_fee,
+            "attached: {}; minting fee: {}",
+            env::attached_deposit(),
+            minting_fee
+        );
+
+        let covered_storage = env::account_balance()
+            - (env::storage_usage() as u128 * self.storage_costs().storage_price_per_byte);
+        let (metadata, md_size) = TokenMetadata::from_with_size(metadata, num_to_mint);
+        let roy_len = royalty_args
+            .as_ref()
+            .map(|pre_roy| pre_roy.split_between.len() as u32)
+            .unwrap_or(0);
+        let split_len = split_owners
+            .as_ref()
+            .map(|pre_split| pre_split.len() as u32)
+            .unwrap_or(1);
+        assert!(roy_len + split_len <= MAX_LEN_PAYOUT);
+        let expected_storage_consumption: Balance =
+            self.storage_cost_to_mint(num_owners, num_to_mint, md_size, roy_len, split_len);
+        assert!(
+            covered_storage >= expected_storage_consumption,
+            "covered: {}; need: {}",
+            covered_storage,
+            expected_storage_consumption
         );
+
+        let checked_royalty = royalty_args.map(Self::checked_royalty);
+        let checked_split = split_owners.map(Self::checked_split_owners);
+
+        // Metadata and royalty are shared by all recipients.
+        let lookup_id: u64 = self.tokens_minted;
+        let royalty_id = checked_royalty.clone().map(|royalty| {
+            self.token_royalty
+                .insert(&lookup_id, &(num_to_mint as u16, royalty));
+            lookup_id
+        });
+        let meta_ref = metadata.reference.as_ref().map(|s| s.to_string());
+        let meta_extra = metadata.extra.as_ref().map(|s| s.to_string());
+        let media_hints = metadata.media_hints();
+        self.token_metadata
+            .insert(&lookup_id, &(num_to_mint as u16, metadata));
+
+        for (owner_id, n) in recipients {
+            let first_token_id = self.tokens_minted;
+            let last_token_id = first_token_id + n - 1;
+            let mut owned_set = self.get_or_make_new_owner_set(&owner_id);
+            (first_token_id..=last_token_id).for_each(|token_id| {
+                let mut token = Token::new(
+                    owner_id.clone(),
+                    token_id,
+                    lookup_id,
+                    royalty_id,
+                    checked_split.clone(),
+                    minter_id.clone(),
+                );
+                token.soulbound = !transferable.unwrap_or(true);
+                token.expires_at = expires_at.clone();
+                owned_set.insert(&token_id);
+                self.tokens.insert(&token_id, &token);
+            });
+            self.tokens_minted += n;
+            self.tokens_per_owner.insert(&owner_id, &owned_set);
+
+            // token ids are unique, and thus serve as nonce
+            let mint_id = MintBatch::generate_id(&minter_id, first_token_id);
+            self.insert_mint_batch(MintBatch {
+                mint_id: mint_id.clone(),
+                minter: minter_id.clone(),
+                owner_id: owner_id.clone(),
+                first_token_id,
+                last_token_id,
+                metadata_id: lookup_id,
+                royalty_id,
+                block_height: env::block_height(),
+            });
+
+            log_nft_batch_mint(
+                first_token_id,
+                last_token_id,
+                minter_id.as_ref(),
+                owner_id.as_ref(),
+                &checked_royalty,
+                &checked_split,
+                &meta_ref,
+                &meta_extra,
+                &mint_id,
+                &media_hints,
+                // embedding the metadata in every event would exceed the log limits
+                None,
+            );
+        }
+        log_storage_deposit(
+            StorageDepositAction::Consumed,
+            &minter_id,
+            expected_storage_consumption,
+            "nft_batch_mint_multi",
+        );
+        self.pay_minting_fee(minting_fee);
+        (U64(lookup_id), U64(self.tokens_minted - 1))
+    }
+
+    /// Replace the royalty of the tokens minted in the batch starting at
+    /// `lookup_id`, or remove it by passing `None`, e.g. to fix a
+    /// misconfigured royalty. The storage of additional royalty receivers
+    /// must be covered.
+    ///
+    /// Only the minter of the batch may call this function, and only while
+    /// all of its tokens are still owned by the account they were minted
+    /// to. The royalty may not be shared with tokens of other batches, nor
+    /// may the batch have been minted with per-copy royalties.
+    #[payable]
+    pub fn update_token_royalty(
+        &mut self,
+        lookup_id: U64,
+        royalty_args: Option<RoyaltyArgs>,
+    ) {
+        near_sdk::assert_one_yocto();
+        let lookup_id: u64 = lookup_id.into();
+        let mint_id = self.mint_batch_ids.get(&lookup_id).expect("no such batch");
+        let mut batch = self.mint_batches.get(&mint_id).unwrap();
+        let minter_id = env::predecessor_account_id();
+        assert_eq!(batch.minter, minter_id, "batch of another minter");
+        assert!(
+            self.mint_sessions.get(&mint_id).is_none(),
+            "mint session unfinished"
+        );
+        self.assert_can_set_royalty(&minter_id, royalty_args.is_some());
+        self.assert_royalty_within_cap(&royalty_args);
+
+        let mut tokens: Vec<Token> = (batch.first_token_id..=batch.last_token_id)
+            .filter_map(|token_id| self.tokens.get(&token_id))
+            .collect();
+        assert!(!tokens.is_empty(), "all tokens burned");
+        assert!(
+            tokens
+                .iter()
+                .all(|token| token.owner_id.to_string() == batch.owner_id.to_string()),
+            "tokens already transferred"
+        );
+        assert!(
+            tokens
+                .iter()
+                .all(|token| token.royalty_id == batch.royalty_id),
+            "batch has per-copy royalties"
+        );
+        let old_royalty = batch
+            .royalty_id
+            .map(|royalty_id| self.token_royalty.get(&royalty_id).unwrap());
+        if let Some((count, _)) = old_royalty.as_ref() {
+            assert_eq!(
+                *count as usize,
+                tokens.len(),
+                "royalty shared with other batches"
+            );
+        }
+
+        let roy_len = royalty_args
+            .as_ref()
+            .map(|royalty_args| royalty_args.split_between.len() as u32)
+            .unwrap_or(0);
+        let split_len = tokens
+            .iter()
+            .map(|token| {
+                token
+                    .split_owners
+                    .as_ref()
+                    .map(|split_owners| split_owners.split_between.len() as u32)
+                    .unwrap_or(1)
+            })
+            .max()
+            .unwrap();
+        assert!(roy_len + split_len <= MAX_LEN_PAYOUT);
+        let old_roy_len = old_royalty
+            .as_ref()
+            .map(|(_, royalty)| royalty.split_between.len() as u32)
+            .unwrap_or(0);
+        if roy_len > old_roy_len {
+            let covered_storage = env::account_balance()
+                - (env::storage_usage() as u128 * self.storage_costs().storage_price_per_byte);
+            let expected_storage_consumption: Balance =
+                (roy_len - old_roy_len) as u128 * self.storage_costs().common;
+            assert!(
+                covered_storage >= expected_storage_consumption,
+                "covered: {}; need: {}",
+                covered_storage,
+                expected_storage_consumption
+            );
+            log_storage_deposit(
+                StorageDepositAction::Consumed,
+                &minter_id,
+                expected_storage_consumption,
+                "update_token_royalty",
+            );
+        }
+
+        // the old royalty is exclusive to this batch, the new one is looked
+        // up by the first token id of the batch
+        if let Some(royalty_id) = batch.royalty_id {
+            self.token_royalty.remove(&royalty_id);
+        }
+        let royalty = royalty_args.map(Self::checked_royalty);
+        let royalty_id = royalty.as_ref().map(|royalty| {
+            self.token_royalty
+                .insert(&lookup_id, &(tokens.len() as u16, royalty.clone()));
+            lookup_id
+        });
+        if royalty_id != batch.royalty_id {
+            tokens.iter_mut().for_each(|token| {
+                token.royalty_id = royalty_id;
+                self.tokens.insert(&token.id, token);
+            });
+            batch.royalty_id = royalty_id;
+            self.mint_batches.insert(&mint_id, &batch);
+        }
+
+        let token_ids: Vec<u64> = tokens.iter().map(|token| token.id).collect();
+        log_update_token_royalty(&token_ids, &royalty);
+    }
+
+    /// Set the fee in yoctoNEAR that minters other than the store owner
+    /// must attach per minted token. The fee is forwarded to the store
+    /// owner.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_minting_fee(
+        &mut self,
+        minting_fee: U128,
+    ) {
+        self.assert_store_owner();
+        self.minting_fee = minting_fee.into();
+        log_set_minting_fee(minting_fee);
+    }
+
+    /// Set the royalty that is applied to tokens minted without
+    /// `royalty_args`, or remove it by passing `None`. Tokens that have
+    /// already been minted keep their royalty.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_default_royalty(
+        &mut self,
+        royalty_args: Option<RoyaltyArgs>,
+    ) {
+        self.assert_store_owner();
+        self.assert_royalty_within_cap(&royalty_args);
+        // validates the royalty
+        let royalty = royalty_args.clone().map(Self::checked_royalty);
+        self.default_royalty = royalty_args;
+        log_set_default_royalty(&royalty);
+    }
+
+    /// Cap the royalty percentage of newly minted tokens at
+    /// `max_royalty_bps` basis points, or lift the cap by passing `None`.
+    /// Tokens that have already been minted keep their royalty, but the
+    /// default royalty must be within the cap.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_max_royalty_bps(
+        &mut self,
+        max_royalty_bps: Option<u32>,
+    ) {
+        self.assert_store_owner();
+        self.max_royalty_bps = max_royalty_bps;
+        self.assert_royalty_within_cap(&self.default_royalty);
+    }
+
+    /// Enable or disable embedding the full metadata of minted tokens in
+    /// mint events. Metadata that would exceed the log limits is omitted,
+    /// as is the metadata of mints that emit an event per token or owner.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_mint_event_metadata(
+        &mut self,
+        enabled: bool,
+    ) {
+        self.assert_store_owner();
+        self.mint_event_metadata = enabled;
+    }
+
+    /// Require newly granted minters to wait `hours` before they may mint,
+    /// as a safeguard against a briefly compromised owner key being used to
+    /// grant minters that drain the storage deposit with junk mints. Zero
+    /// disables the cooling-off period. Existing minters are not affected.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_minter_cooling_off(
+        &mut self,
+        hours: u64,
+    ) {
+        self.assert_store_owner();
+        assert!(hours < 70_000, "maximum cooling-off is 70,000 hours");
+        self.minter_cooling_off_hours = hours;
+        log_set_minter_cooling_off(hours);
     }
 
     /// Modify the minting privileges of `account_id`. Minters are able to
-    /// mint tokens on this `Store`.
+    /// mint tokens on this `Store`, including royalties and split owners,
+    /// which lifts a `mint_only` role. If `max_mints` is set, the minter may
+    /// mint at most that many tokens, otherwise without limit. Granting an
+    /// existing minter replaces their cap, and lifts the expiry of their
+    /// grant.
     ///
     /// Only the store owner may call this function.
     ///
@@ -148,13 +610,74 @@ impl MintbaseStore {
     pub fn grant_minter(
         &mut self,
         account_id: AccountId,
+        max_mints: Option<u64>,
     ) {
         self.assert_store_owner();
         let account_id: AccountId = account_id;
+        match max_mints {
+            Some(max_mints) => {
+                assert_ne!(account_id, self.owner_id, "can't cap owner");
+                self.minter_allowances.insert(&account_id, &max_mints);
+            },
+            None => {
+                self.minter_allowances.remove(&account_id);
+            },
+        }
         // does nothing if account_id is already a minter
+        if self.grant_minter_internal(&account_id) {
+            log_grant_minter(&account_id);
+        }
+    }
+
+    /// Grant minting privileges to `account_id` until `expiry_timestamp`,
+    /// e.g. for the duration of a collaboration. Once expired, the account
+    /// may no longer mint, and the grant can be removed via
+    /// `sweep_expired_minters`. Granting an existing minter limits their
+    /// grant to `expiry_timestamp`.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn grant_minter_until(
+        &mut self,
+        account_id: AccountId,
+        expiry_timestamp: NearTime,
+    ) {
+        self.assert_store_owner();
+        assert_ne!(account_id, self.owner_id, "can't expire owner");
+        assert!(
+            expiry_timestamp.is_before_timeout(),
+            "expiry must be in the future"
+        );
         if self.minters.insert(&account_id) {
+            self.start_minter_cooling_off(&account_id);
             log_grant_minter(&account_id);
         }
+        log_grant_minter_until(&account_id, &expiry_timestamp);
+        self.minter_expiries.insert(&account_id, &expiry_timestamp);
+    }
+
+    /// Remove the grants of those `account_ids` that have expired. Accounts
+    /// that aren't minters or whose grants haven't expired are skipped.
+    ///
+    /// Anyone may call this function.
+    pub fn sweep_expired_minters(
+        &mut self,
+        account_ids: Vec<AccountId>,
+    ) {
+        for account_id in account_ids {
+            if !self.minter_grant_expired(&account_id) {
+                continue;
+            }
+            self.minters.remove(&account_id);
+            self.minter_expiries.remove(&account_id);
+            self.minter_mintable_from.remove(&account_id);
+            self.minter_allowances.remove(&account_id);
+            self.minter_mint_windows.remove(&account_id);
+            self.mint_delegates.remove(&account_id);
+            self.revoke_minting_roles(&account_id);
+            log_minter_grant_lapsed(&account_id);
+            log_revoke_minter(&account_id);
+        }
     }
 
     /// Modify the minting privileges of `account_id`. Minters are able to
@@ -168,22 +691,111 @@ impl MintbaseStore {
         account_id: AccountId,
     ) {
         self.assert_store_owner();
-        assert_ne!(account_id, self.owner_id, "can't revoke owner");
-        if !self.minters.remove(&account_id) {
+        self.revoke_minter_internal(&account_id);
+        log_revoke_minter(&account_id);
+    }
+
+    /// Grant minting privileges to all accounts in `grant` and revoke them
+    /// from all accounts in `revoke` at once, emitting a single event. Grants
+    /// behave like `grant_minter` without `max_mints`, and revocations like
+    /// `revoke_minter`. Accounts in `grant` that already are minters are
+    /// left out of the event.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn batch_change_minters(
+        &mut self,
+        grant: Vec<AccountId>,
+        revoke: Vec<AccountId>,
+    ) {
+        self.assert_store_owner();
+        assert!(
+            grant.iter().all(|account_id| !revoke.contains(account_id)),
+            "can't grant and revoke the same account"
+        );
+        let mut granted = vec![];
+        for account_id in grant {
+            self.minter_allowances.remove(&account_id);
+            if self.grant_minter_internal(&account_id) {
+                granted.push(account_id);
+            }
+        }
+        revoke
+            .iter()
+            .for_each(|account_id| self.revoke_minter_internal(account_id));
+        log_change_minters(&granted, &revoke);
+    }
+
+    /// Atomically revoke the minting privileges of `old_account` and grant
+    /// them to `new_account`, e.g. to replace a compromised key. Any
+    /// configuration tied to `old_account` as a minter is moved over to
+    /// `new_account`.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn rotate_minter(
+        &mut self,
+        old_account: AccountId,
+        new_account: AccountId,
+    ) {
+        self.assert_store_owner();
+        assert_ne!(old_account, self.owner_id, "can't revoke owner");
+        assert!(
+            !self.minters.contains(&new_account),
+            "{} already a minter",
+            new_account
+        );
+        if !self.minters.remove(&old_account) {
             env::panic_str("not a minter")
-        } else {
-            log_revoke_minter(&account_id);
         }
+        self.minters.insert(&new_account);
+        self.minter_mintable_from.remove(&old_account);
+        self.start_minter_cooling_off(&new_account);
+        if let Some(allowance) = self.minter_allowances.remove(&old_account) {
+            self.minter_allowances.insert(&new_account, &allowance);
+        }
+        if let Some(expiry) = self.minter_expiries.remove(&old_account) {
+            self.minter_expiries.insert(&new_account, &expiry);
+        }
+        if let Some(window) = self.minter_mint_windows.remove(&old_account) {
+            self.minter_mint_windows.insert(&new_account, &window);
+        }
+        if let Some(delegates) = self.mint_delegates.remove(&old_account) {
+            self.mint_delegates.insert(&new_account, &delegates);
+        }
+        let (minting, other): (Vec<_>, Vec<_>) = self
+            .roles
+            .get(&old_account)
+            .unwrap_or_default()
+            .into_iter()
+            .partition(StoreRole::is_minting);
+        if !minting.is_empty() {
+            self.set_roles(&old_account, other);
+            let mut roles = self.roles.get(&new_account).unwrap_or_default();
+            roles.extend(minting);
+            self.roles.insert(&new_account, &roles);
+        }
+        log_revoke_minter(&old_account);
+        log_grant_minter(&new_account);
     }
 
     // -------------------------- view methods -----------------------------
 
-    /// Check if `account_id` is a minter.
+    /// Check if `account_id` is a minter whose grant hasn't expired.
     pub fn check_is_minter(
         &self,
         account_id: AccountId,
     ) -> bool {
-        self.minters.contains(&account_id)
+        self.minters.contains(&account_id) && !self.minter_grant_expired(&account_id)
+    }
+
+    /// Get the time at which the grant of `account_id` expires, or `None`
+    /// if the grant doesn't expire.
+    pub fn get_minter_expiry(
+        &self,
+        account_id: AccountId,
+    ) -> Option<NearTime> {
+        self.minter_expiries.get(&account_id)
     }
 
     /// Lists all account IDs that are currently allowed to mint on this
@@ -192,26 +804,485 @@ impl MintbaseStore {
         self.minters.iter().collect()
     }
 
+    /// Get the royalty that is applied to tokens minted without
+    /// `royalty_args`, if any.
+    pub fn get_default_royalty(&self) -> Option<RoyaltyArgs> {
+        self.default_royalty.clone()
+    }
+
+    /// Get the maximum royalty percentage in basis points with which tokens
+    /// may be minted on this `Store`, if capped.
+    pub fn get_max_royalty_bps(&self) -> Option<u32> {
+        self.max_royalty_bps
+    }
+
+    /// Check whether mint events embed the full metadata of minted tokens.
+    pub fn get_mint_event_metadata(&self) -> bool {
+        self.mint_event_metadata
+    }
+
+    /// Get the fee in yoctoNEAR per minted token.
+    pub fn get_minting_fee(&self) -> U128 {
+        self.minting_fee.into()
+    }
+
+    /// Get the restrictions under which `account_id` may mint: the number of
+    /// tokens it may still mint, the end of its cooling-off period and the
+    /// expiry of its grant.
+    pub fn get_minter_quota(
+        &self,
+        account_id: AccountId,
+    ) -> MinterQuota {
+        MinterQuota {
+            remaining: self.minter_allowances.get(&account_id),
+            mintable_from: self.minter_mintable_from.get(&account_id),
+            expires_at: self.minter_expiries.get(&account_id),
+        }
+    }
+
+    /// Get the number of tokens that `account_id` may still mint, or `None`
+    /// if the minter is not capped.
+    pub fn get_minter_allowance(
+        &self,
+        account_id: AccountId,
+    ) -> Option<u64> {
+        self.minter_allowances.get(&account_id)
+    }
+
+    /// Get the record of a `nft_batch_mint` call by its `mint_id`, which is
+    /// included in the memo of the mint event.
+    pub fn get_mint_batch(
+        &self,
+        mint_id: String,
+    ) -> Option<MintBatch> {
+        self.mint_batches.get(&mint_id)
+    }
+
+    /// Estimate the storage costs of minting `num_to_mint` copies of a
+    /// token with `metadata`, `roy_len` royalty receivers and `split_len`
+    /// split owners via `nft_batch_mint`. Minting consumes the unused
+    /// balance of the `Store`, which must cover at least this amount. The
+    /// deposit that minters must attach is given by `get_minting_fee`.
+    ///
+    /// A `roy_len` of zero stands for minting without `royalty_args`, in
+    /// which case the default royalty of the `Store` applies, if any.
+    pub fn estimate_mint_cost(
+        &self,
+        metadata: TokenMetadata,
+        num_to_mint: u64,
+        roy_len: u32,
+        split_len: u32,
+    ) -> U128 {
+        let (_, md_size) = TokenMetadata::from_with_size(metadata, num_to_mint);
+        let roy_len = match (roy_len, &self.default_royalty) {
+            (0, Some(default_royalty)) => default_royalty.split_between.len() as u32,
+            _ => roy_len,
+        };
+        self.storage_cost_to_mint(1, num_to_mint, md_size, roy_len, split_len.max(1))
+            .into()
+    }
+
     // -------------------------- private methods --------------------------
     // -------------------------- internal methods -------------------------
 
+    /// Validate `royalty_args`, panicking with the reason if they are
+    /// invalid.
+    pub(crate) fn checked_royalty(royalty_args: RoyaltyArgs) -> Royalty {
+        Royalty::try_new(royalty_args)
+            .unwrap_or_else(|err| env::panic_str(&format!("invalid royalty: {}", err)))
+    }
+
+    /// Validate `split_between`, panicking with the reason if it is invalid.
+    pub(crate) fn checked_split_owners(split_between: SplitBetweenUnparsed) -> SplitOwners {
+        SplitOwners::try_new(split_between)
+            .unwrap_or_else(|err| env::panic_str(&format!("invalid split owners: {}", err)))
+    }
+
+    /// Panics unless `minter_id` may mint right now: they must be a minter
+    /// or on the mint allowlist, not banned from minting, and within their
+    /// mint window. Counts `num_tokens` against the rate limit of
+    /// `minter_id`. Called by every mint on behalf of a minter, including
+    /// each chunk of a mint session.
+    pub(crate) fn admit_mint(
+        &mut self,
+        minter_id: &AccountId,
+        num_tokens: u64,
+    ) {
+        self.assert_can_mint(minter_id);
+        self.assert_not_banned_from_minting(minter_id);
+        self.assert_mint_window_open(minter_id);
+        self.record_activity_of(minter_id.clone(), Activity::Mint, num_tokens as u32);
+    }
+
+    /// Check that `minter_id` may set the royalty or split owners, if
+    /// `sets_payout`, and that `royalty_args` are within the royalty cap,
+    /// then use `num_to_mint` of the minter's allowance. Shared by all mints
+    /// on behalf of a minter, after `admit_mint`. Returns the royalty of the
+    /// minted tokens, which defaults to the `Store` default royalty, and the
+    /// minting fee that is due.
+    pub(crate) fn prepare_mint(
+        &mut self,
+        minter_id: &AccountId,
+        num_to_mint: u64,
+        royalty_args: Option<RoyaltyArgs>,
+        sets_payout: bool,
+    ) -> (Option<RoyaltyArgs>, Balance) {
+        self.assert_can_set_royalty(minter_id, sets_payout);
+        self.assert_royalty_within_cap(&royalty_args);
+        let royalty_args = royalty_args.or_else(|| self.default_royalty.clone());
+        self.use_minter_allowance(minter_id, num_to_mint);
+        (royalty_args, self.minting_fee_for(minter_id, num_to_mint))
+    }
+
+    /// Panics if `expires_at` has already passed.
+    pub(crate) fn assert_expiry_in_future(expires_at: &Option<NearTime>) {
+        if let Some(expires_at) = expires_at {
+            assert!(
+                expires_at.is_before_timeout(),
+                "expiry must be in the future"
+            );
+        }
+    }
+
+    /// Mint `num_to_mint` copies of a token on behalf of `minter_id`, after
+    /// the caller has checked that `minter_id` may mint them, and reserved
+    /// the token ids starting at `first_token_id`. Shared by
+    /// `nft_batch_mint`, `nft_mint_signed` and `nft_mint_reserved`, `method`
+    /// names the caller in the storage deposit event. Returns the first and
+    /// last minted token id.
+    ///
+    /// Copies in `royalty_overrides` get a royalty of their own, stored under
+    /// their token id. The royalty shared by the other copies is stored under
+    /// the id of the first of them.
+    pub(crate) fn mint_batch_internal(
+        &mut self,
+        minter_id: AccountId,
+        owner_id: AccountId,
+        first_token_id: u64,
+        metadata: TokenMetadata,
+        num_to_mint: u64,
+        royalty_args: Option<RoyaltyArgs>,
+        royalty_overrides: BTreeMap<U64, RoyaltyArgs>,
+        split_owners: Option<SplitBetweenUnparsed>,
+        transferable: Option<bool>,
+        expires_at: Option<NearTime>,
+        minting_fee: Balance,
+        method: &str,
+    ) -> (U64, U64) {
+        Self::assert_expiry_in_future(&expires_at);
+        // Calculating storage consuption upfront saves gas if the transaction
+        // were to fail later.
+        let covered_storage = env::account_balance()
+            - (env::storage_usage() as u128 * self.storage_costs().storage_price_per_byte);
+        let (metadata, md_size) = TokenMetadata::from_with_size(metadata, num_to_mint);
+        let roy_len = royalty_args
+            .as_ref()
+            .map(|pre_roy| {
+                let len = pre_roy.split_between.len();
+                len as u32
+            })
+            .unwrap_or(0);
+        let split_len = split_owners
+            .as_ref()
+            .map(|pre_split| {
+                let len = pre_split.len();
+                len as u32
+            })
+            // if there is no split map, there still is an owner, thus default to 1
+            .unwrap_or(1);
+        assert!(roy_len + split_len <= MAX_LEN_PAYOUT);
+        let override_roy_len = royalty_overrides
+            .iter()
+            .map(|(index, royalty_args)| {
+                assert!(index.0 < num_to_mint, "copy index out of range");
+                let len = royalty_args.split_between.len() as u32;
+                assert!(len + split_len <= MAX_LEN_PAYOUT);
+                len
+            })
+            .sum::<u32>();
+        let expected_storage_consumption: Balance = self.storage_cost_to_mint(
+            1,
+            num_to_mint,
+            md_size,
+            roy_len + override_roy_len,
+            split_len,
+        );
+        assert!(
+            covered_storage >= expected_storage_consumption,
+            "covered: {}; need: {}",
+            covered_storage,
+            expected_storage_consumption
+        );
+
+        let checked_royalty = royalty_args.map(Self::checked_royalty);
+        let checked_overrides: BTreeMap<u64, Royalty> = royalty_overrides
+            .into_iter()
+            .map(|(index, royalty_args)| {
+                (
+                    first_token_id + index.0,
+                    Self::checked_royalty(royalty_args),
+                )
+            })
+            .collect();
+        let checked_split = split_owners.map(Self::checked_split_owners);
+
+        let mut owned_set = self.get_or_make_new_owner_set(&owner_id);
+
+        // Lookup Id is used by the token to lookup Royalty and Metadata fields on
+        // the contract (to avoid unnecessary duplication)
+        let lookup_id: u64 = first_token_id;
+        let last_token_id = first_token_id + num_to_mint - 1;
+        let shared_royalty_id = (first_token_id..=last_token_id)
+            .find(|token_id| !checked_overrides.contains_key(token_id));
+        let royalty_id =
+            checked_royalty
+                .clone()
+                .zip(shared_royalty_id)
+                .map(|(royalty, royalty_id)| {
+                    let num_shared = num_to_mint - checked_overrides.len() as u64;
+                    self.token_royalty
+                        .insert(&royalty_id, &(num_shared as u16, royalty));
+                    royalty_id
+                });
+        checked_overrides.iter().for_each(|(token_id, royalty)| {
+            self.token_royalty.insert(token_id, &(1, royalty.clone()));
+        });
+
+        let meta_ref = metadata.reference.as_ref().map(|s| s.to_string());
+        let meta_extra = metadata.extra.as_ref().map(|s| s.to_string());
+        let media_hints = metadata.media_hints();
+        let event_metadata = self.mint_event_metadata.then(|| metadata.clone());
+        self.token_metadata
+            .insert(&lookup_id, &(num_to_mint as u16, metadata));
+
+        // Mint em up hot n fresh with a side of vegan bacon
+        (0..num_to_mint).for_each(|i| {
+            let token_id = first_token_id + i;
+            let royalty_id = match checked_overrides.contains_key(&token_id) {
+                true => Some(token_id),
+                false => royalty_id,
+            };
+            let mut token = Token::new(
+                owner_id.clone(),
+                token_id,
+                lookup_id,
+                royalty_id,
+                checked_split.clone(),
+                minter_id.clone(),
+            );
+            token.soulbound = !transferable.unwrap_or(true);
+            token.expires_at = expires_at.clone();
+            owned_set.insert(&token_id);
+            self.tokens.insert(&token_id, &token);
+        });
+        self.tokens_per_owner.insert(&owner_id, &owned_set);
+
+        // `lookup_id` is unique per batch, and thus serves as nonce
+        let mint_id = MintBatch::generate_id(&minter_id, lookup_id);
+        self.insert_mint_batch(MintBatch {
+            mint_id: mint_id.clone(),
+            minter: minter_id.clone(),
+            owner_id: owner_id.clone(),
+            first_token_id,
+            last_token_id,
+            metadata_id: lookup_id,
+            royalty_id,
+            block_height: env::block_height(),
+        });
+
+        log_nft_batch_mint(
+            first_token_id,
+            last_token_id,
+            minter_id.as_ref(),
+            owner_id.as_ref(),
+            &checked_royalty,
+            &checked_split,
+            &meta_ref,
+            &meta_extra,
+            &mint_id,
+            &media_hints,
+            event_metadata.as_ref(),
+        );
+        checked_overrides
+            .into_iter()
+            .for_each(|(token_id, royalty)| {
+                log_update_token_royalty(&[token_id], &Some(royalty));
+            });
+        log_storage_deposit(
+            StorageDepositAction::Consumed,
+            &minter_id,
+            expected_storage_consumption,
+            method,
+        );
+        self.pay_minting_fee(minting_fee);
+        (U64(first_token_id), U64(last_token_id))
+    }
+
+    /// Panics if `royalty_args` exceed the royalty cap of this `Store`.
+    pub(crate) fn assert_royalty_within_cap(
+        &self,
+        royalty_args: &Option<RoyaltyArgs>,
+    ) {
+        if let (Some(max_royalty_bps), Some(royalty_args)) = (self.max_royalty_bps, royalty_args) {
+            assert!(
+                royalty_args.percentage <= max_royalty_bps,
+                "royalty exceeds cap of {} bps",
+                max_royalty_bps
+            );
+        }
+    }
+
+    /// Record `batch`, such that it can be looked up by its `mint_id` and by
+    /// its first token id.
+    pub(crate) fn insert_mint_batch(
+        &mut self,
+        batch: MintBatch,
+    ) {
+        self.mint_batch_ids
+            .insert(&batch.first_token_id, &batch.mint_id);
+        self.mint_batches.insert(&batch.mint_id, &batch);
+    }
+
+    /// Add `account_id` to the minters without an expiry, returning false if
+    /// it already was a minter.
+    fn grant_minter_internal(
+        &mut self,
+        account_id: &AccountId,
+    ) -> bool {
+        let inserted = self.minters.insert(account_id);
+        if inserted {
+            self.start_minter_cooling_off(account_id);
+        }
+        self.minter_expiries.remove(account_id);
+        self.revoke_minting_roles(account_id);
+        inserted
+    }
+
+    /// Remove `account_id` from the minters, along with any configuration
+    /// tied to it as a minter.
+    fn revoke_minter_internal(
+        &mut self,
+        account_id: &AccountId,
+    ) {
+        assert_ne!(account_id, &self.owner_id, "can't revoke owner");
+        if !self.minters.remove(account_id) {
+            env::panic_str("not a minter")
+        }
+        self.minter_allowances.remove(account_id);
+        self.minter_expiries.remove(account_id);
+        self.minter_mintable_from.remove(account_id);
+        self.minter_mint_windows.remove(account_id);
+        self.mint_delegates.remove(account_id);
+        self.revoke_minting_roles(account_id);
+    }
+
+    /// Start the cooling-off period of a newly granted minter, if the store
+    /// requires one.
+    pub(crate) fn start_minter_cooling_off(
+        &mut self,
+        account_id: &AccountId,
+    ) {
+        if self.minter_cooling_off_hours == 0 || *account_id == self.owner_id {
+            self.minter_mintable_from.remove(account_id);
+        } else {
+            let mintable_from = NearTime::new(TimeUnit::Hours(self.minter_cooling_off_hours));
+            self.minter_mintable_from.insert(account_id, &mintable_from);
+        }
+    }
+
+    /// Whether the grant of `account_id` has an expiry that has passed.
+    pub(crate) fn minter_grant_expired(
+        &self,
+        account_id: &AccountId,
+    ) -> bool {
+        self.minter_expiries
+            .get(account_id)
+            .map(|expiry| !expiry.is_before_timeout())
+            .unwrap_or(false)
+    }
+
+    /// Panics if `minter_id` is still cooling off or its grant has expired.
+    pub(crate) fn assert_minter_grant_active(
+        &self,
+        minter_id: &AccountId,
+    ) {
+        if let Some(mintable_from) = self.minter_mintable_from.get(minter_id) {
+            assert!(
+                !mintable_from.is_before_timeout(),
+                "{} may not mint before {}",
+                minter_id,
+                mintable_from.0
+            );
+        }
+        assert!(
+            !self.minter_grant_expired(minter_id),
+            "{} minter grant expired",
+            minter_id
+        );
+    }
+
+    /// The minting fee that `minter_id` has to pay for `num_tokens`. The
+    /// store owner does not pay minting fees.
+    pub(crate) fn minting_fee_for(
+        &self,
+        minter_id: &AccountId,
+        num_tokens: u64,
+    ) -> Balance {
+        if *minter_id == self.owner_id {
+            0
+        } else {
+            self.minting_fee * num_tokens as u128
+        }
+    }
+
+    /// Forward a collected minting fee to the store owner.
+    pub(crate) fn pay_minting_fee(
+        &self,
+        minting_fee: Balance,
+    ) {
+        if minting_fee > 0 {
+            Promise::new(self.owner_id.clone()).transfer(minting_fee);
+        }
+    }
+
+    /// Deduct `num_tokens` from the allowance of a capped minter, panicking
+    /// if the allowance is insufficient.
+    pub(crate) fn use_minter_allowance(
+        &mut self,
+        minter_id: &AccountId,
+        num_tokens: u64,
+    ) {
+        if let Some(allowance) = self.minter_allowances.get(minter_id) {
+            assert!(
+                allowance >= num_tokens,
+                "minter allowance exhausted: {} remaining",
+                allowance
+            );
+            self.minter_allowances
+                .insert(minter_id, &(allowance - num_tokens));
+        }
+    }
+
     /// Get the storage in bytes to mint `num_tokens` each with
-    /// `metadata_storage` and `len_map` royalty receivers.
+    /// `metadata_storage` and `len_map` royalty receivers, distributed among
+    /// `num_owners` owners.
     /// Internal
-    fn storage_cost_to_mint(
+    pub(crate) fn storage_cost_to_mint(
         &self,
+        num_owners: u64,
         num_tokens: u64,
         metadata_storage: StorageUsage,
         num_royalties: u32,
         num_splits: u32,
     ) -> near_sdk::Balance {
-        // create an entry in tokens_per_owner
-        self.storage_costs.common
+        let storage_costs = self.storage_costs();
+        // create an entry in tokens_per_owner and a mint batch record per owner
+        num_owners as u128 * (storage_costs.common + storage_costs.mint_batch)
             // create a metadata record
-            + metadata_storage as u128 * self.storage_costs.storage_price_per_byte
+            + metadata_storage as u128 * storage_costs.storage_price_per_byte
             // create a royalty record
-            + num_royalties as u128 * self.storage_costs.common
+            + num_royalties as u128 * storage_costs.common
             // create n tokens each with splits stored on-token
-            + num_tokens as u128 * (self.storage_costs.token + num_splits as u128 * self.storage_costs.common)
+            + num_tokens as u128 * (storage_costs.token + num_splits as u128 * storage_costs.common)
     }
 }
@@ -0,0 +1,80 @@
+use mintbase_deps::common::MintBan;
+use mintbase_deps::logging::{
+    log_ban_from_minting,
+    log_unban_from_minting,
+};
+use mintbase_deps::near_sdk::json_types::Base64VecU8;
+use mintbase_deps::near_sdk::{
+    self,
+    near_bindgen,
+    AccountId,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Ban `account_id` from minting on this `Store`, e.g. an abusive mint
+    /// bot. Tokens the account already holds are not affected.
+    /// `reason_hash` is the hash of an off-chain document stating the
+    /// reason for the ban.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn ban_from_minting(
+        &mut self,
+        account_id: AccountId,
+        reason_hash: Base64VecU8,
+    ) {
+        self.assert_store_owner();
+        assert_ne!(account_id, self.owner_id, "can't ban owner");
+        if let Some(ban) = self.mint_bans.get(&account_id) {
+            assert!(!ban.is_active(), "{} already banned", account_id);
+        }
+        self.mint_bans
+            .insert(&account_id, &MintBan::new(reason_hash));
+        log_ban_from_minting(&account_id);
+    }
+
+    /// Lift the minting ban of `account_id`. The ban record is kept with
+    /// the time at which the ban was lifted.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn unban_from_minting(
+        &mut self,
+        account_id: AccountId,
+    ) {
+        self.assert_store_owner();
+        let mut ban = self.mint_bans.get(&account_id).expect("not banned");
+        ban.lift();
+        self.mint_bans.insert(&account_id, &ban);
+        log_unban_from_minting(&account_id);
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the most recent minting ban of `account_id`, whether active or
+    /// lifted.
+    pub fn get_mint_ban(
+        &self,
+        account_id: AccountId,
+    ) -> Option<MintBan> {
+        self.mint_bans.get(&account_id)
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+
+    /// Panics if `account_id` is banned from minting.
+    pub(crate) fn assert_not_banned_from_minting(
+        &self,
+        account_id: &AccountId,
+    ) {
+        if let Some(ban) = self.mint_bans.get(account_id) {
+            assert!(!ban.is_active(), "{} banned from minting", account_id);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use mintbase_deps::common::StorageDeposit;
+use mintbase_deps::logging::{
+    log_approve_operator,
+    log_revoke_operator,
+    log_storage_deposit,
+    StorageDepositAction,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    AccountId,
+    Promise,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Allow `operator_id` to transfer all current and future tokens of the
+    /// caller, e.g. a marketplace or custodial service, without approving
+    /// each token individually. Operators need not pass an `approval_id`.
+    ///
+    /// The attached deposit must cover the storage of the operator, and the
+    /// excess is refunded.
+    #[payable]
+    pub fn approve_operator(
+        &mut self,
+        operator_id: AccountId,
+    ) {
+        let storage_cost = self.storage_costs().common;
+        let attached_deposit = env::attached_deposit();
+        assert!(attached_deposit > storage_cost);
+        let owner_id = env::predecessor_account_id();
+        assert_ne!(owner_id, operator_id, "can't approve self as operator");
+        let mut operators = self.operators.get(&owner_id).unwrap_or_default();
+        let storage_cost = if operators.iter().any(|(other, _)| *other == operator_id) {
+            0
+        } else {
+            operators.push((operator_id.clone(), storage_cost));
+            self.operators.insert(&owner_id, &operators);
+            log_approve_operator(&owner_id, &operator_id);
+            log_storage_deposit(
+                StorageDepositAction::Taken,
+                &owner_id,
+                storage_cost,
+                "approve_operator",
+            );
+            storage_cost
+        };
+        let refund = attached_deposit - storage_cost;
+        log_storage_deposit(
+            StorageDepositAction::Refunded,
+            &owner_id,
+            refund,
+            "approve_operator",
+        );
+        Promise::new(owner_id).transfer(refund);
+    }
+
+    /// Revoke the permission of `operator_id` to transfer the tokens of the
+    /// caller, refunding the deposit paid for it. Per-token approvals of
+    /// `operator_id` are not affected.
+    #[payable]
+    pub fn revoke_operator(
+        &mut self,
+        operator_id: AccountId,
+    ) {
+        near_sdk::assert_one_yocto();
+        let owner_id = env::predecessor_account_id();
+        let mut operators = self.operators.get(&owner_id).unwrap_or_default();
+        let index = operators
+            .iter()
+            .position(|(other, _)| *other == operator_id)
+            .unwrap_or_else(|| env::panic_str(&format!("{} not an operator", operator_id)));
+        let (_, deposit) = operators.remove(index);
+        if operators.is_empty() {
+            self.operators.remove(&owner_id);
+        } else {
+            self.operators.insert(&owner_id, &operators);
+        }
+        log_revoke_operator(&owner_id, &operator_id);
+        self.refund_storage_deposits(
+            vec![StorageDeposit::new(owner_id, deposit)],
+            "revoke_operator",
+        );
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// List the operators of `owner_id`.
+    pub fn get_operators(
+        &self,
+        owner_id: AccountId,
+    ) -> Vec<AccountId> {
+        self.operators
+            .get(&owner_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(operator_id, _)| operator_id)
+            .collect()
+    }
+
+    /// Whether `operator_id` may transfer all tokens of `owner_id`.
+    pub fn is_operator(
+        &self,
+        owner_id: AccountId,
+        operator_id: AccountId,
+    ) -> bool {
+        self.is_operator_internal(owner_id.as_str(), &operator_id)
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+
+    pub(crate) fn is_operator_internal(
+        &self,
+        owner_id: &str,
+        operator_id: &AccountId,
+    ) -> bool {
+        AccountId::try_from(owner_id.to_string())
+            .ok()
+            .and_then(|owner_id| self.operators.get(&owner_id))
+            .map(|operators| operators.iter().any(|(other, _)| other == operator_id))
+            .unwrap_or(false)
+    }
+}
@@ -1,12 +1,19 @@
+use std::collections::BTreeMap;
+
+use mintbase_deps::common::StorageDeposit;
 use mintbase_deps::logging::{
     log_grant_minter,
     log_revoke_minter,
+    log_storage_deposit,
     log_transfer_store,
+    StorageDepositAction,
 };
 use mintbase_deps::near_sdk::{
     self,
     near_bindgen,
     AccountId,
+    Balance,
+    Promise,
 };
 
 use crate::*;
@@ -16,7 +23,7 @@ impl MintbaseStore {
     // -------------------------- change methods ---------------------------
     /// Transfer ownership of `Store` to a new owner. Setting
     /// `keep_old_minters=true` allows all existing minters (including the
-    /// prior owner) to keep their minter status.
+    /// prior owner) to keep their minter status and roles.
     ///
     /// Only the store owner may call this function.
     #[payable]
@@ -33,10 +40,14 @@ impl MintbaseStore {
                 log_revoke_minter(&minter);
             }
             self.minters.clear();
+            self.roles.clear();
+            self.minter_expiries.clear();
         }
         log_grant_minter(&new_owner);
         // add the new_owner to the minter set (insert does nothing if they already are a minter).
         self.minters.insert(&new_owner);
+        self.minter_expiries.remove(&new_owner);
+        self.minter_mintable_from.remove(&new_owner);
         log_transfer_store(&new_owner);
         self.owner_id = new_owner;
     }
@@ -51,8 +62,14 @@ impl MintbaseStore {
     pub fn withdraw_excess_storage_deposits(&mut self) {
         self.assert_store_owner();
         let unused_deposit: u128 = env::account_balance()
-            - env::storage_usage() as u128 * self.storage_costs.storage_price_per_byte;
+            - env::storage_usage() as u128 * self.storage_costs().storage_price_per_byte;
         if unused_deposit > storage_stake::CUSHION {
+            log_storage_deposit(
+                StorageDepositAction::Refunded,
+                &self.owner_id,
+                unused_deposit - storage_stake::CUSHION,
+                "withdraw_excess_storage_deposits",
+            );
             near_sdk::Promise::new(self.owner_id.clone())
                 .transfer(unused_deposit - storage_stake::CUSHION);
         } else {
@@ -64,25 +81,61 @@ impl MintbaseStore {
         }
     }
 
-    /// The Near Storage price per byte has changed in the past, and may
-    /// change in the future. This method may never be used.
+    /// Storage costs are computed from the price per byte of the protocol,
+    /// which has changed in the past, and may change in the future. Setting
+    /// `new_price` overrides that price, unsetting it reverts to the
+    /// protocol price. This method may never be used.
     ///
     /// Only the store owner may call this function.
     #[payable]
     pub fn set_storage_price_per_byte(
         &mut self,
-        new_price: U128,
+        new_price: Option<U128>,
     ) {
         self.assert_store_owner();
-        self.storage_costs = StorageCosts::new(new_price.into())
+        self.storage_price_override = new_price.map(|price| price.into());
     }
 
     // -------------------------- view methods -----------------------------
     // TODO: get_owner
-    // TODO: get_storage_price_per_byte
+
+    /// The price per byte of storage from which storage costs are computed.
+    pub fn get_storage_price_per_byte(&self) -> U128 {
+        self.storage_costs().storage_price_per_byte.into()
+    }
+
     // -------------------------- private methods --------------------------
     // -------------------------- internal methods -------------------------
 
+    /// Storage costs at the overridden price per byte if set, otherwise at
+    /// the price per byte of the protocol.
+    pub(crate) fn storage_costs(&self) -> StorageCosts {
+        StorageCosts::new(
+            self.storage_price_override
+                .unwrap_or_else(env::storage_byte_cost),
+        )
+    }
+
+    /// Refund `deposits` to the accounts that paid them, with a single
+    /// transfer per account.
+    pub(crate) fn refund_storage_deposits(
+        &self,
+        deposits: Vec<StorageDeposit>,
+        method: &str,
+    ) {
+        let mut refunds: BTreeMap<AccountId, Balance> = BTreeMap::new();
+        for deposit in deposits {
+            *refunds.entry(deposit.account_id).or_default() += deposit.amount.0;
+        }
+        for (account_id, refund) in refunds {
+            if refund == 0 {
+                continue;
+            }
+            log_storage_deposit(StorageDepositAction::Refunded, &account_id, refund, method);
+            Promise::new(account_id).transfer(refund);
+        }
+    }
+
     /// Validate the caller of this method matches the owner of this `Store`.
     pub(crate) fn assert_store_owner(&self) {
         assert_one_yocto();
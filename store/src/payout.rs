@@ -1,13 +1,41 @@
+// the code generated for `nft_transfer_payout` takes all of its arguments
+#![allow(clippy::too_many_arguments)]
+
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
 use mintbase_deps::common::{
-    NewSplitOwner,
+    FeeHookConfig,
     OwnershipFractions,
     Payout,
     Royalty,
+    RoyaltyArgs,
+    SplitBetweenArgs,
     SplitBetweenUnparsed,
     SplitOwners,
+    StorageDeposit,
+    UncheckedRoyaltyArgs,
+    UncheckedSplitBetween,
+    ValidationReport,
+    VestingSchedule,
+};
+use mintbase_deps::constants::{
+    gas,
+    MAX_LEN_PAYOUT,
+    NO_DEPOSIT,
+    ROYALTY_UPPER_LIMIT,
+};
+use mintbase_deps::interfaces::ext_fee_hook;
+use mintbase_deps::logging::{
+    log_clear_split_owners,
+    log_nft_payout,
+    log_set_payout_override,
+    log_set_split_owners,
+    log_storage_deposit,
+    StorageDepositAction,
 };
-use mintbase_deps::constants::MAX_LEN_PAYOUT;
-use mintbase_deps::logging::log_set_split_owners;
 use mintbase_deps::near_sdk::json_types::{
     U128,
     U64,
@@ -18,8 +46,10 @@ use mintbase_deps::near_sdk::{
     env,
     near_bindgen,
     AccountId,
+    PromiseOrValue,
+    PromiseResult,
 };
-use mintbase_deps::token::Owner;
+use mintbase_deps::serde_json;
 
 use crate::*;
 
@@ -27,6 +57,15 @@ use crate::*;
 #[near_bindgen]
 impl MintbaseStore {
     // -------------------------- change methods ---------------------------
+
+    /// Sales are exempt from the transfer fee, see `set_transfer_fee`. A
+    /// composed token that its holder approved for sale is uncomposed, and
+    /// the owner's share goes to the holder.
+    ///
+    /// If `truncate` is set, payouts longer than `max_len_payout` are
+    /// truncated as described for `nft_payout` instead of panicking. The
+    /// `memo`, e.g. the id of the sale on a marketplace, is included in the
+    /// `nft_transfer` and `nft_payout` events.
     #[payable]
     pub fn nft_transfer_payout(
         &mut self,
@@ -35,31 +74,79 @@ impl MintbaseStore {
         approval_id: u64,
         balance: near_sdk::json_types::U128,
         max_len_payout: u32,
-    ) -> Payout {
+        truncate: Option<bool>,
+        memo: Option<String>,
+    ) -> PromiseOrValue<Payout> {
         assert_one_yocto();
-        let payout = self.nft_payout(token_id, balance, max_len_payout);
-        self.nft_transfer(receiver_id, token_id, Some(approval_id), None);
-        payout
+        // the payout is owed only if the transfer completes in this call
+        self.assert_transfer_policy(&receiver_id);
+        let payout = self.nft_payout(token_id, balance, max_len_payout, truncate);
+        // a composed token is sold by its holder, and leaves its parent
+        self.uncompose_to_holder(token_id.0);
+        self.nft_transfer_internal(
+            receiver_id,
+            token_id,
+            Some(approval_id),
+            memo.clone(),
+            None,
+            "nft_transfer_payout",
+        );
+        match self.fee_hook {
+            Some(ref fee_hook) => ext_fee_hook::nft_fee_legs(
+                token_id,
+                balance,
+                payout.payout.clone(),
+                fee_hook.hook_id.clone(),
+                NO_DEPOSIT,
+                gas::NFT_FEE_LEGS,
+            )
+            .then(store_self::on_fee_legs(
+                token_id,
+                payout,
+                balance,
+                max_len_payout,
+                memo,
+                env::current_account_id(),
+                NO_DEPOSIT,
+                gas::ON_FEE_LEGS,
+            ))
+            .into(),
+            None => {
+                log_nft_payout(token_id.into(), balance, &payout, &memo);
+                PromiseOrValue::Value(payout)
+            },
+        }
     }
 
     // -------------------------- view methods -----------------------------
+
+    /// If `truncate` is set, payouts longer than `max_len_payout` keep only
+    /// the largest shares, and the rest is folded into the owner's cut.
+    /// Otherwise, this method panics on payouts that are too long. Tokens
+    /// composed into tokens on another contract have no holder on this
+    /// contract, thus cannot be approved or paid out.
     pub fn nft_payout(
         &self,
         token_id: U64,
         balance: U128,
         max_len_payout: u32,
+        truncate: Option<bool>,
     ) -> Payout {
-        let token = self.nft_token(token_id).expect("no token");
-        match token.owner_id {
-            Owner::Account(_) => {},
-            _ => env::panic_str("token is composed"),
+        let (owner_id, fractions) = self.ownership_fractions(token_id);
+        let mut payout = fractions.into_payout(balance.into());
+        payout.fold_dust(self.payout_dust_threshold, &owner_id);
+        if truncate.unwrap_or(false) {
+            payout.truncate(max_len_payout as usize, &owner_id);
         }
-        let payout = OwnershipFractions::new(
-            &token.owner_id.to_string(),
-            &self.get_token_royalty(token_id),
-            &token.split_owners,
-        )
-        .into_payout(balance.into());
+        payout.vesting = payout
+            .payout
+            .keys()
+            .filter_map(|account_id| {
+                self.payout_vesting
+                    .get(account_id)
+                    .map(|schedule| (account_id.clone(), schedule))
+            })
+            .collect();
         let payout_len = payout.payout.len();
         if max_len_payout < payout_len as u32 {
             near_sdk::env::panic_str(format!("payout too long: {}", payout_len).as_str());
@@ -79,23 +166,46 @@ impl MintbaseStore {
     /// token. This method may only be called if the current `SplitOwners` field
     /// is `None`.
     ///
+    /// If `burn_quorum` is set, burning the token requires that many of the
+    /// split owners to confirm via `confirm_burn`, and the split owners
+    /// survive transfers of the token. If `sticky` is set, the
+    /// split owners survive transfers until cleared via `clear_split_owners`
+    /// by the caller of this method, once they own the token again.
+    ///
+    /// `split_between` may also be given in basis points as
+    /// `{ "split_bps": { ... } }`, see `SplitBetweenArgs`.
+    ///
     /// Only the token owner may call this function.
     #[payable]
     pub fn set_split_owners(
         &mut self,
         token_ids: Vec<U64>,
-        split_between: SplitBetweenUnparsed,
+        split_between: SplitBetweenArgs,
+        burn_quorum: Option<u32>,
+        sticky: Option<bool>,
     ) {
+        let split_between = split_between.into_split_between();
         assert!(!token_ids.is_empty());
         assert!(split_between.len() >= 2, "split len must be >= 2");
+        let split_between_len = split_between.len();
         let storage_cost =
-            (self.storage_costs.common * split_between.len() as u128) * token_ids.len() as u128;
+            (self.storage_costs().common * split_between_len as u128) * token_ids.len() as u128;
         assert!(
             env::attached_deposit() >= storage_cost,
             "insuf. deposit. Need: {}",
             storage_cost
         );
-        let splits = SplitOwners::new(split_between);
+        let splits = Self::checked_split_owners(split_between);
+        let deposit = StorageDeposit::new(
+            env::predecessor_account_id(),
+            self.storage_costs().common * split_between_len as u128,
+        );
+        let splits = match burn_quorum {
+            Some(burn_quorum) => splits.with_burn_quorum(burn_quorum),
+            None => splits,
+        }
+        .with_sticky(sticky.unwrap_or(false))
+        .with_deposit(deposit);
 
         token_ids.iter().for_each(|&token_id| {
             let mut token = self.nft_token_internal(token_id.into());
@@ -118,24 +228,471 @@ impl MintbaseStore {
             self.tokens.insert(&token_id.into(), &token);
         });
         log_set_split_owners(&token_ids, &splits);
+        log_storage_deposit(
+            StorageDepositAction::Taken,
+            &env::predecessor_account_id(),
+            env::attached_deposit(),
+            "set_split_owners",
+        );
+    }
+
+    /// Remove the `SplitOwners` of the tokens, e.g. if they were set by
+    /// mistake, and refund the storage deposit taken by `set_split_owners`
+    /// to the account that paid it. Splits with a burn quorum cannot be
+    /// cleared, as that would bypass the quorum, and sticky splits may only
+    /// be cleared by the account that set them.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn clear_split_owners(
+        &mut self,
+        token_ids: Vec<U64>,
+    ) {
+        assert_one_yocto();
+        assert!(!token_ids.is_empty());
+        let mut deposits = vec![];
+        token_ids.iter().for_each(|&token_id| {
+            let mut token = self.nft_token_internal(token_id.into());
+            assert!(!token.is_loaned());
+            assert!(token.is_pred_owner());
+            let splits = token.split_owners.take().expect("no split owners");
+            deposits.extend(Self::removable_split_deposit(splits));
+            self.tokens.insert(&token_id.into(), &token);
+        });
+        log_clear_split_owners(&token_ids);
+        self.refund_storage_deposits(deposits, "clear_split_owners");
+    }
+
+    /// Replace the `SplitOwners` of the tokens in a single transaction,
+    /// whether or not they currently have any. The storage deposit is
+    /// adjusted by the difference to the deposits the caller paid for the
+    /// replaced splits: the increase must be attached, a decrease is
+    /// refunded. Deposits paid by other accounts are refunded to them. The
+    /// same restrictions as for `clear_split_owners` apply to the replaced
+    /// splits.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn replace_split_owners(
+        &mut self,
+        token_ids: Vec<U64>,
+        split_between: SplitBetweenArgs,
+        sticky: Option<bool>,
+    ) {
+        let split_between = split_between.into_split_between();
+        assert!(env::attached_deposit() >= 1);
+        assert!(!token_ids.is_empty());
+        assert!(split_between.len() >= 2, "split len must be >= 2");
+        let account_id = env::predecessor_account_id();
+        let deposit = StorageDeposit::new(
+            account_id.clone(),
+            self.storage_costs().common * split_between.len() as u128,
+        );
+        let storage_cost = deposit.amount.0 * token_ids.len() as u128;
+        let splits = Self::checked_split_owners(split_between)
+            .with_sticky(sticky.unwrap_or(false))
+            .with_deposit(deposit);
+
+        let mut credit = 0;
+        let mut deposits = vec![];
+        token_ids.iter().for_each(|&token_id| {
+            let mut token = self.nft_token_internal(token_id.into());
+            assert!(!token.is_loaned());
+            assert!(token.is_pred_owner());
+            if let Some(old_splits) = token.split_owners.take() {
+                match Self::removable_split_deposit(old_splits) {
+                    Some(deposit) if deposit.account_id == account_id => credit += deposit.amount.0,
+                    deposit => deposits.extend(deposit),
+                }
+            }
+            let roy_len = match token.royalty_id {
+                Some(royalty_id) => self
+                    .token_royalty
+                    .get(&royalty_id)
+                    .unwrap()
+                    .1
+                    .split_between
+                    .len(),
+                None => 0,
+            };
+            assert!(splits.split_between.len() + roy_len <= MAX_LEN_PAYOUT as usize);
+
+            token.split_owners = Some(splits.clone());
+            self.tokens.insert(&token_id.into(), &token);
+        });
+        log_set_split_owners(&token_ids, &splits);
+
+        if storage_cost > credit {
+            let storage_cost = storage_cost - credit;
+            assert!(
+                env::attached_deposit() >= storage_cost,
+                "insuf. deposit. Need: {}",
+                storage_cost
+            );
+            log_storage_deposit(
+                StorageDepositAction::Taken,
+                &account_id,
+                env::attached_deposit(),
+                "replace_split_owners",
+            );
+        } else if storage_cost < credit {
+            deposits.push(StorageDeposit::new(account_id, credit - storage_cost));
+        }
+        self.refund_storage_deposits(deposits, "replace_split_owners");
+    }
+
+    /// Distribute the owner's share of the next sale of the token between
+    /// the accounts in `split_between`, e.g. to donate the proceeds of a
+    /// charity auction. Royalties are paid out as usual. The override takes
+    /// precedence over the `SplitOwners` of the token, may have a single
+    /// receiver, and is cleared on the next transfer. Setting
+    /// `split_between` to `None` removes the override.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn set_payout_override(
+        &mut self,
+        token_id: U64,
+        split_between: Option<SplitBetweenUnparsed>,
+    ) {
+        let split_len = split_between.as_ref().map(|s| s.len()).unwrap_or(0);
+        let storage_cost = self.storage_costs().common * split_len as u128;
+        assert!(
+            env::attached_deposit() >= storage_cost.max(1),
+            "insuf. deposit. Need: {}",
+            storage_cost.max(1)
+        );
+        let mut token = self.nft_token_internal(token_id.into());
+        assert!(!token.is_loaned());
+        assert!(token.is_pred_owner());
+        let payout_override = split_between.map(SplitOwners::new_payout_override);
+        if payout_override.is_some() {
+            let roy_len = match token.royalty_id {
+                Some(royalty_id) => self
+                    .token_royalty
+                    .get(&royalty_id)
+                    .unwrap()
+                    .1
+                    .split_between
+                    .len(),
+                None => 0,
+            };
+            assert!(split_len + roy_len <= MAX_LEN_PAYOUT as usize);
+        }
+
+        token.payout_override = payout_override;
+        self.tokens.insert(&token_id.into(), &token);
+        log_set_payout_override(token_id.into(), &token.payout_override);
+        if storage_cost > 0 {
+            log_storage_deposit(
+                StorageDepositAction::Taken,
+                &env::predecessor_account_id(),
+                env::attached_deposit(),
+                "set_payout_override",
+            );
+        }
+    }
+
+    /// Mark all payouts to `account_id` as vesting, e.g. for team
+    /// allocations on primary sales. The marketplace accrues vesting payouts
+    /// as a `ClaimableBalance`, from which the receiver may only withdraw
+    /// according to `schedule`. Setting `schedule` to `None` removes the
+    /// vesting, and only affects future payouts.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_payout_vesting(
+        &mut self,
+        account_id: AccountId,
+        schedule: Option<VestingSchedule>,
+    ) {
+        self.assert_store_owner();
+        match schedule {
+            Some(schedule) => {
+                let schedule = VestingSchedule::new(schedule.start, schedule.cliff, schedule.end);
+                self.payout_vesting.insert(&account_id, &schedule);
+            },
+            None => {
+                self.payout_vesting.remove(&account_id);
+            },
+        }
+    }
+
+    /// Fold shares below `threshold` yoctoNEAR into the owner's cut in
+    /// `nft_payout`, keeping payouts short. Setting `threshold` to zero
+    /// disables folding.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_payout_dust_threshold(
+        &mut self,
+        threshold: U128,
+    ) {
+        self.assert_store_owner();
+        self.payout_dust_threshold = threshold.into();
+    }
+
+    /// If `enabled`, payouts of a token also honor the royalties of the
+    /// tokens directly composed into it, as described for
+    /// `get_child_royalties`.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_aggregate_child_royalties(
+        &mut self,
+        enabled: bool,
+    ) {
+        self.assert_store_owner();
+        self.aggregate_child_royalties = enabled;
+    }
+
+    /// Set the contract that is queried for additional fee legs on each
+    /// `nft_transfer_payout`, capped at `max_fee` (out of 10,000) of the
+    /// sale balance. Setting `hook_id` to `None` removes the fee hook.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_fee_hook(
+        &mut self,
+        hook_id: Option<AccountId>,
+        max_fee: u32,
+    ) {
+        self.assert_store_owner();
+        self.fee_hook = hook_id.map(|hook_id| FeeHookConfig::new(hook_id, max_fee));
     }
 
     // -------------------------- view methods -----------------------------
 
+    /// Get the amount in yoctoNEAR below which shares are folded into the
+    /// owner's cut of a payout.
+    pub fn get_payout_dust_threshold(&self) -> U128 {
+        self.payout_dust_threshold.into()
+    }
+
+    /// Whether payouts honor the royalties of composed children.
+    pub fn get_aggregate_child_royalties(&self) -> bool {
+        self.aggregate_child_royalties
+    }
+
+    /// Get the royalties of the tokens directly composed into `token_id`
+    /// that are merged into its payouts if child royalties are aggregated,
+    /// in ascending order of token id. Children on other contracts are
+    /// ignored. Children are skipped if merging their royalty would push
+    /// the payout beyond `MAX_LEN_PAYOUT` receivers or the royalties of the
+    /// token beyond `ROYALTY_UPPER_LIMIT`.
+    pub fn get_child_royalties(
+        &self,
+        token_id: U64,
+    ) -> Vec<(U64, Royalty)> {
+        let token = self.nft_token_internal(token_id.into());
+        let royalty = self.royalty_of(&token);
+        let mut receivers: HashSet<AccountId> = royalty
+            .iter()
+            .flat_map(|royalty| royalty.split_between.keys().cloned())
+            .collect();
+        let split_len = token
+            .payout_override
+            .as_ref()
+            .or(token.split_owners.as_ref())
+            .map(|splits| splits.split_between.len())
+            .unwrap_or(1);
+        let mut percentage = royalty
+            .map(|royalty| royalty.percentage.numerator)
+            .unwrap_or(0);
+
+        let mut child_ids = self.composed_children(token_id.0);
+        child_ids.sort_unstable();
+        child_ids
+            .into_iter()
+            .filter_map(|child_id| {
+                let royalty = self.royalty_of(&self.nft_token_internal(child_id))?;
+                let new_receivers: Vec<&AccountId> = royalty
+                    .split_between
+                    .keys()
+                    .filter(|receiver| !receivers.contains(*receiver))
+                    .collect();
+                if receivers.len() + new_receivers.len() + split_len > MAX_LEN_PAYOUT as usize
+                    || percentage + royalty.percentage.numerator > ROYALTY_UPPER_LIMIT
+                {
+                    return None;
+                }
+                receivers.extend(new_receivers.into_iter().cloned());
+                percentage += royalty.percentage.numerator;
+                Some((child_id.into(), royalty))
+            })
+            .collect()
+    }
+
+    /// Get the fee hook configuration of this `Store`, if any.
+    pub fn get_fee_hook(&self) -> Option<FeeHookConfig> {
+        self.fee_hook.clone()
+    }
+
+    /// Get the `VestingSchedule` of payouts to `account_id`, if any.
+    pub fn get_payout_vesting(
+        &self,
+        account_id: AccountId,
+    ) -> Option<VestingSchedule> {
+        self.payout_vesting.get(&account_id)
+    }
+
+    /// Like `nft_payout`, but for a sale settled in `amount` of the fungible
+    /// token at `ft_contract`, e.g. USDC. Shares are computed at full
+    /// precision of the token's smallest unit. Vesting only applies to NEAR
+    /// payouts, thus all receivers are paid out directly.
+    pub fn nft_payout_ft(
+        &self,
+        token_id: U64,
+        ft_contract: AccountId,
+        amount: U128,
+        max_len_payout: u32,
+        truncate: Option<bool>,
+    ) -> Payout {
+        let (owner_id, fractions) = self.ownership_fractions(token_id);
+        let mut payout = fractions.into_ft_payout(amount.into(), ft_contract);
+        if truncate.unwrap_or(false) {
+            payout.truncate(max_len_payout as usize, &owner_id);
+        }
+        let payout_len = payout.payout.len();
+        if max_len_payout < payout_len as u32 {
+            near_sdk::env::panic_str(format!("payout too long: {}", payout_len).as_str());
+        }
+        payout
+    }
+
+    /// Check `royalty_args` and `split_between` as they would be passed to
+    /// `nft_batch_mint`, including the royalty cap of this `Store`, without
+    /// changing any state. Reports all problems found, such that frontends
+    /// can catch them before sending a failing transaction.
+    pub fn validate_payout_args(
+        &self,
+        royalty_args: Option<UncheckedRoyaltyArgs>,
+        split_between: Option<UncheckedSplitBetween>,
+    ) -> ValidationReport {
+        let mut report = ValidationReport::new(&royalty_args, &split_between);
+        if let (Some(max_royalty_bps), Some(royalty_args)) = (self.max_royalty_bps, royalty_args) {
+            if royalty_args.percentage > max_royalty_bps {
+                report.add_error(format!("royalty exceeds cap of {} bps", max_royalty_bps));
+            }
+        }
+        report
+    }
+
+    /// Get the canonical representation of `royalty_args`, which may be
+    /// given in basis points of the sale price as `{ "royalty_bps": { ... } }`.
+    /// This is the form in which royalties are stored and logged.
+    pub fn canonical_royalty_args(
+        &self,
+        royalty_args: RoyaltyArgs,
+    ) -> RoyaltyArgs {
+        royalty_args
+    }
+
+    /// Get the canonical representation of `split_between`, which may be
+    /// given in basis points as `{ "split_bps": { ... } }`.
+    pub fn canonical_split_between(
+        &self,
+        split_between: SplitBetweenArgs,
+    ) -> SplitBetweenUnparsed {
+        split_between.into_split_between()
+    }
+
     /// Get the Royalty for a Token. The `Royalty` structure is not stored on the
     /// token, as this would lead to duplication of `Royalty`s across tokens.
-    /// Instead, the `Royalty` is stored in a Contract `LookupMap`.
+    /// Instead, the `Royalty` is stored in a Contract `LookupMap`. Royalties
+    /// cached from a royalty registry take precedence.
     pub fn get_token_royalty(
         &self,
         token_id: U64,
     ) -> Option<Royalty> {
-        let royalty_id = self.nft_token_internal(token_id.into()).royalty_id;
-        match royalty_id {
-            Some(id) => self.token_royalty.get(&id).map(|(_, r)| r),
-            None => None,
-        }
+        self.royalty_of(&self.nft_token_internal(token_id.into()))
     }
 
     // -------------------------- private methods --------------------------
+
+    #[private]
+    pub fn on_fee_legs(
+        &mut self,
+        token_id: U64,
+        payout: Payout,
+        balance: U128,
+        max_len_payout: u32,
+        memo: Option<String>,
+    ) -> Payout {
+        let mut payout = payout;
+        let fee_legs = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                serde_json::from_slice::<HashMap<AccountId, U128>>(&value).ok()
+            },
+            _ => None,
+        };
+        let max_fee = self.fee_hook.as_ref().map(|fee_hook| fee_hook.max_fee);
+        match (fee_legs, max_fee) {
+            (Some(fee_legs), Some(max_fee)) => {
+                let new_receivers = fee_legs
+                    .keys()
+                    .filter(|receiver| !payout.payout.contains_key(*receiver))
+                    .count();
+                if payout.payout.len() + new_receivers > max_len_payout.min(MAX_LEN_PAYOUT) as usize
+                {
+                    env::log_str("fee legs exceed max_len_payout");
+                } else if !payout.add_fee_legs(fee_legs, balance.into(), max_fee) {
+                    env::log_str("fee legs exceed the fee hook cap");
+                }
+            },
+            _ => env::log_str("fee hook failed"),
+        }
+        log_nft_payout(token_id.into(), balance, &payout, &memo);
+        payout
+    }
+
     // -------------------------- internal methods -------------------------
+
+    /// Panics unless the caller may remove `splits` from a token: splits
+    /// with a burn quorum may not be removed, and sticky splits only by the
+    /// account that set them. Returns the storage deposit to refund, if
+    /// any was taken.
+    fn removable_split_deposit(splits: SplitOwners) -> Option<StorageDeposit> {
+        assert!(
+            splits.burn_quorum.is_none(),
+            "split owners have a burn quorum"
+        );
+        if splits.sticky {
+            assert!(
+                splits
+                    .deposit
+                    .as_ref()
+                    .map(|deposit| deposit.account_id == env::predecessor_account_id())
+                    .unwrap_or(false),
+                "sticky split owners set by another account"
+            );
+        }
+        splits.deposit
+    }
+
+    /// The owner of a token, and who receives which fraction of its next
+    /// sale. The owner's share of a composed token goes to the account
+    /// owning its root token. If child royalties are aggregated, the
+    /// royalties of composed children are honored as well.
+    fn ownership_fractions(
+        &self,
+        token_id: U64,
+    ) -> (AccountId, OwnershipFractions) {
+        let token = self.nft_token(token_id).expect("no token");
+        let (_, owner_id) = self.compose_chain(token_id.0);
+        let mut royalties: Vec<Royalty> = self.get_token_royalty(token_id).into_iter().collect();
+        if self.aggregate_child_royalties {
+            royalties.extend(
+                self.get_child_royalties(token_id)
+                    .into_iter()
+                    .map(|(_, royalty)| royalty),
+            );
+        }
+        let fractions = OwnershipFractions::new_merged(
+            owner_id.as_str(),
+            &royalties,
+            &token.payout_override.or(token.split_owners),
+        );
+        (owner_id, fractions)
+    }
 }
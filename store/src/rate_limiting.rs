@@ -0,0 +1,79 @@
+use mintbase_deps::common::time::now;
+use mintbase_deps::common::{
+    Activity,
+    RateLimits,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    AccountId,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Limit how many tokens each account may mint, and how many approvals
+    /// each account may grant, within any hour. The store owner is exempt.
+    /// Mints count against the minter, also when a delegate, relayer or
+    /// voucher holder submits them.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_rate_limits(
+        &mut self,
+        mints_per_hour: Option<u32>,
+        approvals_per_hour: Option<u32>,
+    ) {
+        self.assert_store_owner();
+        self.rate_limits = RateLimits {
+            mints_per_hour,
+            approvals_per_hour,
+        };
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    pub fn get_rate_limits(&self) -> RateLimits {
+        self.rate_limits.clone()
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+
+    /// Record `count` activities of the predecessor, panicking if this
+    /// exceeds the configured rate limit.
+    pub(crate) fn record_activity(
+        &mut self,
+        activity: Activity,
+        count: u32,
+    ) {
+        self.record_activity_of(env::predecessor_account_id(), activity, count)
+    }
+
+    /// Same as `record_activity`, but for `account_id` instead of the caller,
+    /// e.g. for a minter whose request is submitted by a relayer.
+    pub(crate) fn record_activity_of(
+        &mut self,
+        account_id: AccountId,
+        activity: Activity,
+        count: u32,
+    ) {
+        let limit = match activity {
+            Activity::Mint => self.rate_limits.mints_per_hour,
+            Activity::Approval => self.rate_limits.approvals_per_hour,
+        };
+        if limit.is_none() || account_id == self.owner_id {
+            return;
+        }
+
+        let mut window = self.activity.get(&account_id).unwrap_or_default();
+        if let Err(err) = window.record(activity, count, &self.rate_limits, &now()) {
+            env::panic_str(&err.to_string());
+        }
+        self.activity.insert(&account_id, &window);
+    }
+}
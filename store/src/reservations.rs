@@ -0,0 +1,167 @@
+// the code generated for `nft_mint_reserved` takes all of its arguments
+#![allow(clippy::too_many_arguments)]
+
+use std::collections::BTreeMap;
+
+use mintbase_deps::common::{
+    NearTime,
+    RoyaltyArgs,
+    SplitBetweenUnparsed,
+    TokenMetadata,
+    TokenReservation,
+};
+use mintbase_deps::logging::{
+    log_reserve_token_range,
+    log_storage_deposit,
+    StorageDepositAction,
+};
+use mintbase_deps::near_sdk::json_types::U64;
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    AccountId,
+    Balance,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Reserve `count` consecutive token ids for the calling minter. The
+    /// returned range can't be taken by any other mint, such that media and
+    /// metadata keyed by token id may be prepared before the tokens are
+    /// minted via `nft_mint_reserved`.
+    ///
+    /// Only minters may call this function. The storage of the reservation
+    /// must be covered.
+    #[payable]
+    pub fn reserve_token_range(
+        &mut self,
+        count: u64,
+    ) -> TokenReservation {
+        near_sdk::assert_one_yocto();
+        assert!(count > 0);
+        assert!(count <= u16::MAX as u64); // as for mint sessions
+        let minter_id = env::predecessor_account_id();
+        self.assert_can_mint(&minter_id);
+        self.assert_not_banned_from_minting(&minter_id);
+
+        let covered_storage = env::account_balance()
+            - (env::storage_usage() as u128 * self.storage_costs().storage_price_per_byte);
+        // the reservation takes about as much storage as a mint batch
+        let expected_storage_consumption: Balance = self.storage_costs().mint_batch;
+        assert!(
+            covered_storage >= expected_storage_consumption,
+            "covered: {}; need: {}",
+            covered_storage,
+            expected_storage_consumption
+        );
+
+        let reservation = TokenReservation {
+            minter: minter_id,
+            first_token_id: self.tokens_minted,
+            last_token_id: self.tokens_minted + count - 1,
+            num_minted: 0,
+        };
+        self.tokens_minted += count;
+        self.token_reservations
+            .insert(&reservation.first_token_id, &reservation);
+
+        log_reserve_token_range(&reservation);
+        log_storage_deposit(
+            StorageDepositAction::Consumed,
+            &reservation.minter,
+            expected_storage_consumption,
+            "reserve_token_range",
+        );
+        reservation
+    }
+
+    /// Mint the next `num_to_mint` tokens of the reservation starting at
+    /// `first_token_id`, as copies of a token. The reservation ends once all
+    /// of its tokens have been minted.
+    ///
+    /// The same restrictions as for `nft_batch_mint` apply, and only the
+    /// minter that reserved the range may call this function.
+    #[payable]
+    pub fn nft_mint_reserved(
+        &mut self,
+        first_token_id: U64,
+        owner_id: AccountId,
+        metadata: TokenMetadata,
+        num_to_mint: u64,
+        royalty_args: Option<RoyaltyArgs>,
+        split_owners: Option<SplitBetweenUnparsed>,
+        transferable: Option<bool>,
+        expires_at: Option<NearTime>,
+    ) {
+        assert!(num_to_mint > 0);
+        assert!(num_to_mint <= 125); // upper gas limit
+        assert!(env::attached_deposit() >= 1);
+        let mut reservation = self
+            .token_reservations
+            .get(&first_token_id.into())
+            .expect("no such reservation");
+        let minter_id = env::predecessor_account_id();
+        assert_eq!(
+            reservation.minter, minter_id,
+            "reservation of another minter"
+        );
+        assert!(
+            num_to_mint <= reservation.remaining(),
+            "only {} reserved tokens remain",
+            reservation.remaining()
+        );
+        let sets_payout = royalty_args.is_some() || split_owners.is_some();
+        self.admit_mint(&minter_id, num_to_mint);
+        let (royalty_args, minting_fee) =
+            self.prepare_mint(&minter_id, num_to_mint, royalty_args, sets_payout);
+        assert!(
+            env::attached_deposit() >= minting_fee,
+            "attached: {}; minting fee: {}",
+            env::attached_deposit(),
+            minting_fee
+        );
+
+        let next_token_id = reservation.next_token_id();
+        reservation.num_minted += num_to_mint;
+        if reservation.remaining() > 0 {
+            self.token_reservations
+                .insert(&reservation.first_token_id, &reservation);
+        } else {
+            self.token_reservations.remove(&reservation.first_token_id);
+        }
+
+        self.mint_batch_internal(
+            minter_id,
+            owner_id,
+            next_token_id,
+            metadata,
+            num_to_mint,
+            royalty_args,
+            BTreeMap::new(),
+            split_owners,
+            transferable,
+            expires_at,
+            minting_fee,
+            "nft_mint_reserved",
+        );
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the state of the reservation starting at `first_token_id`, if
+    /// some of its tokens remain to be minted.
+    pub fn get_token_reservation(
+        &self,
+        first_token_id: U64,
+    ) -> Option<TokenReservation> {
+        self.token_reservations.get(&first_token_id.into())
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+}
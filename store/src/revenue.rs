@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use mintbase_deps::common::time::now;
+use mintbase_deps::common::ClaimableBalance;
+use mintbase_deps::constants::MAX_LEN_PAYOUT;
+use mintbase_deps::logging::{
+    log_claim_revenue,
+    log_deposit_revenue,
+    log_storage_deposit,
+    StorageDepositAction,
+};
+use mintbase_deps::near_sdk::json_types::U128;
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    AccountId,
+    Balance,
+    Promise,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Deposit the proceeds of a sale into the revenue vault of this
+    /// `Store`, e.g. the `payout` returned by `nft_transfer_payout`, instead
+    /// of transferring them to each receiver. Receivers withdraw their
+    /// revenue via `claim_revenue`, thus they need not exist at sale time.
+    /// Revenue of receivers with a payout vesting vests according to the
+    /// schedule at the time of their first deposit.
+    ///
+    /// The attached deposit must cover the sum of `payout` and the storage
+    /// of new vault entries. Any excess is refunded.
+    #[payable]
+    pub fn deposit_revenue(
+        &mut self,
+        payout: HashMap<AccountId, U128>,
+    ) {
+        assert!(!payout.is_empty());
+        assert!(
+            payout.len() as u32 <= MAX_LEN_PAYOUT,
+            "payout too long: {}",
+            payout.len()
+        );
+        let total: Balance = payout
+            .values()
+            .try_fold(0u128, |acc, amount| acc.checked_add(amount.0))
+            .expect("payout overflows");
+
+        let storage_before = env::storage_usage();
+        payout.iter().for_each(|(account_id, amount)| {
+            let mut balance = self
+                .revenue_vault
+                .get(account_id)
+                .unwrap_or_else(|| ClaimableBalance::new(self.payout_vesting.get(account_id)));
+            balance.accrue(amount.0);
+            self.revenue_vault.insert(account_id, &balance);
+        });
+        let storage_cost = env::storage_usage().saturating_sub(storage_before) as u128
+            * self.storage_costs().storage_price_per_byte;
+        assert!(
+            env::attached_deposit() >= total + storage_cost,
+            "attached: {}; need: {}",
+            env::attached_deposit(),
+            total + storage_cost
+        );
+
+        log_deposit_revenue(&payout);
+        let depositor = env::predecessor_account_id();
+        if storage_cost > 0 {
+            log_storage_deposit(
+                StorageDepositAction::Taken,
+                &depositor,
+                storage_cost,
+                "deposit_revenue",
+            );
+        }
+        let refund = env::attached_deposit() - total - storage_cost;
+        if refund > 0 {
+            log_storage_deposit(
+                StorageDepositAction::Refunded,
+                &depositor,
+                refund,
+                "deposit_revenue",
+            );
+            Promise::new(depositor).transfer(refund);
+        }
+    }
+
+    /// Withdraw all revenue of the caller that is claimable from the
+    /// revenue vault. Returns the withdrawn amount.
+    pub fn claim_revenue(&mut self) -> U128 {
+        let account_id = env::predecessor_account_id();
+        let mut balance = self.revenue_vault.get(&account_id).expect("no revenue");
+        let amount = balance.withdraw(&now());
+        assert!(amount > 0, "nothing to claim");
+        if balance.withdrawn == balance.accrued {
+            self.revenue_vault.remove(&account_id);
+        } else {
+            self.revenue_vault.insert(&account_id, &balance);
+        }
+
+        log_claim_revenue(&account_id, amount);
+        Promise::new(account_id).transfer(amount);
+        amount.into()
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the revenue vault entry of `account_id`, if any.
+    pub fn get_revenue_balance(
+        &self,
+        account_id: AccountId,
+    ) -> Option<ClaimableBalance> {
+        self.revenue_vault.get(&account_id)
+    }
+
+    /// Get the amount that `account_id` may currently withdraw via
+    /// `claim_revenue`.
+    pub fn get_claimable_revenue(
+        &self,
+        account_id: AccountId,
+    ) -> U128 {
+        self.revenue_vault
+            .get(&account_id)
+            .map(|balance| balance.claimable(&now()))
+            .unwrap_or(0)
+            .into()
+    }
+}
@@ -0,0 +1,169 @@
+use mintbase_deps::common::StoreRole;
+use mintbase_deps::logging::{
+    log_grant_minter,
+    log_grant_role,
+    log_revoke_minter,
+    log_revoke_role,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    assert_one_yocto,
+    env,
+    near_bindgen,
+    AccountId,
+};
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Grant `role` to `account_id`. Minting roles make `account_id` a
+    /// minter, and replace any other minting role it has, e.g. to allow a
+    /// service account to mint without setting royalties or splits.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn grant_role(
+        &mut self,
+        account_id: AccountId,
+        role: StoreRole,
+    ) {
+        self.assert_store_owner();
+        let mut roles = self.roles.get(&account_id).unwrap_or_default();
+        if roles.contains(&role) {
+            return;
+        }
+        if role.is_minting() {
+            assert_ne!(account_id, self.owner_id, "can't restrict owner");
+            roles.retain(|other| {
+                if other.is_minting() {
+                    log_revoke_role(&account_id, *other);
+                }
+                !other.is_minting()
+            });
+            if self.minters.insert(&account_id) {
+                self.start_minter_cooling_off(&account_id);
+                log_grant_minter(&account_id);
+            }
+        }
+        roles.push(role);
+        self.roles.insert(&account_id, &roles);
+        log_grant_role(&account_id, role);
+    }
+
+    /// Revoke `role` from `account_id`. Revoking a minting role also revokes
+    /// the minting privileges of `account_id`.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn revoke_role(
+        &mut self,
+        account_id: AccountId,
+        role: StoreRole,
+    ) {
+        self.assert_store_owner();
+        let mut roles = self.roles.get(&account_id).unwrap_or_default();
+        assert!(roles.contains(&role), "{} lacks role {}", account_id, role);
+        roles.retain(|other| *other != role);
+        self.set_roles(&account_id, roles);
+        log_revoke_role(&account_id, role);
+        if role.is_minting() && self.minters.remove(&account_id) {
+            self.minter_allowances.remove(&account_id);
+            self.minter_expiries.remove(&account_id);
+            self.minter_mintable_from.remove(&account_id);
+            log_revoke_minter(&account_id);
+        }
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the roles of `account_id`. Minters that have been granted via
+    /// `grant_minter`, as well as the store owner, have the
+    /// `mint_with_royalty` role.
+    pub fn get_roles(
+        &self,
+        account_id: AccountId,
+    ) -> Vec<StoreRole> {
+        if account_id == self.owner_id {
+            return vec![StoreRole::MintWithRoyalty, StoreRole::MetadataEditor];
+        }
+        let mut roles = self.roles.get(&account_id).unwrap_or_default();
+        if self.minters.contains(&account_id) && !roles.iter().any(StoreRole::is_minting) {
+            roles.insert(0, StoreRole::MintWithRoyalty);
+        }
+        roles
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+
+    /// Panics unless the predecessor is the store owner or has `role`, and
+    /// one yoctoNEAR is attached.
+    pub(crate) fn assert_store_owner_or_role(
+        &self,
+        role: StoreRole,
+    ) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        assert!(
+            account_id == self.owner_id
+                || self
+                    .roles
+                    .get(&account_id)
+                    .map(|roles| roles.contains(&role))
+                    .unwrap_or(false),
+            "{} lacks role {}",
+            account_id,
+            role
+        );
+    }
+
+    /// Panics if `minter_id` sets royalties or split owners on minting, but
+    /// may only mint without.
+    pub(crate) fn assert_can_set_royalty(
+        &self,
+        minter_id: &AccountId,
+        sets_royalty: bool,
+    ) {
+        let mint_only = self
+            .roles
+            .get(minter_id)
+            .map(|roles| roles.contains(&StoreRole::MintOnly))
+            .unwrap_or(false);
+        assert!(
+            !(sets_royalty && mint_only),
+            "{} may not set royalties or splits",
+            minter_id
+        );
+    }
+
+    /// Remove the minting roles of `account_id`, keeping all other roles.
+    pub(crate) fn revoke_minting_roles(
+        &mut self,
+        account_id: &AccountId,
+    ) {
+        let mut roles = self.roles.get(account_id).unwrap_or_default();
+        roles.retain(|role| {
+            if role.is_minting() {
+                log_revoke_role(account_id, *role);
+            }
+            !role.is_minting()
+        });
+        self.set_roles(account_id, roles);
+    }
+
+    /// Store `roles` for `account_id`, removing the entry if empty.
+    pub(crate) fn set_roles(
+        &mut self,
+        account_id: &AccountId,
+        roles: Vec<StoreRole>,
+    ) {
+        if roles.is_empty() {
+            self.roles.remove(account_id);
+        } else {
+            self.roles.insert(account_id, &roles);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use mintbase_deps::common::{
+    Royalty,
+    RoyaltyArgs,
+};
+use mintbase_deps::constants::{
+    gas,
+    MAX_LEN_PAYOUT,
+    NO_DEPOSIT,
+};
+use mintbase_deps::interfaces::ext_royalty_registry;
+use mintbase_deps::logging::log_royalty_update;
+use mintbase_deps::near_sdk::json_types::U64;
+use mintbase_deps::near_sdk::{
+    self,
+    assert_one_yocto,
+    env,
+    near_bindgen,
+    AccountId,
+    Promise,
+    PromiseResult,
+};
+use mintbase_deps::serde_json;
+use mintbase_deps::token::Token;
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Defer royalty resolution to `registry_id`. Royalties are queried
+    /// from the registry via `refresh_registry_royalty` and cached per
+    /// lookup id. Tokens without a cached royalty fall back to the
+    /// royalties stored on this `Store`. Setting `registry_id` to `None`
+    /// resolves all royalties locally again.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_royalty_registry(
+        &mut self,
+        registry_id: Option<AccountId>,
+    ) {
+        self.assert_store_owner();
+        self.royalty_registry = registry_id;
+    }
+
+    /// Query the royalty registry for the royalty of the tokens that share
+    /// `lookup_id`, and cache the result. Anyone may call this function,
+    /// e.g. after the royalty has been updated on the registry.
+    #[payable]
+    pub fn refresh_registry_royalty(
+        &mut self,
+        lookup_id: U64,
+    ) -> Promise {
+        assert_one_yocto();
+        let registry_id = self.royalty_registry.clone().expect("no royalty registry");
+        assert!(
+            self.token_metadata.contains_key(&lookup_id.into()),
+            "unknown lookup id"
+        );
+
+        ext_royalty_registry::get_royalty(
+            env::current_account_id(),
+            lookup_id,
+            registry_id.clone(),
+            NO_DEPOSIT,
+            gas::GET_REGISTRY_ROYALTY,
+        )
+        .then(store_self::on_registry_royalty(
+            registry_id,
+            lookup_id,
+            env::current_account_id(),
+            NO_DEPOSIT,
+            gas::ON_REGISTRY_ROYALTY,
+        ))
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the royalty registry of this `Store`, if any.
+    pub fn get_royalty_registry(&self) -> Option<AccountId> {
+        self.royalty_registry.clone()
+    }
+
+    /// Get the royalty cached from the current royalty registry for
+    /// `lookup_id`, if any.
+    pub fn get_registry_royalty(
+        &self,
+        lookup_id: U64,
+    ) -> Option<Royalty> {
+        let registry_id = self.royalty_registry.as_ref()?;
+        self.registry_royalties
+            .get(&lookup_id.into())
+            .filter(|(cached_from, _)| cached_from == registry_id)
+            .map(|(_, royalty)| royalty)
+    }
+
+    // -------------------------- private methods --------------------------
+
+    #[private]
+    pub fn on_registry_royalty(
+        &mut self,
+        registry_id: AccountId,
+        lookup_id: U64,
+    ) {
+        let royalty_args = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                serde_json::from_slice::<Option<RoyaltyArgs>>(&value).expect("bad registry royalty")
+            },
+            _ => env::panic_str("royalty registry failed"),
+        };
+
+        let royalty = match royalty_args {
+            Some(royalty_args) => {
+                // leave room for the owner in payouts
+                assert!(
+                    (royalty_args.split_between.len() as u32) < MAX_LEN_PAYOUT,
+                    "registry royalty too long"
+                );
+                let royalty = Royalty::new(royalty_args);
+                self.registry_royalties
+                    .insert(&lookup_id.into(), &(registry_id.clone(), royalty.clone()));
+                Some(royalty)
+            },
+            None => {
+                self.registry_royalties.remove(&lookup_id.into());
+                None
+            },
+        };
+        log_royalty_update(lookup_id.into(), &registry_id, &royalty);
+    }
+
+    // -------------------------- internal methods -------------------------
+
+    /// The royalty of `token`. If a royalty has been cached from the
+    /// royalty registry for the lookup id of `token`, it takes precedence
+    /// over the royalty stored on this `Store`.
+    pub(crate) fn royalty_of(
+        &self,
+        token: &Token,
+    ) -> Option<Royalty> {
+        self.get_registry_royalty(token.metadata_id.into())
+            .or_else(|| {
+                token
+                    .royalty_id
+                    .and_then(|id| self.token_royalty.get(&id).map(|(_, r)| r))
+            })
+    }
+}
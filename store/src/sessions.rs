@@ -0,0 +1,254 @@
+use mintbase_deps::common::{
+    MintBatch,
+    MintSession,
+    NewSplitOwner,
+    Royalty,
+    RoyaltyArgs,
+    SplitBetweenUnparsed,
+    SplitOwners,
+    TokenMetadata,
+};
+use mintbase_deps::constants::MAX_LEN_PAYOUT;
+use mintbase_deps::logging::{
+    log_nft_batch_mint,
+    log_storage_deposit,
+    StorageDepositAction,
+};
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    AccountId,
+    Balance,
+};
+use mintbase_deps::token::Token;
+
+use crate::*;
+
+/// Maximum number of tokens minted per call within a mint session.
+const MAX_CHUNK_SIZE: u64 = 125;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Start minting `num_to_mint` copies of a token, which may exceed the
+    /// number of tokens that can be minted in a single call. The token ids
+    /// of all copies are reserved, and the first 125 copies are minted
+    /// right away. The remaining copies are minted via
+    /// `continue_mint_session`. Returns the `mint_id` identifying the
+    /// session.
+    ///
+    /// The same restrictions as for `nft_batch_mint` apply, except that up
+    /// to 65535 copies may be minted. The storage of all copies must be
+    /// covered when starting the session, and minting fee and allowance
+    /// apply to all copies. Rate limits and the mint window apply to each
+    /// chunk as it is minted.
+    #[payable]
+    pub fn start_mint_session(
+        &mut self,
+        owner_id: AccountId,
+        metadata: TokenMetadata,
+        num_to_mint: u64,
+        royalty_args: Option<RoyaltyArgs>,
+        split_owners: Option<SplitBetweenUnparsed>,
+    ) -> String {
+        assert!(num_to_mint > 0);
+        assert!(num_to_mint <= u16::MAX as u64); // copies are counted as u16
+        assert!(env::attached_deposit() >= 1);
+        let minter_id = env::predecessor_account_id();
+        let sets_payout = royalty_args.is_some() || split_owners.is_some();
+        // rate limits apply to each chunk as it is minted
+        self.admit_mint(&minter_id, num_to_mint.min(MAX_CHUNK_SIZE));
+        let (royalty_args, minting_fee) =
+            self.prepare_mint(&minter_id, num_to_mint, royalty_args, sets_payout);
+        assert!(
+            env::attached_deposit() >= minting_fee,
+            "attached: {}; minting fee: {}",
+            env::attached_deposit(),
+            minting_fee
+        );
+
+        let covered_storage = env::account_balance()
+            - (env::storage_usage() as u128 * self.storage_costs().storage_price_per_byte);
+        let (metadata, md_size) = TokenMetadata::from_with_size(metadata, num_to_mint);
+        let roy_len = royalty_args
+            .as_ref()
+            .map(|pre_roy| pre_roy.split_between.len() as u32)
+            .unwrap_or(0);
+        let split_len = split_owners
+            .as_ref()
+            .map(|pre_split| pre_split.len() as u32)
+            .unwrap_or(1);
+        assert!(roy_len + split_len <= MAX_LEN_PAYOUT);
+        // the session record takes about as much storage as a mint batch
+        let expected_storage_consumption: Balance = self.storage_costs().mint_batch
+            + self.storage_cost_to_mint(1, num_to_mint, md_size, roy_len, split_len);
+        assert!(
+            covered_storage >= expected_storage_consumption,
+            "covered: {}; need: {}",
+            covered_storage,
+            expected_storage_consumption
+        );
+
+        let checked_royalty = royalty_args.map(Royalty::new);
+        let checked_split = split_owners.map(SplitOwners::new);
+
+        // Reserve the token ids of the whole session
+        let lookup_id: u64 = self.tokens_minted;
+        self.tokens_minted += num_to_mint;
+        let royalty_id = checked_royalty.map(|royalty| {
+            self.token_royalty
+                .insert(&lookup_id, &(num_to_mint as u16, royalty));
+            lookup_id
+        });
+        self.token_metadata
+            .insert(&lookup_id, &(num_to_mint as u16, metadata));
+
+        let mint_id = MintBatch::generate_id(&minter_id, lookup_id);
+        self.insert_mint_batch(MintBatch {
+            mint_id: mint_id.clone(),
+            minter: minter_id.clone(),
+            owner_id: owner_id.clone(),
+            first_token_id: lookup_id,
+            last_token_id: lookup_id + num_to_mint - 1,
+            metadata_id: lookup_id,
+            royalty_id,
+            block_height: env::block_height(),
+        });
+
+        let mut session = MintSession {
+            mint_id: mint_id.clone(),
+            minter: minter_id,
+            owner_id,
+            first_token_id: lookup_id,
+            num_to_mint,
+            num_minted: 0,
+            metadata_id: lookup_id,
+            royalty_id,
+            split_owners: checked_split,
+        };
+        log_storage_deposit(
+            StorageDepositAction::Consumed,
+            &session.minter,
+            expected_storage_consumption,
+            "start_mint_session",
+        );
+        self.mint_session_chunk(&mut session, MAX_CHUNK_SIZE);
+        self.pay_minting_fee(minting_fee);
+        mint_id
+    }
+
+    /// Mint the next copies of the mint session identified by `mint_id`, up
+    /// to 125 at a time. The session ends once all copies have been minted.
+    ///
+    /// Only the minter that started the session may call this function, and
+    /// only while they may still mint on this `Store`, within their rate
+    /// limit and mint window. The storage of the minted copies must be
+    /// covered.
+    #[payable]
+    pub fn continue_mint_session(
+        &mut self,
+        mint_id: String,
+    ) {
+        near_sdk::assert_one_yocto();
+        let mut session = self.mint_sessions.get(&mint_id).expect("no such session");
+        assert_eq!(
+            session.minter,
+            env::predecessor_account_id(),
+            "session of another minter"
+        );
+        let num_tokens = session.remaining().min(MAX_CHUNK_SIZE);
+        self.admit_mint(&session.minter, num_tokens);
+
+        let split_len = session
+            .split_owners
+            .as_ref()
+            .map(|split_owners| split_owners.split_between.len() as u128)
+            .unwrap_or(1);
+        let storage_costs = self.storage_costs();
+        let covered_storage = env::account_balance()
+            - (env::storage_usage() as u128 * storage_costs.storage_price_per_byte);
+        let expected_storage_consumption: Balance =
+            num_tokens as u128 * (storage_costs.token + split_len * storage_costs.common);
+        assert!(
+            covered_storage >= expected_storage_consumption,
+            "covered: {}; need: {}",
+            covered_storage,
+            expected_storage_consumption
+        );
+
+        log_storage_deposit(
+            StorageDepositAction::Consumed,
+            &session.minter,
+            expected_storage_consumption,
+            "continue_mint_session",
+        );
+        self.mint_session_chunk(&mut session, num_tokens);
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the state of the unfinished mint session identified by `mint_id`.
+    pub fn get_mint_session(
+        &self,
+        mint_id: String,
+    ) -> Option<MintSession> {
+        self.mint_sessions.get(&mint_id)
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+
+    /// Mint up to `max_tokens` of the reserved tokens of `session`, and
+    /// store or drop the session depending on whether tokens remain.
+    fn mint_session_chunk(
+        &mut self,
+        session: &mut MintSession,
+        max_tokens: u64,
+    ) {
+        let num_tokens = session.remaining().min(max_tokens);
+        let first_token_id = session.next_token_id();
+        let last_token_id = first_token_id + num_tokens - 1;
+
+        let mut owned_set = self.get_or_make_new_owner_set(&session.owner_id);
+        (first_token_id..=last_token_id).for_each(|token_id| {
+            let token = Token::new(
+                session.owner_id.clone(),
+                token_id,
+                session.metadata_id,
+                session.royalty_id,
+                session.split_owners.clone(),
+                session.minter.clone(),
+            );
+            owned_set.insert(&token_id);
+            self.tokens.insert(&token_id, &token);
+        });
+        self.tokens_per_owner.insert(&session.owner_id, &owned_set);
+        session.num_minted += num_tokens;
+
+        let metadata = self.token_metadata.get(&session.metadata_id).unwrap().1;
+        let royalty = session
+            .royalty_id
+            .map(|royalty_id| self.token_royalty.get(&royalty_id).unwrap().1);
+        log_nft_batch_mint(
+            first_token_id,
+            last_token_id,
+            session.minter.as_ref(),
+            session.owner_id.as_ref(),
+            &royalty,
+            &session.split_owners,
+            &metadata.reference,
+            &metadata.extra,
+            &session.mint_id,
+            &metadata.media_hints(),
+            self.mint_event_metadata.then_some(&metadata),
+        );
+
+        if session.remaining() > 0 {
+            self.mint_sessions.insert(&session.mint_id, session);
+        } else {
+            self.mint_sessions.remove(&session.mint_id);
+        }
+    }
+}
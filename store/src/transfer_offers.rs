@@ -0,0 +1,112 @@
+use mintbase_deps::common::NearTime;
+use mintbase_deps::logging::log_nft_transfer;
+use mintbase_deps::near_sdk::json_types::U64;
+use mintbase_deps::near_sdk::{
+    self,
+    assert_one_yocto,
+    env,
+    near_bindgen,
+    AccountId,
+};
+use mintbase_deps::token::TransferOffer;
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Offer the token to `receiver_id`, which is transferred only once the
+    /// receiver calls `nft_accept_transfer`, e.g. to prevent losing
+    /// high-value tokens to typos in account ids. Until `expires_at`, at
+    /// most 30 days from now, the token is held in escrow: it may not be
+    /// transferred otherwise, approved, or burned, and the owner may not
+    /// cancel the offer. The
+    /// transfer fee is due when offering, and not refunded if the offer is
+    /// declined or expires.
+    ///
+    /// Only the token owner may call this function.
+    #[payable]
+    pub fn nft_offer_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: U64,
+        expires_at: NearTime,
+        memo: Option<String>,
+    ) {
+        self.collect_transfer_fee(1, 1);
+        let mut token = self.nft_token_internal(token_id.into());
+        token.assert_movable_by_owner();
+        assert!(token.is_pred_owner());
+        assert_ne!(receiver_id.to_string(), token.owner_id.to_string()); // can't transfer to self
+        self.assert_transfer_policy(&receiver_id);
+        token.transfer_offer = Some(TransferOffer::new(receiver_id, memo, expires_at));
+        self.tokens.insert(&token.id, &token);
+    }
+
+    /// Accept the transfer offered via `nft_offer_transfer`, transferring
+    /// the token to the caller.
+    ///
+    /// Only the receiver of the offer may call this function, and only
+    /// before the offer expires.
+    #[payable]
+    pub fn nft_accept_transfer(
+        &mut self,
+        token_id: U64,
+    ) {
+        assert_one_yocto();
+        let mut token = self.nft_token_internal(token_id.into());
+        let offer = token.transfer_offer.clone().expect("no transfer offer");
+        assert_eq!(
+            offer.receiver_id,
+            env::predecessor_account_id(),
+            "not the receiver"
+        );
+        assert!(!offer.is_expired(), "transfer offer expired");
+        assert!(!token.is_loaned());
+        token.assert_not_frozen();
+        self.assert_transfer_policy(&offer.receiver_id);
+        let old_owner = token.owner_id.to_string();
+        self.transfer_internal(
+            &mut token,
+            offer.receiver_id.clone(),
+            true,
+            "nft_accept_transfer",
+        );
+        log_nft_transfer(&offer.receiver_id, token.id, &offer.memo, old_owner);
+    }
+
+    /// Cancel the transfer offered via `nft_offer_transfer`.
+    ///
+    /// The receiver may decline the offer at any time, the token owner may
+    /// only cancel it once it expired.
+    #[payable]
+    pub fn nft_cancel_transfer_offer(
+        &mut self,
+        token_id: U64,
+    ) {
+        assert_one_yocto();
+        let mut token = self.nft_token_internal(token_id.into());
+        let offer = token.transfer_offer.take().expect("no transfer offer");
+        if token.is_pred_owner() {
+            assert!(offer.is_expired(), "transfer offer not expired");
+        } else {
+            assert_eq!(
+                offer.receiver_id,
+                env::predecessor_account_id(),
+                "only owner or receiver"
+            );
+        }
+        self.tokens.insert(&token.id, &token);
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the transfer offered for the token, if any.
+    pub fn get_transfer_offer(
+        &self,
+        token_id: U64,
+    ) -> Option<TransferOffer> {
+        self.nft_token_internal(token_id.into()).transfer_offer
+    }
+}
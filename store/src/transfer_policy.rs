@@ -0,0 +1,200 @@
+use mintbase_deps::common::{
+    StoreError,
+    TransferPolicy,
+};
+use mintbase_deps::constants::{
+    gas,
+    NO_DEPOSIT,
+};
+use mintbase_deps::interfaces::ext_transfer_policy;
+use mintbase_deps::logging::{
+    log_nft_transfer,
+    log_nft_transfer_rejected,
+};
+use mintbase_deps::near_sdk::json_types::U64;
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    AccountId,
+    Promise,
+    PromiseResult,
+};
+use mintbase_deps::serde_json;
+use mintbase_deps::token::Token;
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Restrict the receivers of transfers, e.g. for regulated issuers of
+    /// tickets or real-world assets. Setting `policy` to `None` lifts all
+    /// restrictions. The transfer policy list is kept when switching
+    /// policies.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn set_transfer_policy(
+        &mut self,
+        policy: Option<TransferPolicy>,
+    ) {
+        self.assert_store_owner();
+        self.transfer_policy = policy;
+    }
+
+    /// Add `account_ids` to the transfer policy list if `listed` is set,
+    /// otherwise remove them.
+    ///
+    /// Only the store owner may call this function.
+    #[payable]
+    pub fn update_transfer_policy_list(
+        &mut self,
+        account_ids: Vec<AccountId>,
+        listed: bool,
+    ) {
+        self.assert_store_owner();
+        account_ids.iter().for_each(|account_id| {
+            if listed {
+                self.transfer_policy_list.insert(account_id);
+            } else {
+                self.transfer_policy_list.remove(account_id);
+            }
+        });
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    pub fn get_transfer_policy(&self) -> Option<TransferPolicy> {
+        self.transfer_policy.clone()
+    }
+
+    pub fn is_on_transfer_policy_list(
+        &self,
+        account_id: AccountId,
+    ) -> bool {
+        self.transfer_policy_list.contains(&account_id)
+    }
+
+    /// Whether `receiver_id` may currently receive tokens. Under a hook
+    /// policy, each transfer is checked by the hook instead, thus this
+    /// method returns true.
+    pub fn can_receive_transfers(
+        &self,
+        receiver_id: AccountId,
+    ) -> bool {
+        self.transfer_policy
+            .as_ref()
+            .map(|policy| policy.allows(self.transfer_policy_list.contains(&receiver_id)))
+            .unwrap_or(true)
+    }
+
+    // -------------------------- private methods --------------------------
+
+    #[private]
+    pub fn on_check_transfer(
+        &mut self,
+        hook_id: AccountId,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: U64,
+        memo: Option<String>,
+    ) -> bool {
+        let mut token = self.nft_token_internal(token_id.0);
+        if !self.unlock_token(&mut token) {
+            env::log_str(format!("token {} force-unlocked, transfer aborted", token_id.0).as_str());
+            return false;
+        }
+        let approved = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                serde_json::from_slice::<bool>(&value).unwrap_or(false)
+            },
+            _ => false,
+        };
+        if approved {
+            self.transfer_internal(&mut token, receiver_id.clone(), true, "on_check_transfer");
+            log_nft_transfer(&receiver_id, token_id.0, &memo, owner_id.to_string());
+        } else {
+            log_nft_transfer_rejected(token_id.0, &owner_id, &receiver_id, &hook_id);
+        }
+        approved
+    }
+
+    // -------------------------- internal methods -------------------------
+
+    /// Check `receiver_id` against the transfer policy for transfers that
+    /// complete within the current call. These are not available under a
+    /// hook policy.
+    pub(crate) fn check_transfer_policy(
+        &self,
+        receiver_id: &AccountId,
+    ) -> Result<(), StoreError> {
+        match self.transfer_policy {
+            None => Ok(()),
+            Some(TransferPolicy::Hook { .. }) => Err(StoreError::TransferHookRequired),
+            Some(ref policy) => {
+                if policy.allows(self.transfer_policy_list.contains(receiver_id)) {
+                    Ok(())
+                } else {
+                    Err(StoreError::ReceiverRestricted)
+                }
+            },
+        }
+    }
+
+    /// Panicking version of `check_transfer_policy`.
+    pub(crate) fn assert_transfer_policy(
+        &self,
+        receiver_id: &AccountId,
+    ) {
+        match self.check_transfer_policy(receiver_id) {
+            Ok(()) => {},
+            Err(StoreError::TransferHookRequired) => {
+                env::panic_str("transfer policy hook requires nft_transfer")
+            },
+            Err(_) => env::panic_str(&format!("{} may not receive tokens", receiver_id)),
+        }
+    }
+
+    pub(crate) fn transfer_policy_hook(&self) -> Option<AccountId> {
+        match self.transfer_policy {
+            Some(TransferPolicy::Hook { ref hook_id }) => Some(hook_id.clone()),
+            _ => None,
+        }
+    }
+
+    /// Lock `token` while `hook_id` decides whether it may be transferred
+    /// to `receiver_id`. The transfer is completed in `on_check_transfer`.
+    pub(crate) fn transfer_via_policy_hook(
+        &mut self,
+        token: &mut Token,
+        hook_id: AccountId,
+        receiver_id: AccountId,
+        memo: Option<String>,
+    ) -> Promise {
+        let owner_id = AccountId::new_unchecked(token.owner_id.to_string());
+        let token_id = U64(token.id);
+        self.lock_token(token);
+
+        ext_transfer_policy::nft_check_transfer(
+            token_id,
+            env::predecessor_account_id(),
+            owner_id.clone(),
+            receiver_id.clone(),
+            hook_id.clone(),
+            NO_DEPOSIT,
+            gas::NFT_CHECK_TRANSFER,
+        )
+        .then(store_self::on_check_transfer(
+            hook_id,
+            owner_id,
+            receiver_id,
+            token_id,
+            memo,
+            env::current_account_id(),
+            NO_DEPOSIT,
+            gas::ON_CHECK_TRANSFER,
+        ))
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::BTreeMap;
+
+use mintbase_deps::common::{
+    MintBatch,
+    MintRequest,
+    MintVoucher,
+    Royalty,
+    TokenMetadata,
+};
+use mintbase_deps::logging::{
+    log_nft_batch_mint,
+    log_storage_deposit,
+    StorageDepositAction,
+};
+use mintbase_deps::near_sdk::json_types::Base64VecU8;
+use mintbase_deps::near_sdk::{
+    self,
+    env,
+    near_bindgen,
+    AccountId,
+    Balance,
+    Promise,
+    PublicKey,
+};
+use mintbase_deps::token::Token;
+
+use crate::*;
+
+#[near_bindgen]
+impl MintbaseStore {
+    // -------------------------- change methods ---------------------------
+
+    /// Register the ed25519 `public_key` with which the calling minter signs
+    /// their `MintVoucher`s, or remove it to invalidate all vouchers that
+    /// have not yet been redeemed.
+    ///
+    /// Only minters may call this function.
+    #[payable]
+    pub fn set_voucher_key(
+        &mut self,
+        public_key: Option<PublicKey>,
+    ) {
+        near_sdk::assert_one_yocto();
+        let minter_id = env::predecessor_account_id();
+        assert!(
+            self.minters.contains(&minter_id),
+            "{} not a minter",
+            minter_id
+        );
+        match public_key {
+            Some(public_key) => self.voucher_keys.insert(&minter_id, &public_key),
+            None => self.voucher_keys.remove(&minter_id),
+        };
+    }
+
+    /// Mint the token described by `voucher` to the caller. `signature` must
+    /// be the ed25519 signature of `voucher.message()` by the key that the
+    /// voucher minter registered via `set_voucher_key`, and the minter must
+    /// still be a minter on this `Store`.
+    ///
+    /// The attached deposit must cover the voucher price, which is paid to
+    /// the minter, the minting fee, which is paid to the store owner, and
+    /// the storage of the minted token. Any excess is refunded. The mint
+    /// counts against the rate limit and allowance of the minter, and the
+    /// token gets the default royalty unless the voucher has a royalty.
+    #[payable]
+    pub fn nft_redeem_voucher(
+        &mut self,
+        voucher: MintVoucher,
+        signature: Base64VecU8,
+    ) {
+        assert_eq!(
+            voucher.store_id,
+            env::current_account_id(),
+            "voucher for another store"
+        );
+        assert!(
+            self.minters.contains(&voucher.minter),
+            "{} not a minter",
+            voucher.minter
+        );
+        self.assert_minter_grant_active(&voucher.minter);
+        let public_key = self
+            .voucher_keys
+            .get(&voucher.minter)
+            .expect("no voucher key");
+        assert!(
+            voucher.verify(&public_key, &signature.0),
+            "invalid signature"
+        );
+        assert!(
+            self.redeemed_vouchers.insert(&voucher.redemption_key()),
+            "voucher already redeemed"
+        );
+        self.assert_not_banned_from_minting(&env::predecessor_account_id());
+        self.admit_mint(&voucher.minter, 1);
+        let sets_payout = voucher.royalty_args.is_some();
+        let (royalty_args, minting_fee) =
+            self.prepare_mint(&voucher.minter, 1, voucher.royalty_args, sets_payout);
+
+        let (metadata, md_size) = TokenMetadata::from_with_size(voucher.metadata, 1);
+        let roy_len = royalty_args
+            .as_ref()
+            .map(|royalty_args| royalty_args.split_between.len() as u32)
+            .unwrap_or(0);
+        let price: Balance = voucher.price.into();
+        let storage_cost = self.storage_cost_to_mint(1, 1, md_size, roy_len, 1);
+        let required = price + storage_cost + minting_fee;
+        let attached_deposit = env::attached_deposit();
+        assert!(
+            attached_deposit >= required,
+            "attached: {}; need: {}",
+            attached_deposit,
+            required
+        );
+
+        let owner_id = env::predecessor_account_id();
+        let minter_id = voucher.minter;
+        let royalty = royalty_args.map(Royalty::new);
+        self.mint_single_token(owner_id.clone(), minter_id.clone(), metadata, royalty);
+
+        if price > 0 {
+            Promise::new(minter_id).transfer(price);
+        }
+        self.pay_minting_fee(minting_fee);
+        log_storage_deposit(
+            StorageDepositAction::Taken,
+            &owner_id,
+            storage_cost,
+            "nft_redeem_voucher",
+        );
+        let refund = attached_deposit - required;
+        if refund > 0 {
+            log_storage_deposit(
+                StorageDepositAction::Refunded,
+                &owner_id,
+                refund,
+                "nft_redeem_voucher",
+            );
+            Promise::new(owner_id).transfer(refund);
+        }
+    }
+
+    /// Mint the tokens described by `request` on behalf of its minter, such
+    /// that a relayer may submit the request and pay for the gas.
+    /// `signature` must be the ed25519 signature of `request.message()` by
+    /// the key that the minter registered via `set_voucher_key`.
+    ///
+    /// The same restrictions as for `nft_batch_mint` apply to the minter.
+    /// The relayer must attach the minting fee, if any, and the storage of
+    /// the minted tokens. Any excess is refunded to the relayer.
+    #[payable]
+    pub fn nft_mint_signed(
+        &mut self,
+        request: MintRequest,
+        signature: Base64VecU8,
+    ) {
+        assert!(request.num_to_mint > 0);
+        assert!(request.num_to_mint <= 125); // upper gas limit
+        assert_eq!(
+            request.store_id,
+            env::current_account_id(),
+            "request for another store"
+        );
+        assert!(request.deadline.is_before_timeout(), "request expired");
+        let minter_id = request.minter.clone();
+        let public_key = self.voucher_keys.get(&minter_id).expect("no voucher key");
+        assert!(
+            request.verify(&public_key, &signature.0),
+            "invalid signature"
+        );
+        assert!(
+            self.redeemed_vouchers.insert(&request.redemption_key()),
+            "request already used"
+        );
+        let sets_payout = request.royalty_args.is_some() || request.split_owners.is_some();
+        self.admit_mint(&minter_id, request.num_to_mint);
+        let (royalty_args, minting_fee) = self.prepare_mint(
+            &minter_id,
+            request.num_to_mint,
+            request.royalty_args,
+            sets_payout,
+        );
+        let (_, md_size) =
+            TokenMetadata::from_with_size(request.metadata.clone(), request.num_to_mint);
+        let roy_len = royalty_args
+            .as_ref()
+            .map(|royalty_args| royalty_args.split_between.len() as u32)
+            .unwrap_or(0);
+        let split_len = request
+            .split_owners
+            .as_ref()
+            .map(|split_owners| split_owners.len() as u32)
+            .unwrap_or(1);
+        let storage_cost =
+            self.storage_cost_to_mint(1, request.num_to_mint, md_size, roy_len, split_len);
+        let required = storage_cost + minting_fee;
+        let attached_deposit = env::attached_deposit();
+        assert!(
+            attached_deposit >= required,
+            "attached: {}; need: {}",
+            attached_deposit,
+            required
+        );
+
+        let first_token_id = self.tokens_minted;
+        self.tokens_minted += request.num_to_mint;
+        self.mint_batch_internal(
+            minter_id,
+            request.owner_id,
+            first_token_id,
+            request.metadata,
+            request.num_to_mint,
+            royalty_args,
+            BTreeMap::new(),
+            request.split_owners,
+            None,
+            None,
+            minting_fee,
+            "nft_mint_signed",
+        );
+        let relayer_id = env::predecessor_account_id();
+        log_storage_deposit(
+            StorageDepositAction::Taken,
+            &relayer_id,
+            storage_cost,
+            "nft_mint_signed",
+        );
+        let refund = attached_deposit - required;
+        if refund > 0 {
+            log_storage_deposit(
+                StorageDepositAction::Refunded,
+                &relayer_id,
+                refund,
+                "nft_mint_signed",
+            );
+            Promise::new(relayer_id).transfer(refund);
+        }
+    }
+
+    // -------------------------- view methods -----------------------------
+
+    /// Get the public key with which `account_id` signs their `MintVoucher`s.
+    pub fn get_voucher_key(
+        &self,
+        account_id: AccountId,
+    ) -> Option<PublicKey> {
+        self.voucher_keys.get(&account_id)
+    }
+
+    /// Check if the voucher with `nonce` signed by `minter` has already been
+    /// redeemed. Vouchers share their nonces with the minter's
+    /// `MintRequest`s, so this also tells whether a request with `nonce`
+    /// has been submitted via `nft_mint_signed`.
+    pub fn is_voucher_redeemed(
+        &self,
+        minter: AccountId,
+        nonce: u64,
+    ) -> bool {
+        self.redeemed_vouchers
+            .contains(&format!("{}:{}", minter, nonce))
+    }
+
+    // -------------------------- private methods --------------------------
+    // -------------------------- internal methods -------------------------
+
+    /// Mint a single token on behalf of `minter_id`, keeping track of it in
+    /// the same way as `nft_batch_mint`. Callers are responsible for
+    /// checking that the storage of the token is covered.
+    pub(crate) fn mint_single_token(
+        &mut self,
+        owner_id: AccountId,
+        minter_id: AccountId,
+        metadata: TokenMetadata,
+        royalty: Option<Royalty>,
+    ) {
+        let lookup_id: u64 = self.tokens_minted;
+        let royalty_id = royalty.map(|royalty| {
+            self.token_royalty.insert(&lookup_id, &(1, royalty));
+            lookup_id
+        });
+        self.token_metadata.insert(&lookup_id, &(1, metadata));
+        self.mint_copy(owner_id, minter_id, lookup_id, royalty_id, None);
+    }
+
+    /// Mint a single token on behalf of `minter_id` whose metadata and
+    /// royalty are already stored under `lookup_id` and `royalty_id`,
+    /// keeping track of it in the same way as `nft_batch_mint`. Callers are
+    /// responsible for counting the token on the stored entries, and for
+    /// checking that the storage of the token is covered. `edition_number`
+    /// must be given unless the token is the first sharing the entries.
+    pub(crate) fn mint_copy(
+        &mut self,
+        owner_id: AccountId,
+        minter_id: AccountId,
+        lookup_id: u64,
+        royalty_id: Option<u64>,
+        edition_number: Option<u64>,
+    ) {
+        let (_, metadata) = self.token_metadata.get(&lookup_id).unwrap();
+        let royalty = royalty_id.map(|royalty_id| self.token_royalty.get(&royalty_id).unwrap().1);
+        let meta_ref = metadata.reference.as_ref().map(|s| s.to_string());
+        let meta_extra = metadata.extra.as_ref().map(|s| s.to_string());
+        let media_hints = metadata.media_hints();
+        let event_metadata = self.mint_event_metadata.then(|| metadata.clone());
+
+        let mut owned_set = self.get_or_make_new_owner_set(&owner_id);
+        let token_id = self.tokens_minted;
+        let mut token = Token::new(
+            owner_id.clone(),
+            token_id,
+            lookup_id,
+            royalty_id,
+            None,
+            minter_id.clone(),
+        );
+        token.edition_number = edition_number;
+        owned_set.insert(&token_id);
+        self.tokens.insert(&token_id, &token);
+        self.tokens_minted += 1;
+        self.tokens_per_owner.insert(&owner_id, &owned_set);
+
+        // token ids are unique, and thus serve as nonce
+        let mint_id = MintBatch::generate_id(&minter_id, token_id);
+        self.insert_mint_batch(MintBatch {
+            mint_id: mint_id.clone(),
+            minter: minter_id.clone(),
+            owner_id: owner_id.clone(),
+            first_token_id: token_id,
+            last_token_id: token_id,
+            metadata_id: lookup_id,
+            royalty_id,
+            block_height: env::block_height(),
+        });
+
+        log_nft_batch_mint(
+            token_id,
+            token_id,
+            minter_id.as_ref(),
+            owner_id.as_ref(),
+            &royalty,
+            &None,
+            &meta_ref,
+            &meta_extra,
+            &mint_id,
+            &media_hints,
+            event_metadata.as_ref(),
+        );
+    }
+}
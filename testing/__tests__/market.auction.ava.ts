@@ -231,6 +231,17 @@ MARKET_WORKSPACE.test(
       test,
       (acceptOfferCall as TransactionResult).logs,
       [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "refunded",
+            account_id: alice.accountId,
+            amount: mNEAR(0.8).toString(),
+            method: "nft_transfer_payout",
+          }),
+        },
         {
           standard: "nep171",
           version: "1.0.0",
@@ -245,6 +256,16 @@ MARKET_WORKSPACE.test(
             },
           ],
         },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_payout",
+          data: JSON.stringify({
+            token_id: "0",
+            balance: NEAR(1.95).toString(),
+            payout: createPayout([[alice, NEAR(1.95).toString()]]),
+          }),
+        },
         {
           standard: "nep171",
           version: "1.0.0",
@@ -268,10 +289,11 @@ MARKET_WORKSPACE.test(
     );
 
     await Promise.all([
-      // check chain state: alice has received her share
+      // check chain state: alice has received her share and the deposit of
+      // the approval
       assertBalanceChange(
         test,
-        { account: alice, ref: aliceBalance2, diff: NEAR(1.95) },
+        { account: alice, ref: aliceBalance2, diff: mNEAR(1950.8) },
         "accepting auction offer"
       ),
       // check chain state: market has transferred some funds but kept its fee
@@ -187,6 +187,17 @@ MARKET_WORKSPACE.test(
       test,
       (makeOfferCall as TransactionResult).logs.slice(1),
       [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "refunded",
+            account_id: alice.accountId,
+            amount: mNEAR(0.8).toString(),
+            method: "nft_transfer_payout",
+          }),
+        },
         {
           standard: "nep171",
           version: "1.0.0",
@@ -201,6 +212,16 @@ MARKET_WORKSPACE.test(
             },
           ],
         },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_payout",
+          data: JSON.stringify({
+            token_id: "0",
+            balance: NEAR(0.975).toString(),
+            payout: createPayout([[alice, NEAR(0.975).toString()]]),
+          }),
+        },
         {
           standard: "nep171",
           version: "1.0.0",
@@ -213,6 +234,17 @@ MARKET_WORKSPACE.test(
             payout: createPayout([[alice, NEAR(0.975).toString()]]),
           }),
         },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "refunded",
+            account_id: alice.accountId,
+            amount: mNEAR(0.8).toString(),
+            method: "nft_transfer_payout",
+          }),
+        },
         {
           standard: "nep171",
           version: "1.0.0",
@@ -227,6 +259,16 @@ MARKET_WORKSPACE.test(
             },
           ],
         },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_payout",
+          data: JSON.stringify({
+            token_id: "1",
+            balance: mNEAR(1462.5).toString(),
+            payout: createPayout([[alice, mNEAR(1462.5).toString()]]),
+          }),
+        },
         {
           standard: "nep171",
           version: "1.0.0",
@@ -268,7 +310,8 @@ MARKET_WORKSPACE.test(
       [
         // 30 mNEAR extra gas costs for bob
         { account: bob, ref: bobBalance0, diff: NEAR(-2.53) },
-        { account: alice, ref: aliceBalance0, diff: mNEAR(975 * 2.5) },
+        // including the refunded deposits of the approvals
+        { account: alice, ref: aliceBalance0, diff: mNEAR(975 * 2.5 + 1.6) },
         // FIXME::market::low: where do the 15 mNEAR come from?
         { account: market, ref: marketBalance0, diff: mNEAR(25 * 2.5 + 15) },
       ],
@@ -77,8 +77,9 @@ MARKET_WORKSPACE.test(
     // check event logs
     assertEventLogs(
       test,
-      // we already tested the approval event on the store, so skip that
-      (auctionApproveCall as TransactionResult).logs.slice(1),
+      // we already tested the approval and storage deposit events on the
+      // store, so skip those
+      (auctionApproveCall as TransactionResult).logs.slice(3),
       [
         {
           standard: "nep171",
@@ -135,8 +136,9 @@ MARKET_WORKSPACE.test(
     // check event logs
     assertEventLogs(
       test,
-      // we already tested the revoke event on the store, so skip that
-      (auctionRevokeCall as TransactionResult).logs.slice(1),
+      // we already tested the revoke and refund events on the store, so skip
+      // those
+      (auctionRevokeCall as TransactionResult).logs.slice(2),
       [],
       "revoke auction listing"
     );
@@ -167,8 +169,9 @@ MARKET_WORKSPACE.test(
     // check event logs
     assertEventLogs(
       test,
-      // we already tested the approval event, so skip that
-      (buynowApproveCall as TransactionResult).logs.slice(1),
+      // we already tested the approval and storage deposit events, so skip
+      // those
+      (buynowApproveCall as TransactionResult).logs.slice(3),
       [
         {
           standard: "nep171",
@@ -231,8 +234,9 @@ MARKET_WORKSPACE.test(
     // check event logs
     assertEventLogs(
       test,
-      // we already tested the revoke event on the store, so skip that
-      (buynowRevokeCall as TransactionResult).logs.slice(1),
+      // we already tested the revoke and refund events on the store, so skip
+      // those
+      (buynowRevokeCall as TransactionResult).logs.slice(2),
       [],
       "revoke auction listing"
     );
@@ -259,7 +263,8 @@ MARKET_WORKSPACE.test(
     // check event logs
     assertEventLogs(
       test,
-      (batchApproveLogs as TransactionResult).logs.slice(1),
+      // skip the approval and storage deposit events of the store
+      (batchApproveLogs as TransactionResult).logs.slice(2),
       [
         {
           standard: "nep171",
@@ -1,4 +1,4 @@
-import { TransactionResult } from "near-workspaces-ava";
+import { BN, TransactionResult } from "near-workspaces-ava";
 import {
   assertEventLogs,
   failPromiseRejection,
@@ -11,6 +11,9 @@ import {
   Tgas,
   getBalance,
   assertBalanceChange,
+  getMintId,
+  STORE_WORKSPACE,
+  assertContractPanics,
 } from "./test-utils";
 
 MARKET_WORKSPACE.test(
@@ -47,6 +50,12 @@ MARKET_WORKSPACE.test(
         { attachedDeposit: "1" }
       )
       .catch(failPromiseRejection(test, "minting with royalties"));
+    const mintStorage = await store.view("estimate_mint_cost", {
+      metadata: {},
+      num_to_mint: 1,
+      roy_len: 2,
+      split_len: 0,
+    });
 
     // check event logs
     // TODO::store::low: format seems clunky
@@ -77,10 +86,22 @@ MARKET_WORKSPACE.test(
                 meta_id: null,
                 meta_extra: null,
                 minter: alice.accountId,
+                mint_id: getMintId((mintCall as TransactionResult).logs[0]),
               }),
             },
           ],
         },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "consumed",
+            account_id: alice.accountId,
+            amount: mintStorage,
+            method: "nft_batch_mint",
+          }),
+        },
       ],
       "minting"
     );
@@ -146,10 +167,11 @@ MARKET_WORKSPACE.test(
       .catch(failPromiseRejection(test, "making offer"));
     // events have been checked previously -> no need here
 
-    // check chain state: alice received 0.75 * 0.975 NEAR
+    // check chain state: alice received 0.75 * 0.975 NEAR, and the deposit
+    // of the approval
     await assertBalanceChange(
       test,
-      { account: alice, ref: aliceBalance0, diff: mNEAR(0.75 * 975) },
+      { account: alice, ref: aliceBalance0, diff: mNEAR(0.75 * 975 + 0.8) },
       "Checking first royalties payout"
     );
     // check chain state: bob received 0.25 * 0.975 NEAR
@@ -223,11 +245,700 @@ MARKET_WORKSPACE.test(
       { account: bob, ref: bobBalance1, diff: mNEAR(0.25 * 975) },
       "Checking second royalties payout"
     );
-    // check chain state: bob received 0.50 * 0.975 NEAR
+    // check chain state: carol received 0.50 * 0.975 NEAR, and the deposit
+    // of the approval
     await assertBalanceChange(
       test,
-      { account: carol, ref: carolBalance1, diff: mNEAR(0.5 * 975) },
+      { account: carol, ref: carolBalance1, diff: mNEAR(0.5 * 975 + 0.8) },
       "Checking second royalties payout"
     );
   }
 );
+
+STORE_WORKSPACE.test(
+  "ft-payouts",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        {
+          owner_id: bob.accountId,
+          metadata: {},
+          num_to_mint: 1,
+          royalty_args: {
+            split_between: { [carol.accountId]: 10000 },
+            percentage: 1000,
+          },
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    // 1 USDC has 6 decimals, which would be truncated by NEAR payouts
+    test.deepEqual(
+      await store.view("nft_payout_ft", {
+        token_id: "0",
+        ft_contract: "usdc.test.near",
+        amount: "1000000",
+        max_len_payout: 10,
+      }),
+      {
+        payout: { [bob.accountId]: "900000", [carol.accountId]: "100000" },
+        ft_contract: "usdc.test.near",
+      }
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "payout-remainder",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    await alice
+      .call(
+        store,
+        "set_split_owners",
+        {
+          token_ids: ["0"],
+          split_between: {
+            [alice.accountId]: 3333,
+            [bob.accountId]: 3333,
+            [carol.accountId]: 3334,
+          },
+        },
+        { attachedDeposit: mNEAR(2.4) }
+      )
+      .catch(failPromiseRejection(test, "setting splits"));
+
+    // the remainder goes to the largest share
+    test.deepEqual(
+      await store.view("nft_payout_ft", {
+        token_id: "0",
+        ft_contract: "usdc.test.near",
+        amount: "10",
+        max_len_payout: 10,
+      }),
+      {
+        payout: {
+          [alice.accountId]: "3",
+          [bob.accountId]: "3",
+          [carol.accountId]: "4",
+        },
+        ft_contract: "usdc.test.near",
+      }
+    );
+    test.deepEqual(
+      await store.view("nft_payout", {
+        token_id: "0",
+        balance: "10",
+        max_len_payout: 10,
+      }),
+      {
+        payout: {
+          [alice.accountId]: "0",
+          [bob.accountId]: "0",
+          [carol.accountId]: "10",
+        },
+      }
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "per-copy-royalties",
+  async (test, { alice, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        {
+          owner_id: alice.accountId,
+          metadata: {},
+          num_to_mint: 3,
+          royalty_args: {
+            "1": {
+              split_between: { [carol.accountId]: 10000 },
+              percentage: 1000,
+            },
+          },
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    test.is(await store.view("get_token_royalty", { token_id: "0" }), null);
+    test.deepEqual(
+      await store.view("get_token_royalty", { token_id: "1" }),
+      {
+        split_between: { [carol.accountId]: { numerator: 10000 } },
+        percentage: { numerator: 1000 },
+      }
+    );
+    test.is(await store.view("get_token_royalty", { token_id: "2" }), null);
+    test.deepEqual(
+      await store.view("nft_payout_ft", {
+        token_id: "1",
+        ft_contract: "usdc.test.near",
+        amount: "1000",
+        max_len_payout: 10,
+      }),
+      {
+        payout: { [alice.accountId]: "900", [carol.accountId]: "100" },
+        ft_contract: "usdc.test.near",
+      }
+    );
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "update_token_royalty",
+            { lookup_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'batch has per-copy royalties'",
+        "Alice tried to update a batch with per-copy royalties",
+      ],
+    ]);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "validate-payout-args",
+  async (test, { alice, bob, store }) => {
+    test.deepEqual(
+      await store.view("validate_payout_args", {
+        royalty_args: {
+          split_between: { [alice.accountId]: 10000 },
+          percentage: 1000,
+        },
+        split_between: { [alice.accountId]: 5000, [bob.accountId]: 5000 },
+      }),
+      { valid: true, errors: [] }
+    );
+
+    test.deepEqual(
+      await store.view("validate_payout_args", {
+        royalty_args: {
+          split_between: { "Not An Account": 5000, [alice.accountId]: 4000 },
+          percentage: 6000,
+        },
+        split_between: { [bob.accountId]: 10000 },
+      }),
+      {
+        valid: false,
+        errors: [
+          "royalty: percentage 6000 must be <= 5000",
+          "royalty: invalid account id Not An Account",
+          "royalty: sum not 10_000: 9000",
+          "split_between: needs at least 2 accounts",
+        ],
+      }
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "payout-dust-threshold",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        {
+          owner_id: alice.accountId,
+          metadata: {},
+          num_to_mint: 1,
+          royalty_args: {
+            split_between: { [carol.accountId]: 10000 },
+            percentage: 100,
+          },
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    const payout = () =>
+      store.view("nft_payout", {
+        token_id: "0",
+        balance: "10000000000",
+        max_len_payout: 10,
+      });
+    test.deepEqual(await payout(), {
+      payout: {
+        [alice.accountId]: "9900000000",
+        [carol.accountId]: "100000000",
+      },
+    });
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "set_payout_dust_threshold",
+            { threshold: "1000000000" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Bob tried to set the dust threshold",
+      ],
+    ]);
+    await alice
+      .call(
+        store,
+        "set_payout_dust_threshold",
+        { threshold: "1000000000" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "setting dust threshold"));
+    test.is(await store.view("get_payout_dust_threshold"), "1000000000");
+    test.deepEqual(await payout(), {
+      payout: { [alice.accountId]: "10000000000" },
+    });
+  }
+);
+
+STORE_WORKSPACE.test(
+  "revenue-vault",
+  async (test, { bob, carol, store }) => {
+    const payout = {
+      [carol.accountId]: NEAR(0.3).toString(),
+      "ghost.test.near": NEAR(0.2).toString(),
+    };
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "deposit_revenue",
+            { payout },
+            { attachedDeposit: NEAR(0.4) }
+          );
+        },
+        `panicked at 'attached: ${NEAR(0.4).toString()}; need: `,
+        "Bob tried to deposit revenue without covering it",
+      ],
+    ]);
+
+    const depositCall = await bob
+      .call_raw(
+        store,
+        "deposit_revenue",
+        { payout },
+        { attachedDeposit: NEAR(1) }
+      )
+      .catch(failPromiseRejection(test, "depositing revenue"));
+    // the order of receivers in the event data is not deterministic
+    const depositLog = (depositCall as TransactionResult).logs[0];
+    const event = JSON.parse(depositLog.slice(12));
+    test.is(event.event, "nft_deposit_revenue");
+    test.deepEqual(JSON.parse(event.data), { payout });
+    test.is(
+      await store.view("get_claimable_revenue", {
+        account_id: "ghost.test.near",
+      }),
+      NEAR(0.2).toString()
+    );
+
+    test.is(
+      await carol
+        .call(store, "claim_revenue", {})
+        .catch(failPromiseRejection(test, "claiming revenue")),
+      NEAR(0.3).toString()
+    );
+    test.is(
+      await store.view("get_revenue_balance", { account_id: carol }),
+      null
+    );
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await carol.call(store, "claim_revenue", {});
+        },
+        "panicked at 'no revenue'",
+        "Carol tried to claim revenue twice",
+      ],
+    ]);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "revenue-vesting",
+  async (test, { alice, bob, carol, store }) => {
+    // a year of vesting that started a day ago
+    const day = 24 * 60 * 60 * 1000;
+    const start = (Date.now() - day) * 1000000;
+    const end = (Date.now() + 364 * day) * 1000000;
+    await alice
+      .call(
+        store,
+        "set_payout_vesting",
+        { account_id: carol.accountId, schedule: { start, cliff: start, end } },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "setting payout vesting"));
+    await bob
+      .call(
+        store,
+        "deposit_revenue",
+        { payout: { [carol.accountId]: NEAR(10).toString() } },
+        { attachedDeposit: NEAR(11) }
+      )
+      .catch(failPromiseRejection(test, "depositing revenue"));
+
+    // about 1/365 of the revenue has vested
+    const total = new BN(NEAR(10).toString());
+    const claimed = new BN(
+      await carol
+        .call(store, "claim_revenue", {})
+        .catch(failPromiseRejection(test, "claiming revenue"))
+    );
+    test.true(claimed.gte(total.divn(365)), `claimed ${claimed}`);
+    test.true(claimed.lte(total.divn(360)), `claimed ${claimed}`);
+    const balance: any = await store.view("get_revenue_balance", {
+      account_id: carol,
+    });
+    test.is(balance.accrued, total.toString());
+    test.is(balance.withdrawn, claimed.toString());
+  }
+);
+
+STORE_WORKSPACE.test(
+  "payout-truncation",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        {
+          owner_id: alice.accountId,
+          metadata: {},
+          num_to_mint: 1,
+          royalty_args: {
+            split_between: {
+              [carol.accountId]: 5000,
+              [bob.accountId]: 3000,
+              "ghost.test.near": 2000,
+            },
+            percentage: 1000,
+          },
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    // the smaller royalty shares are folded into the owner's cut
+    test.deepEqual(
+      await store.view("nft_payout", {
+        token_id: "0",
+        balance: "10000000000",
+        max_len_payout: 2,
+        truncate: true,
+      }),
+      {
+        payout: {
+          [alice.accountId]: "9500000000",
+          [carol.accountId]: "500000000",
+        },
+      }
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "basis-point-royalties",
+  async (test, { alice, bob, store }) => {
+    const royaltyBps = { [alice.accountId]: 500, [bob.accountId]: 250 };
+    test.deepEqual(
+      await store.view("canonical_royalty_args", {
+        royalty_args: { royalty_bps: royaltyBps },
+      }),
+      {
+        split_between: { [alice.accountId]: 6667, [bob.accountId]: 3333 },
+        percentage: 750,
+      }
+    );
+    test.deepEqual(
+      await store.view("canonical_split_between", {
+        split_between: {
+          split_bps: { [alice.accountId]: 6000, [bob.accountId]: 4000 },
+        },
+      }),
+      { [alice.accountId]: 6000, [bob.accountId]: 4000 }
+    );
+
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        {
+          owner_id: alice.accountId,
+          metadata: {},
+          num_to_mint: 2,
+          royalty_args: { royalty_bps: royaltyBps },
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    test.deepEqual(await store.view("get_token_royalty", { token_id: "0" }), {
+      split_between: {
+        [alice.accountId]: { numerator: 6667 },
+        [bob.accountId]: { numerator: 3333 },
+      },
+      percentage: { numerator: 750 },
+    });
+
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    await alice
+      .call(
+        store,
+        "set_split_owners",
+        {
+          token_ids: ["2"],
+          split_between: {
+            split_bps: { [alice.accountId]: 6000, [bob.accountId]: 4000 },
+          },
+        },
+        { attachedDeposit: mNEAR(1.6) }
+      )
+      .catch(failPromiseRejection(test, "setting splits"));
+    test.deepEqual(
+      await store.view("nft_payout", {
+        token_id: "2",
+        balance: "10000000000",
+        max_len_payout: 10,
+      }),
+      {
+        payout: {
+          [alice.accountId]: "6000000000",
+          [bob.accountId]: "4000000000",
+        },
+      }
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "aggregate-child-royalties",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        {
+          owner_id: alice.accountId,
+          metadata: {},
+          num_to_mint: 1,
+          royalty_args: {
+            split_between: { [bob.accountId]: 10000 },
+            percentage: 1000,
+          },
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    test.false(await store.view("get_aggregate_child_royalties"));
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "set_aggregate_child_royalties",
+            { enabled: true },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Bob tried to enable child royalty aggregation",
+      ],
+    ]);
+    await alice
+      .call(
+        store,
+        "set_aggregate_child_royalties",
+        { enabled: true },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "enabling aggregation"));
+    test.true(await store.view("get_aggregate_child_royalties"));
+
+    // without composed children, the payout is unaffected
+    test.deepEqual(
+      await store.view("get_child_royalties", { token_id: "0" }),
+      []
+    );
+    test.deepEqual(
+      await store.view("nft_payout", {
+        token_id: "0",
+        balance: "10000000000",
+        max_len_payout: 10,
+      }),
+      {
+        payout: {
+          [alice.accountId]: "9000000000",
+          [bob.accountId]: "1000000000",
+        },
+      }
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "transfer-payout-memo",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    const transferCall = await alice
+      .call_raw(
+        store,
+        "nft_transfer_payout",
+        {
+          receiver_id: bob.accountId,
+          token_id: "0",
+          approval_id: 0,
+          balance: "10000000000",
+          max_len_payout: 10,
+          memo: "sale-42",
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring with payout"));
+    assertEventLogs(
+      test,
+      (transferCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_transfer",
+          data: [
+            {
+              authorized_id: null,
+              memo: "sale-42",
+              new_owner_id: bob.accountId,
+              old_owner_id: alice.accountId,
+              token_ids: ["0"],
+            },
+          ],
+        },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_payout",
+          data: JSON.stringify({
+            token_id: "0",
+            balance: "10000000000",
+            payout: { [alice.accountId]: "10000000000" },
+            memo: "sale-42",
+          }),
+        },
+      ],
+      "transferring with payout"
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "payout-args-errors",
+  async (test, { alice, bob, store }) => {
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_batch_mint",
+            {
+              owner_id: alice.accountId,
+              metadata: {},
+              num_to_mint: 1,
+              royalty_args: {
+                split_between: {
+                  [alice.accountId]: 4000,
+                  [bob.accountId]: 5000,
+                },
+                percentage: 1000,
+              },
+            },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'invalid royalty: numerators add up to 9000 instead of 10,000'",
+        "Alice tried to mint with an incomplete royalty",
+      ],
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_batch_mint",
+            {
+              owner_id: alice.accountId,
+              metadata: {},
+              num_to_mint: 1,
+              royalty_args: {
+                split_between: { [bob.accountId]: 10000 },
+                percentage: 6000,
+              },
+            },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'invalid royalty: percentage 6000 not between 1 and 5000'",
+        "Alice tried to mint with an excessive royalty",
+      ],
+    ]);
+
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "set_split_owners",
+            {
+              token_ids: ["0"],
+              split_between: { [alice.accountId]: 6000, [bob.accountId]: 6000 },
+            },
+            { attachedDeposit: mNEAR(1.6) }
+          );
+        },
+        "panicked at 'invalid split owners: numerators add up to 12000 instead of 10,000'",
+        "Alice tried to set splits exceeding the sale",
+      ],
+    ]);
+  }
+);
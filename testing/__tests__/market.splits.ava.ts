@@ -12,6 +12,7 @@ import {
   createPayoutNumerators,
   assertEventLogs,
   assertContractPanics,
+  STORE_WORKSPACE,
 } from "./test-utils";
 
 MARKET_WORKSPACE.test(
@@ -116,6 +117,17 @@ MARKET_WORKSPACE.test(
             token_ids: ["0"],
           }),
         },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "taken",
+            account_id: alice.accountId,
+            amount: mNEAR(1.6).toString(),
+            method: "set_split_owners",
+          }),
+        },
       ],
       "setting splits"
     );
@@ -160,10 +172,15 @@ MARKET_WORKSPACE.test(
       .catch(failPromiseRejection(test, "making offer"));
     // events have been checked previously -> no need here
 
-    // check chain state: alice received 0.75 * 0.975 NEAR
+    // check chain state: alice received 0.6 * 0.975 NEAR, and the deposits
+    // of the approval and the splits
     await assertBalanceChange(
       test,
-      { account: alice, ref: aliceBalance0, diff: mNEAR(0.6 * 975) },
+      {
+        account: alice,
+        ref: aliceBalance0,
+        diff: mNEAR(0.6 * 975 + 0.8 + 1.6),
+      },
       "Checking royalties (without splits)"
     );
     // check chain state: bob received 0.25 * 0.975 NEAR
@@ -222,11 +239,377 @@ MARKET_WORKSPACE.test(
       { account: bob, ref: bobBalance1, diff: NEAR(0) },
       "Checking royalties (without splits)"
     );
-    // check chain state: carol received 0.975 NEAR
+    // check chain state: carol received 0.975 NEAR, and the deposit of the
+    // approval
     await assertBalanceChange(
       test,
-      { account: carol, ref: carolBalance1, diff: mNEAR(975) },
+      { account: carol, ref: carolBalance1, diff: mNEAR(975 + 0.8) },
       "Checking royalties (without splits)"
     );
   }
 );
+
+STORE_WORKSPACE.test(
+  "clear-split-owners",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    await alice
+      .call(
+        store,
+        "set_split_owners",
+        {
+          token_ids: ["0"],
+          split_between: { [alice.accountId]: 6000, [bob.accountId]: 4000 },
+        },
+        { attachedDeposit: mNEAR(1.6) }
+      )
+      .catch(failPromiseRejection(test, "setting splits"));
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "clear_split_owners",
+            { token_ids: ["0"] },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: token.is_pred_owner()'",
+        "Bob tried to clear splits on Alice's token",
+      ],
+    ]);
+
+    const clearCall = await alice
+      .call_raw(
+        store,
+        "clear_split_owners",
+        { token_ids: ["0"] },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "clearing splits"));
+    assertEventLogs(
+      test,
+      (clearCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_clear_split_owners",
+          data: JSON.stringify({ token_ids: ["0"] }),
+        },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "refunded",
+            account_id: alice.accountId,
+            amount: mNEAR(1.6).toString(),
+            method: "clear_split_owners",
+          }),
+        },
+      ],
+      "clearing splits"
+    );
+    test.is(
+      ((await store.view("nft_token", { token_id: "0" })) as any).split_owners,
+      null
+    );
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "clear_split_owners",
+            { token_ids: ["0"] },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'no split owners'",
+        "Alice tried to clear splits twice",
+      ],
+    ]);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "sticky-split-owners",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 2 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    const splitBetween = { [alice.accountId]: 6000, [carol.accountId]: 4000 };
+    await alice
+      .call(
+        store,
+        "set_split_owners",
+        { token_ids: ["0"], split_between: splitBetween, sticky: true },
+        { attachedDeposit: mNEAR(1.6) }
+      )
+      .catch(failPromiseRejection(test, "setting sticky splits"));
+    await alice
+      .call(
+        store,
+        "set_split_owners",
+        { token_ids: ["1"], split_between: splitBetween },
+        { attachedDeposit: mNEAR(1.6) }
+      )
+      .catch(failPromiseRejection(test, "setting splits"));
+
+    await alice
+      .call(
+        store,
+        "nft_batch_transfer",
+        {
+          token_ids: [
+            ["0", bob],
+            ["1", bob],
+          ],
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring"));
+
+    const splitsOf = async (token_id: string) =>
+      ((await store.view("nft_token", { token_id })) as any).split_owners;
+    test.deepEqual(await splitsOf("0"), {
+      split_between: {
+        [alice.accountId]: { numerator: 6000 },
+        [carol.accountId]: { numerator: 4000 },
+      },
+      sticky: true,
+    });
+    test.is(await splitsOf("1"), null);
+
+    // the new owner may neither clear nor replace sticky splits
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "clear_split_owners",
+            { token_ids: ["0"] },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'sticky split owners set by another account'",
+        "Bob tried to clear sticky splits set by Alice",
+      ],
+      [
+        async () => {
+          await bob.call(
+            store,
+            "replace_split_owners",
+            {
+              token_ids: ["0"],
+              split_between: { [bob.accountId]: 5000, [carol.accountId]: 5000 },
+            },
+            { attachedDeposit: mNEAR(1.6) }
+          );
+        },
+        "panicked at 'sticky split owners set by another account'",
+        "Bob tried to replace sticky splits set by Alice",
+      ],
+    ]);
+
+    // once Alice owns the token again, she may clear them and is refunded
+    await bob
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: alice, token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring back"));
+    const clearCall = await alice
+      .call_raw(
+        store,
+        "clear_split_owners",
+        { token_ids: ["0"] },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "clearing sticky splits"));
+    const refundLog = (clearCall as TransactionResult).logs[1];
+    test.deepEqual(JSON.parse(JSON.parse(refundLog.slice(12)).data), {
+      action: "refunded",
+      account_id: alice.accountId,
+      amount: mNEAR(1.6).toString(),
+      method: "clear_split_owners",
+    });
+    test.is(await splitsOf("0"), null);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "replace-split-owners",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    await alice
+      .call(
+        store,
+        "set_split_owners",
+        {
+          token_ids: ["0"],
+          split_between: { [alice.accountId]: 5000, [bob.accountId]: 5000 },
+        },
+        { attachedDeposit: mNEAR(1.6) }
+      )
+      .catch(failPromiseRejection(test, "setting splits"));
+
+    const threeWaySplit = {
+      [alice.accountId]: 4000,
+      [bob.accountId]: 3000,
+      [carol.accountId]: 3000,
+    };
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "replace_split_owners",
+            { token_ids: ["0"], split_between: threeWaySplit },
+            { attachedDeposit: "1" }
+          );
+        },
+        `panicked at 'insuf. deposit. Need: ${mNEAR(0.8)}'`,
+        "Alice tried to grow splits without covering storage",
+      ],
+    ]);
+    await alice
+      .call(
+        store,
+        "replace_split_owners",
+        { token_ids: ["0"], split_between: threeWaySplit },
+        { attachedDeposit: mNEAR(0.8) }
+      )
+      .catch(failPromiseRejection(test, "growing splits"));
+    test.is(
+      Object.keys(
+        ((await store.view("nft_token", { token_id: "0" })) as any)
+          .split_owners.split_between
+      ).length,
+      3
+    );
+
+    const shrinkCall = await alice
+      .call_raw(
+        store,
+        "replace_split_owners",
+        {
+          token_ids: ["0"],
+          split_between: { [bob.accountId]: 5000, [carol.accountId]: 5000 },
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "shrinking splits"));
+    const refundLog = (shrinkCall as TransactionResult).logs[1];
+    test.deepEqual(JSON.parse(JSON.parse(refundLog.slice(12)).data), {
+      action: "refunded",
+      account_id: alice.accountId,
+      amount: mNEAR(0.8).toString(),
+      method: "replace_split_owners",
+    });
+  }
+);
+
+STORE_WORKSPACE.test(
+  "burn-quorum-splits",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    await alice
+      .call(
+        store,
+        "set_split_owners",
+        {
+          token_ids: ["0"],
+          split_between: { [alice.accountId]: 6000, [carol.accountId]: 4000 },
+          burn_quorum: 2,
+        },
+        { attachedDeposit: mNEAR(1.6) }
+      )
+      .catch(failPromiseRejection(test, "setting splits"));
+
+    // transferring the token does not escape the burn quorum
+    await alice
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: bob, token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring"));
+    test.like(await store.view("nft_token", { token_id: "0" }), {
+      owner_id: bob.accountId,
+      split_owners: { burn_quorum: 2 },
+    });
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "nft_batch_burn",
+            { token_ids: ["0"] },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'burn requires 2 split owner confirmations, got 0'",
+        "Bob tried to burn without confirmations",
+      ],
+    ]);
+
+    // once both split owners confirm, the new owner may burn the token
+    await bob
+      .call(store, "propose_burn", { token_id: "0" }, { attachedDeposit: "1" })
+      .catch(failPromiseRejection(test, "proposing burn"));
+    for (const account of [alice, carol]) {
+      await account
+        .call(
+          store,
+          "confirm_burn",
+          { token_id: "0" },
+          { attachedDeposit: "1" }
+        )
+        .catch(failPromiseRejection(test, "confirming burn"));
+    }
+    await bob
+      .call(
+        store,
+        "nft_batch_burn",
+        { token_ids: ["0"] },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "burning"));
+    test.is(
+      await store.view("nft_supply_for_owner", { account_id: bob.accountId }),
+      "0"
+    );
+  }
+);
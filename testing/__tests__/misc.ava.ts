@@ -5,11 +5,16 @@ import {
   mNEAR,
   uNEAR,
   nNEAR,
+  Tgas,
   STORE_WORKSPACE,
+  assertEventLog,
   assertEventLogs,
+  getMintMemo,
   failPromiseRejection,
   assertMinters,
   assertContractPanics,
+  DEPLOY_STORE_RENT,
+  DEPLOY_STORE_GAS,
 } from "./test-utils";
 
 // No need to fire up the chain for testing my utils
@@ -223,6 +228,2024 @@ STORE_WORKSPACE.test(
   }
 );
 
+STORE_WORKSPACE.test(
+  "ownership::clone-store",
+  async (test, { root, alice, bob, factory, store }) => {
+    await alice
+      .call(
+        store,
+        "grant_minter",
+        { account_id: bob },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "granting minter rights"));
+
+    await alice
+      .call(
+        factory,
+        "clone_store",
+        {
+          store_id: store.accountId,
+          metadata: {
+            spec: "nft-1.0.0",
+            name: "aliceclone",
+            symbol: "ALICE",
+          },
+          include: { minters: true, config: false },
+        },
+        { attachedDeposit: DEPLOY_STORE_RENT, gas: DEPLOY_STORE_GAS }
+      )
+      .catch(failPromiseRejection(test, "cloning store"));
+    const clone = root.getFullAccount(`aliceclone.${factory.accountId}`);
+
+    // minters have been copied over
+    await assertMinters(
+      { test, store: clone },
+      [
+        [alice, true],
+        [bob, true],
+      ],
+      "cloning store"
+    );
+
+    // only the store owner may clone the store
+    const bobCloneCall = await bob
+      .call_raw(
+        factory,
+        "clone_store",
+        {
+          store_id: store.accountId,
+          metadata: {
+            spec: "nft-1.0.0",
+            name: "bobclone",
+            symbol: "BOB",
+          },
+          include: { minters: true, config: true },
+        },
+        { attachedDeposit: DEPLOY_STORE_RENT, gas: DEPLOY_STORE_GAS }
+      )
+      .catch(failPromiseRejection(test, "cloning store as non-owner"));
+    test.true(
+      (bobCloneCall as TransactionResult).logs.includes("failed store cloning"),
+      "Non-owner was able to clone the store"
+    );
+
+    await assertContractPanics(test, [
+      // cannot clone accounts that were not created by the factory
+      [
+        async () => {
+          await alice.call(
+            factory,
+            "clone_store",
+            {
+              store_id: alice.accountId,
+              metadata: {
+                spec: "nft-1.0.0",
+                name: "aliceclone2",
+                symbol: "ALICE",
+              },
+              include: { minters: true, config: true },
+            },
+            { attachedDeposit: DEPLOY_STORE_RENT, gas: DEPLOY_STORE_GAS }
+          );
+        },
+        "panicked at 'Not a store of this factory'",
+        "Alice tried to clone an account that is not a store",
+      ],
+    ]);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "ownership::clone-store-config",
+  async (test, { root, alice, factory, store }) => {
+    await alice
+      .call(
+        store,
+        "set_transfer_fee",
+        { transfer_fee: "1000" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "setting transfer fee"));
+    await alice
+      .call(
+        store,
+        "set_max_compose_depth",
+        { max_compose_depth: 2 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "setting max compose depth"));
+
+    await alice
+      .call(
+        factory,
+        "clone_store",
+        {
+          store_id: store.accountId,
+          metadata: {
+            spec: "nft-1.0.0",
+            name: "aliceconfig",
+            symbol: "ALICE",
+          },
+          include: { minters: false, config: true },
+        },
+        { attachedDeposit: DEPLOY_STORE_RENT, gas: DEPLOY_STORE_GAS }
+      )
+      .catch(failPromiseRejection(test, "cloning store"));
+    const clone = root.getFullAccount(`aliceconfig.${factory.accountId}`);
+
+    // settings have been copied over
+    test.is(await clone.view("get_transfer_fee"), "1000");
+    test.is(await clone.view("get_max_compose_depth"), 2);
+  }
+);
+
+STORE_WORKSPACE.test("rate-limits", async (test, { alice, bob, store }) => {
+  await alice
+    .call(
+      store,
+      "grant_minter",
+      { account_id: bob },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "granting minter rights"));
+  await alice
+    .call(
+      store,
+      "set_rate_limits",
+      { mints_per_hour: 2 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "setting rate limits"));
+
+  await bob
+    .call(
+      store,
+      "nft_batch_mint",
+      { owner_id: bob.accountId, metadata: {}, num_to_mint: 2 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting within rate limit"));
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await bob.call(
+          store,
+          "nft_batch_mint",
+          { owner_id: bob.accountId, metadata: {}, num_to_mint: 1 },
+          { attachedDeposit: "1" }
+        );
+      },
+      "rate limit exceeded: 2 mints per hour",
+      "Bob tried to mint beyond the rate limit",
+    ],
+  ]);
+
+  // the store owner is exempt
+  await alice
+    .call(
+      store,
+      "nft_batch_mint",
+      { owner_id: alice.accountId, metadata: {}, num_to_mint: 3 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting as store owner"));
+});
+
+STORE_WORKSPACE.test(
+  "minter-allowances",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "grant_minter",
+        { account_id: bob, max_mints: 3 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "granting capped minter rights"));
+
+    await bob
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: bob.accountId, metadata: {}, num_to_mint: 2 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting within allowance"));
+    test.is(
+      await store.view("get_minter_allowance", { account_id: bob.accountId }),
+      1
+    );
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "nft_batch_mint",
+            { owner_id: bob.accountId, metadata: {}, num_to_mint: 2 },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'minter allowance exhausted: 1 remaining'",
+        "Bob tried to mint beyond the allowance",
+      ],
+    ]);
+  }
+);
+
+STORE_WORKSPACE.test("event-journal", async (test, { alice, bob, store }) => {
+  await alice
+    .call(
+      store,
+      "set_event_journal_capacity",
+      { capacity: 2 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "enabling the event journal"));
+
+  for (const method of ["grant_minter", "revoke_minter", "grant_minter"]) {
+    await alice
+      .call(store, method, { account_id: bob }, { attachedDeposit: "1" })
+      .catch(failPromiseRejection(test, method));
+  }
+
+  // only the two most recent events are retained
+  const events: { seq: number; event: string }[] = await store.view(
+    "events_since",
+    { seq: 0, limit: 10 }
+  );
+  test.deepEqual(events.map(({ seq }) => seq), [1, 2]);
+  test.true(events[1].event.startsWith("EVENT_JSON:"));
+});
+
+STORE_WORKSPACE.test("minting-fee", async (test, { alice, bob, store }) => {
+  await alice
+    .call(store, "grant_minter", { account_id: bob }, { attachedDeposit: "1" })
+    .catch(failPromiseRejection(test, "granting minter rights"));
+  await alice
+    .call(
+      store,
+      "set_minting_fee",
+      { minting_fee: mNEAR(1).toString() },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "setting minting fee"));
+  test.is(await store.view("get_minting_fee"), mNEAR(1).toString());
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await bob.call(
+          store,
+          "nft_batch_mint",
+          { owner_id: bob.accountId, metadata: {}, num_to_mint: 2 },
+          { attachedDeposit: mNEAR(1) }
+        );
+      },
+      `panicked at 'attached: ${mNEAR(1)}; minting fee: ${mNEAR(2)}'`,
+      "Bob tried to mint without paying the minting fee",
+    ],
+  ]);
+
+  await bob
+    .call(
+      store,
+      "nft_batch_mint",
+      { owner_id: bob.accountId, metadata: {}, num_to_mint: 2 },
+      { attachedDeposit: mNEAR(2) }
+    )
+    .catch(failPromiseRejection(test, "minting with minting fee"));
+});
+
+STORE_WORKSPACE.test("mint-window", async (test, { alice, store }) => {
+  // 2100-01-01T00:00:00Z in nanoseconds
+  const mintStart = 4102444800000000000;
+  await alice
+    .call(
+      store,
+      "set_mint_window",
+      { mint_start: mintStart },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "setting mint window"));
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await alice.call(
+          store,
+          "nft_batch_mint",
+          { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+          { attachedDeposit: "1" }
+        );
+      },
+      "panicked at 'minting not started'",
+      "Alice tried to mint before the mint window",
+    ],
+  ]);
+
+  await alice
+    .call(store, "set_mint_window", {}, { attachedDeposit: "1" })
+    .catch(failPromiseRejection(test, "removing mint window"));
+  await alice
+    .call(
+      store,
+      "nft_batch_mint",
+      { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting without mint window"));
+});
+
+STORE_WORKSPACE.test(
+  "minter-mint-windows",
+  async (test, { alice, bob, carol, store }) => {
+    // 2100-01-01T00:00:00Z in nanoseconds
+    const mintStart = 4102444800000000000;
+    await alice
+      .call(store, "grant_minter", { account_id: bob }, { attachedDeposit: "1" })
+      .catch(failPromiseRejection(test, "granting minter rights"));
+    await alice
+      .call(
+        store,
+        "set_mint_window",
+        { minter: bob, mint_start: mintStart },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "setting mint window"));
+
+    // the window moves with the minter's grant
+    await alice
+      .call(
+        store,
+        "rotate_minter",
+        { old_account: bob, new_account: carol },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "rotating minter"));
+    test.is(await store.view("get_mint_window", { minter: bob }), null);
+    test.like(await store.view("get_mint_window", { minter: carol }), {
+      mint_start: mintStart,
+    });
+
+    // and is removed along with it
+    await alice
+      .call(
+        store,
+        "revoke_minter",
+        { account_id: carol },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "revoking minter"));
+    test.is(await store.view("get_mint_window", { minter: carol }), null);
+  }
+);
+
+STORE_WORKSPACE.test("mint-bans", async (test, { alice, bob, store }) => {
+  await alice
+    .call(store, "grant_minter", { account_id: bob }, { attachedDeposit: "1" })
+    .catch(failPromiseRejection(test, "granting minter rights"));
+  await alice
+    .call(
+      store,
+      "ban_from_minting",
+      { account_id: bob, reason_hash: "cmVhc29u" },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "banning from minting"));
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await bob.call(
+          store,
+          "nft_batch_mint",
+          { owner_id: bob.accountId, metadata: {}, num_to_mint: 1 },
+          { attachedDeposit: "1" }
+        );
+      },
+      `panicked at '${bob.accountId} banned from minting'`,
+      "Bob tried to mint while banned",
+    ],
+  ]);
+
+  await alice
+    .call(
+      store,
+      "unban_from_minting",
+      { account_id: bob },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "lifting minting ban"));
+  test.like(await store.view("get_mint_ban", { account_id: bob }), {
+    reason_hash: "cmVhc29u",
+  });
+  await bob
+    .call(
+      store,
+      "nft_batch_mint",
+      { owner_id: bob.accountId, metadata: {}, num_to_mint: 1 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting after ban was lifted"));
+});
+
+STORE_WORKSPACE.test("mint-allowlist", async (test, { alice, bob, store }) => {
+  await alice
+    .call(
+      store,
+      "add_to_mint_allowlist",
+      { account_ids: [bob.accountId], max_mints: 1 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "adding to mint allowlist"));
+  test.deepEqual(await store.view("list_mint_allowlist"), [bob.accountId]);
+
+  await bob
+    .call(
+      store,
+      "nft_batch_mint",
+      { owner_id: bob.accountId, metadata: {}, num_to_mint: 1 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting from the allowlist"));
+
+  await alice
+    .call(
+      store,
+      "remove_from_mint_allowlist",
+      { account_ids: [bob.accountId] },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "removing from mint allowlist"));
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await bob.call(
+          store,
+          "nft_batch_mint",
+          { owner_id: bob.accountId, metadata: {}, num_to_mint: 1 },
+          { attachedDeposit: "1" }
+        );
+      },
+      `panicked at '${bob.accountId} not a minter'`,
+      "Bob tried to mint after removal from the allowlist",
+    ],
+  ]);
+});
+
+STORE_WORKSPACE.test("open-editions", async (test, { alice, bob, store }) => {
+  const editionId = await alice
+    .call(
+      store,
+      "register_open_edition",
+      { metadata: { title: "Open" }, price: mNEAR(1).toString() },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "registering open edition"));
+  test.is(editionId, "0");
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await bob.call(
+          store,
+          "nft_mint_edition",
+          { edition_id: "0" },
+          { attachedDeposit: "1" }
+        );
+      },
+      "panicked at 'attached: 1; need: ",
+      "Bob tried to mint a copy without paying for it",
+    ],
+  ]);
+
+  await bob
+    .call(
+      store,
+      "nft_mint_edition",
+      { edition_id: "0" },
+      { attachedDeposit: mNEAR(100) }
+    )
+    .catch(failPromiseRejection(test, "minting copy of open edition"));
+  test.like(await store.view("nft_token", { token_id: "0" }), {
+    owner_id: bob.accountId,
+    metadata: { title: "Open" },
+  });
+  test.like(await store.view("get_open_edition", { edition_id: "0" }), {
+    minted: 1,
+  });
+
+  // copies minted in between other tokens are still numbered consecutively
+  await alice
+    .call(
+      store,
+      "nft_batch_mint",
+      { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting"));
+  await bob
+    .call(
+      store,
+      "nft_mint_edition",
+      { edition_id: "0" },
+      { attachedDeposit: mNEAR(100) }
+    )
+    .catch(
+      failPromiseRejection(test, "minting second copy of open edition")
+    );
+  test.like(await store.view("nft_token", { token_id: "0" }), {
+    edition_number: 1,
+    metadata: { copies: 2 },
+  });
+  test.like(await store.view("nft_token", { token_id: "2" }), {
+    owner_id: bob.accountId,
+    edition_number: 2,
+    metadata: { title: "Open", copies: 2 },
+  });
+
+  await alice
+    .call(
+      store,
+      "end_open_edition",
+      { edition_id: "0" },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "ending open edition"));
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await bob.call(
+          store,
+          "nft_mint_edition",
+          { edition_id: "0" },
+          { attachedDeposit: mNEAR(100) }
+        );
+      },
+      "panicked at 'edition ended'",
+      "Bob tried to mint a copy of an ended edition",
+    ],
+  ]);
+});
+
+STORE_WORKSPACE.test(
+  "batch-mint-multi",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint_multi",
+        {
+          recipients: [
+            [bob.accountId, 2],
+            [carol.accountId, 1],
+          ],
+          metadata: { title: "Airdrop" },
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting to multiple owners"));
+
+    test.is(
+      await store.view("nft_supply_for_owner", { account_id: bob.accountId }),
+      "2"
+    );
+    test.is(
+      await store.view("nft_supply_for_owner", {
+        account_id: carol.accountId,
+      }),
+      "1"
+    );
+    test.like(await store.view("nft_token_metadata", { token_id: "2" }), {
+      title: "Airdrop",
+      copies: 3,
+    });
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_batch_mint_multi",
+            { recipients: [[bob.accountId, 0]], metadata: {} },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: recipients.iter().all(|(_, n)| *n > 0)'",
+        "Alice tried to mint zero tokens to Bob",
+      ],
+    ]);
+  }
+);
+
+STORE_WORKSPACE.test("mint-sessions", async (test, { alice, store }) => {
+  const mintId = await alice
+    .call(
+      store,
+      "start_mint_session",
+      { owner_id: alice.accountId, metadata: {}, num_to_mint: 130 },
+      { attachedDeposit: "1", gas: Tgas(200) }
+    )
+    .catch(failPromiseRejection(test, "starting mint session"));
+  test.like(await store.view("get_mint_session", { mint_id: mintId }), {
+    first_token_id: 0,
+    num_to_mint: 130,
+    num_minted: 125,
+  });
+  test.is(
+    await store.view("nft_supply_for_owner", { account_id: alice.accountId }),
+    "125"
+  );
+
+  await alice
+    .call(
+      store,
+      "continue_mint_session",
+      { mint_id: mintId },
+      { attachedDeposit: "1", gas: Tgas(200) }
+    )
+    .catch(failPromiseRejection(test, "continuing mint session"));
+  test.is(await store.view("get_mint_session", { mint_id: mintId }), null);
+  test.is(
+    await store.view("nft_supply_for_owner", { account_id: alice.accountId }),
+    "130"
+  );
+  test.like(await store.view("get_mint_batch", { mint_id: mintId }), {
+    first_token_id: 0,
+    last_token_id: 129,
+  });
+});
+
+STORE_WORKSPACE.test("markets", async (test, { alice, bob, store }) => {
+  await alice
+    .call(
+      store,
+      "nft_batch_mint",
+      { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting"));
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await alice.call(
+          store,
+          "list_on_market",
+          { token_ids: ["0"], market_id: bob, price: NEAR(1).toString() },
+          { attachedDeposit: mNEAR(1) }
+        );
+      },
+      `panicked at '${bob.accountId} not a registered market'`,
+      "Alice tried to list on an unregistered market",
+    ],
+  ]);
+
+  await alice
+    .call(store, "add_market", { market_id: bob }, { attachedDeposit: "1" })
+    .catch(failPromiseRejection(test, "adding market"));
+  test.deepEqual(await store.view("list_markets"), [bob.accountId]);
+
+  await alice
+    .call(store, "remove_market", { market_id: bob }, { attachedDeposit: "1" })
+    .catch(failPromiseRejection(test, "removing market"));
+  test.deepEqual(await store.view("list_markets"), []);
+});
+
+STORE_WORKSPACE.test(
+  "payout-override",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    await alice
+      .call(
+        store,
+        "set_payout_override",
+        { token_id: "0", split_between: { [bob.accountId]: 10000 } },
+        { attachedDeposit: mNEAR(1) }
+      )
+      .catch(failPromiseRejection(test, "setting payout override"));
+    test.deepEqual(
+      await store.view("nft_payout", {
+        token_id: "0",
+        balance: "1000",
+        max_len_payout: 10,
+      }),
+      { payout: { [bob.accountId]: "1000" } }
+    );
+
+    await alice
+      .call(
+        store,
+        "set_payout_override",
+        { token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "removing payout override"));
+    test.deepEqual(
+      await store.view("nft_payout", {
+        token_id: "0",
+        balance: "1000",
+        max_len_payout: 10,
+      }),
+      { payout: { [alice.accountId]: "1000" } }
+    );
+  }
+);
+
+STORE_WORKSPACE.test("roles", async (test, { alice, bob, carol, store }) => {
+  await alice
+    .call(
+      store,
+      "grant_role",
+      { account_id: bob, role: "mint_only" },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "granting role"));
+  test.deepEqual(await store.view("get_roles", { account_id: bob }), [
+    "mint_only",
+  ]);
+  test.true(await store.view("check_is_minter", { account_id: bob }));
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await bob.call(
+          store,
+          "nft_batch_mint",
+          {
+            owner_id: bob.accountId,
+            metadata: {},
+            num_to_mint: 1,
+            royalty_args: {
+              split_between: { [carol.accountId]: 10000 },
+              percentage: 1000,
+            },
+          },
+          { attachedDeposit: "1" }
+        );
+      },
+      `panicked at '${bob.accountId} may not set royalties or splits'`,
+      "Mint-only minter tried to set royalties",
+    ],
+    [
+      async () => {
+        await bob.call(
+          store,
+          "set_base_uri",
+          { base_uri: "https://example.com" },
+          { attachedDeposit: "1" }
+        );
+      },
+      `panicked at '${bob.accountId} lacks role metadata_editor'`,
+      "Mint-only minter tried to set the base URI",
+    ],
+  ]);
+
+  await bob
+    .call(
+      store,
+      "nft_batch_mint",
+      { owner_id: bob.accountId, metadata: {}, num_to_mint: 1 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting without royalties"));
+
+  await alice
+    .call(
+      store,
+      "grant_role",
+      { account_id: bob, role: "metadata_editor" },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "granting role"));
+  await bob
+    .call(
+      store,
+      "set_base_uri",
+      { base_uri: "https://example.com" },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "setting base URI"));
+
+  await alice
+    .call(
+      store,
+      "revoke_role",
+      { account_id: bob, role: "mint_only" },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "revoking role"));
+  test.deepEqual(await store.view("get_roles", { account_id: bob }), [
+    "metadata_editor",
+  ]);
+  test.false(await store.view("check_is_minter", { account_id: bob }));
+});
+
+STORE_WORKSPACE.test(
+  "minter-applications",
+  async (test, { alice, bob, carol, store }) => {
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "apply_for_minter",
+            { profile_reference: "bob-profile" },
+            { attachedDeposit: mNEAR(1) }
+          );
+        },
+        "panicked at 'deposit must be at least",
+        "Bob tried to apply with an insufficient deposit",
+      ],
+    ]);
+
+    await bob
+      .call(
+        store,
+        "apply_for_minter",
+        { profile_reference: "bob-profile" },
+        { attachedDeposit: mNEAR(100) }
+      )
+      .catch(failPromiseRejection(test, "applying as Bob"));
+    await carol
+      .call(
+        store,
+        "apply_for_minter",
+        { profile_reference: "carol-profile" },
+        { attachedDeposit: mNEAR(100) }
+      )
+      .catch(failPromiseRejection(test, "applying as Carol"));
+    test.is(
+      ((await store.view("list_minter_applications", {})) as any[]).length,
+      2
+    );
+
+    await alice
+      .call(
+        store,
+        "approve_minter_application",
+        { account_id: bob },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "approving Bob"));
+    test.true(await store.view("check_is_minter", { account_id: bob }));
+
+    await alice
+      .call(
+        store,
+        "reject_minter_application",
+        { account_id: carol, slash: true },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "rejecting Carol"));
+    test.false(await store.view("check_is_minter", { account_id: carol }));
+    test.deepEqual(await store.view("list_minter_applications", {}), []);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "time-limited-minters",
+  async (test, { alice, bob, store }) => {
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "grant_minter_until",
+            { account_id: bob, expiry_timestamp: 1 },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'expiry must be in the future'",
+        "Alice tried to grant an already expired minter",
+      ],
+    ]);
+
+    // nanoseconds, in 2030
+    const expiry = 1900000000000000000;
+    await alice
+      .call(
+        store,
+        "grant_minter_until",
+        { account_id: bob, expiry_timestamp: expiry },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "granting minter"));
+    test.true(await store.view("check_is_minter", { account_id: bob }));
+    test.is(
+      await store.view("get_minter_expiry", { account_id: bob }),
+      expiry
+    );
+
+    // unexpired grants are not swept
+    await alice
+      .call(store, "sweep_expired_minters", { account_ids: [bob] })
+      .catch(failPromiseRejection(test, "sweeping minters"));
+    test.true(await store.view("check_is_minter", { account_id: bob }));
+
+    await bob
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: bob.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    // granting without expiry lifts the expiry
+    await alice
+      .call(
+        store,
+        "grant_minter",
+        { account_id: bob },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "granting minter"));
+    test.is(await store.view("get_minter_expiry", { account_id: bob }), null);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "minter-cooling-off",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "set_minter_cooling_off",
+        { hours: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "setting cooling-off"));
+    await alice
+      .call(
+        store,
+        "grant_minter",
+        { account_id: bob },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "granting minter"));
+
+    const quota: any = await store.view("get_minter_quota", {
+      account_id: bob,
+    });
+    test.is(quota.remaining, null);
+    test.is(quota.expires_at, null);
+    test.not(quota.mintable_from, null);
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "nft_batch_mint",
+            { owner_id: bob.accountId, metadata: {}, num_to_mint: 1 },
+            { attachedDeposit: "1" }
+          );
+        },
+        `panicked at '${bob.accountId} may not mint before`,
+        "Bob tried to mint while cooling off",
+      ],
+    ]);
+
+    // the store owner is never cooling off
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+  }
+);
+
+STORE_WORKSPACE.test("mint-delegates", async (test, { alice, bob, store }) => {
+  const mintOnBehalfOfAlice = () =>
+    bob.call(
+      store,
+      "nft_batch_mint",
+      {
+        owner_id: bob.accountId,
+        metadata: {},
+        num_to_mint: 1,
+        on_behalf_of: alice.accountId,
+      },
+      { attachedDeposit: "1" }
+    );
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await mintOnBehalfOfAlice();
+      },
+      `panicked at '${bob.accountId} not a delegate of ${alice.accountId}'`,
+      "Bob tried to mint on behalf of Alice without delegation",
+    ],
+  ]);
+
+  await alice
+    .call(
+      store,
+      "approve_mint_delegate",
+      { delegate_id: bob },
+      { attachedDeposit: mNEAR(1) }
+    )
+    .catch(failPromiseRejection(test, "approving delegate"));
+  test.deepEqual(
+    await store.view("get_mint_delegates", { minter_id: alice }),
+    [bob.accountId]
+  );
+
+  await mintOnBehalfOfAlice().catch(
+    failPromiseRejection(test, "minting on behalf of Alice")
+  );
+  const token: any = await store.view("nft_token", { token_id: "0" });
+  test.is(token.minter, alice.accountId);
+  test.is(token.owner_id, bob.accountId);
+
+  await alice
+    .call(
+      store,
+      "revoke_mint_delegate",
+      { delegate_id: bob },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "revoking delegate"));
+  test.deepEqual(
+    await store.view("get_mint_delegates", { minter_id: alice }),
+    []
+  );
+});
+
+STORE_WORKSPACE.test("soulbound", async (test, { alice, bob, store }) => {
+  await alice
+    .call(
+      store,
+      "nft_batch_mint",
+      {
+        owner_id: alice.accountId,
+        metadata: {},
+        num_to_mint: 1,
+        transferable: false,
+      },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting"));
+  const token: any = await store.view("nft_token", { token_id: "0" });
+  test.true(token.soulbound);
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await alice.call(
+          store,
+          "nft_transfer",
+          { receiver_id: bob, token_id: "0" },
+          { attachedDeposit: "1" }
+        );
+      },
+      "panicked at 'token is soulbound'",
+      "Alice tried to transfer a soulbound token",
+    ],
+    [
+      async () => {
+        await alice.call(
+          store,
+          "nft_approve",
+          { token_id: "0", account_id: bob },
+          { attachedDeposit: mNEAR(1) }
+        );
+      },
+      "panicked at 'token is soulbound'",
+      "Alice tried to approve a soulbound token",
+    ],
+  ]);
+
+  await alice
+    .call(
+      store,
+      "nft_batch_burn",
+      { token_ids: ["0"] },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "burning"));
+});
+
+STORE_WORKSPACE.test(
+  "batch-transfer-checked",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 2 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    const results = await alice
+      .call(
+        store,
+        "nft_batch_transfer_checked",
+        {
+          transfers: [
+            { token_id: "0", receiver_id: bob },
+            { token_id: "5", receiver_id: bob },
+            { token_id: "1", receiver_id: alice },
+          ],
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring"));
+    test.deepEqual(results, [
+      { Ok: null },
+      { Err: "token_not_found" },
+      { Err: "transfer_to_self" },
+    ]);
+
+    const token: any = await store.view("nft_token", { token_id: "0" });
+    test.is(token.owner_id, bob.accountId);
+  }
+);
+
+STORE_WORKSPACE.test("mint-and-list", async (test, { alice, bob, store }) => {
+  const mintAndListArgs = {
+    metadata: {},
+    num_to_mint: 2,
+    market_id: bob,
+    price: NEAR(1).toString(),
+  };
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await alice.call(store, "nft_batch_mint_and_list", mintAndListArgs, {
+          attachedDeposit: mNEAR(10),
+          gas: Tgas(300),
+        });
+      },
+      `panicked at '${bob.accountId} not a registered market'`,
+      "Alice tried to mint and list on an unregistered market",
+    ],
+  ]);
+
+  await alice
+    .call(store, "add_market", { market_id: bob }, { attachedDeposit: "1" })
+    .catch(failPromiseRejection(test, "adding market"));
+
+  // Bob has no contract deployed, so the listing fails
+  await alice
+    .call_raw(store, "nft_batch_mint_and_list", mintAndListArgs, {
+      attachedDeposit: mNEAR(10),
+      gas: Tgas(300),
+    })
+    .catch(failPromiseRejection(test, "minting and listing"));
+
+  test.is(
+    await store.view("nft_supply_for_owner", { account_id: alice.accountId }),
+    "2"
+  );
+  const token: any = await store.view("nft_token", { token_id: "1" });
+  test.deepEqual(token.approved_account_ids, {});
+});
+
+STORE_WORKSPACE.test(
+  "royalty-registry",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "refresh_registry_royalty",
+            { lookup_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'no royalty registry'",
+        "Alice tried to refresh a royalty without a registry",
+      ],
+      [
+        async () => {
+          await bob.call(
+            store,
+            "set_royalty_registry",
+            { registry_id: bob },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Bob tried to set the royalty registry",
+      ],
+    ]);
+
+    await alice
+      .call(
+        store,
+        "set_royalty_registry",
+        { registry_id: bob },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "setting royalty registry"));
+    test.is(await store.view("get_royalty_registry"), bob.accountId);
+
+    // Bob has no contract deployed, so nothing is cached and the local
+    // royalty remains in effect
+    await alice
+      .call_raw(
+        store,
+        "refresh_registry_royalty",
+        { lookup_id: "0" },
+        { attachedDeposit: "1", gas: Tgas(100) }
+      )
+      .catch(failPromiseRejection(test, "refreshing registry royalty"));
+    test.is(await store.view("get_registry_royalty", { lookup_id: "0" }), null);
+    test.is(await store.view("get_token_royalty", { token_id: "0" }), null);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "hidden-metadata",
+  async (test, { alice, bob, store }) => {
+    const realMetadata = { title: "revealed", reference: "real-reference" };
+    const metadataHash = await store.view("hash_metadata", {
+      metadata: realMetadata,
+    });
+
+    await alice
+      .call(
+        store,
+        "nft_batch_mint_hidden",
+        {
+          owner_id: bob.accountId,
+          placeholder: { title: "hidden" },
+          num_to_mint: 2,
+          metadata_hash: metadataHash,
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting hidden tokens"));
+    test.like(await store.view("get_metadata_commitment", { lookup_id: "0" }), {
+      minter: alice.accountId,
+      first_token_id: 0,
+      last_token_id: 1,
+    });
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "reveal_metadata",
+            { lookup_id: "0", real_metadata: realMetadata },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Bob tried to reveal metadata of tokens minted by Alice",
+      ],
+      [
+        async () => {
+          await alice.call(
+            store,
+            "reveal_metadata",
+            { lookup_id: "0", real_metadata: { title: "fake" } },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'metadata does not match commitment'",
+        "Alice tried to reveal metadata that doesn't match the commitment",
+      ],
+    ]);
+
+    const revealCall = await alice
+      .call_raw(
+        store,
+        "reveal_metadata",
+        { lookup_id: "0", real_metadata: realMetadata },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "revealing metadata"));
+    assertEventLogs(
+      test,
+      (revealCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.1.0",
+          event: "nft_metadata_update",
+          data: [{ token_ids: ["0", "1"] }],
+        },
+      ],
+      "revealing metadata"
+    );
+    test.like(await store.view("nft_token_metadata", { token_id: "1" }), {
+      title: "revealed",
+      reference: "real-reference",
+    });
+    test.is(
+      await store.view("get_metadata_commitment", { lookup_id: "0" }),
+      null
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "batch-change-minters",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "grant_minter",
+        { account_id: carol.accountId },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "granting minter"));
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "batch_change_minters",
+            { grant: [bob.accountId], revoke: [bob.accountId] },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'can't grant and revoke the same account'",
+        "Alice tried to grant and revoke Bob at once",
+      ],
+    ]);
+
+    const changeCall = await alice
+      .call_raw(
+        store,
+        "batch_change_minters",
+        { grant: [bob.accountId, alice.accountId], revoke: [carol.accountId] },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "changing minters"));
+    assertEventLogs(
+      test,
+      (changeCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_change_minters",
+          data: JSON.stringify({
+            granted: [bob.accountId],
+            revoked: [carol.accountId],
+          }),
+        },
+      ],
+      "changing minters"
+    );
+    test.true(
+      await store.view("check_is_minter", { account_id: bob.accountId })
+    );
+    test.false(
+      await store.view("check_is_minter", { account_id: carol.accountId })
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "default-royalty",
+  async (test, { alice, bob, store }) => {
+    const royaltyArgs = {
+      split_between: { [alice.accountId]: 10000 },
+      percentage: 1000,
+    };
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "set_default_royalty",
+            { royalty_args: royaltyArgs },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Bob tried to set the default royalty",
+      ],
+    ]);
+
+    const costWithoutDefault = await store.view("estimate_mint_cost", {
+      metadata: {},
+      num_to_mint: 1,
+      roy_len: 0,
+      split_len: 0,
+    });
+    await alice
+      .call(
+        store,
+        "set_default_royalty",
+        { royalty_args: royaltyArgs },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "setting default royalty"));
+    test.deepEqual(await store.view("get_default_royalty"), royaltyArgs);
+    test.not(
+      await store.view("estimate_mint_cost", {
+        metadata: {},
+        num_to_mint: 1,
+        roy_len: 0,
+        split_len: 0,
+      }),
+      costWithoutDefault
+    );
+
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: bob.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    test.deepEqual(await store.view("get_token_royalty", { token_id: "0" }), {
+      split_between: { [alice.accountId]: { numerator: 10000 } },
+      percentage: { numerator: 1000 },
+    });
+  }
+);
+
+STORE_WORKSPACE.test("edition-numbers", async (test, { alice, store }) => {
+  await alice
+    .call(
+      store,
+      "nft_batch_mint",
+      { owner_id: alice.accountId, metadata: {}, num_to_mint: 2 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting"));
+  await alice
+    .call(
+      store,
+      "nft_batch_mint",
+      { owner_id: alice.accountId, metadata: {}, num_to_mint: 3 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting"));
+
+  const token: any = await store.view("nft_token", { token_id: "4" });
+  test.is(token.edition_number, 3);
+  test.is(token.metadata.copies, 3);
+  test.like(await store.view("nft_token", { token_id: "1" }), {
+    edition_number: 2,
+  });
+});
+
+STORE_WORKSPACE.test("signed-minting", async (test, { alice, bob, store }) => {
+  const request = {
+    store_id: store.accountId,
+    minter: alice.accountId,
+    nonce: 0,
+    deadline: Number.MAX_SAFE_INTEGER,
+    owner_id: bob.accountId,
+    metadata: {},
+    num_to_mint: 1,
+  };
+  // signatures can't be checked without a registered key
+  const signature = Buffer.alloc(64).toString("base64");
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await bob.call(
+          store,
+          "nft_mint_signed",
+          { request: { ...request, store_id: bob.accountId }, signature },
+          { attachedDeposit: "1" }
+        );
+      },
+      "panicked at 'assertion failed: `(left == right)`",
+      "Bob submitted a request for another store",
+    ],
+    [
+      async () => {
+        await bob.call(
+          store,
+          "nft_mint_signed",
+          { request, signature },
+          { attachedDeposit: "1" }
+        );
+      },
+      "panicked at 'no voucher key'",
+      "Bob submitted a request by a minter without a key",
+    ],
+  ]);
+});
+
+STORE_WORKSPACE.test("expiring-tokens", async (test, { alice, bob, store }) => {
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await alice.call(
+          store,
+          "nft_batch_mint",
+          {
+            owner_id: alice.accountId,
+            metadata: {},
+            num_to_mint: 1,
+            expires_at: 1,
+          },
+          { attachedDeposit: "1" }
+        );
+      },
+      "panicked at 'expiry must be in the future'",
+      "Alice tried to mint an already expired token",
+    ],
+    [
+      async () => {
+        await alice.call(
+          store,
+          "nft_batch_mint_distinct",
+          { owner_id: alice.accountId, metadata: [{}], expires_at: 1 },
+          { attachedDeposit: "1" }
+        );
+      },
+      "panicked at 'expiry must be in the future'",
+      "Alice tried to mint an already expired distinct token",
+    ],
+    [
+      async () => {
+        await alice.call(
+          store,
+          "nft_batch_mint_multi",
+          { recipients: [[bob.accountId, 1]], metadata: {}, expires_at: 1 },
+          { attachedDeposit: "1" }
+        );
+      },
+      "panicked at 'expiry must be in the future'",
+      "Alice tried to airdrop an already expired token",
+    ],
+  ]);
+
+  // nanoseconds, a few seconds from now
+  const expiry = (Date.now() + 5000) * 1000000;
+  await alice
+    .call(
+      store,
+      "nft_batch_mint",
+      {
+        owner_id: alice.accountId,
+        metadata: {},
+        num_to_mint: 3,
+        expires_at: expiry,
+      },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting"));
+  test.like(await store.view("nft_token", { token_id: "0" }), {
+    expires_at: expiry,
+  });
+  await alice
+    .call(
+      store,
+      "gift_token",
+      {
+        token_id: "2",
+        claim_pk: "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp",
+      },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "gifting"));
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await bob.call(store, "nft_burn_expired", { token_ids: ["0"] });
+      },
+      "panicked at 'token: 0 not expired'",
+      "Bob tried to burn an unexpired token",
+    ],
+  ]);
+
+  await new Promise((resolve) => setTimeout(resolve, 6000));
+  test.is(await store.view("nft_token", { token_id: "0" }), null);
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await alice.call(
+          store,
+          "nft_transfer",
+          { receiver_id: bob, token_id: "0" },
+          { attachedDeposit: "1" }
+        );
+      },
+      "panicked at 'token expired'",
+      "Alice tried to transfer an expired token",
+    ],
+  ]);
+
+  const burnCall = await bob.call_raw(store, "nft_burn_expired", {
+    token_ids: ["0", "1"],
+  });
+  assertEventLogs(
+    test,
+    (burnCall as TransactionResult).logs,
+    [
+      {
+        standard: "nep171",
+        version: "1.0.0",
+        event: "nft_burn",
+        data: [
+          {
+            owner_id: alice.accountId,
+            authorized_id: null,
+            token_ids: ["0", "1"],
+            memo: null,
+          },
+        ],
+      },
+    ],
+    "burning expired tokens"
+  );
+  test.is(
+    await store.view("nft_supply_for_owner", { account_id: alice.accountId }),
+    "0"
+  );
+
+  // burning an expired gift removes the gift
+  await bob
+    .call(store, "nft_burn_expired", { token_ids: ["2"] })
+    .catch(failPromiseRejection(test, "burning expired gift"));
+  test.is(await store.view("get_gift", { token_id: "2" }), null);
+});
+
+STORE_WORKSPACE.test(
+  "token-reservations",
+  async (test, { alice, bob, store }) => {
+    const reservation = await alice.call(
+      store,
+      "reserve_token_range",
+      { count: 3 },
+      { attachedDeposit: "1" }
+    );
+    test.deepEqual(reservation, {
+      minter: alice.accountId,
+      first_token_id: 0,
+      last_token_id: 2,
+      num_minted: 0,
+    });
+
+    // regular mints skip the reserved range
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    test.like(await store.view("nft_token", { token_id: "3" }), {
+      owner_id: alice.accountId,
+    });
+
+    const mintReserved = (account: any, num_to_mint: number) =>
+      account.call(
+        store,
+        "nft_mint_reserved",
+        {
+          first_token_id: "0",
+          owner_id: bob.accountId,
+          metadata: {},
+          num_to_mint,
+        },
+        { attachedDeposit: "1" }
+      );
+    await mintReserved(alice, 2).catch(
+      failPromiseRejection(test, "minting reserved tokens")
+    );
+    test.like(await store.view("nft_token", { token_id: "1" }), {
+      owner_id: bob.accountId,
+      edition_number: 2,
+    });
+    test.like(
+      await store.view("get_token_reservation", { first_token_id: "0" }),
+      { num_minted: 2 }
+    );
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "reserve_token_range",
+            { count: 1 },
+            { attachedDeposit: "1" }
+          );
+        },
+        `panicked at '${bob.accountId} not a minter'`,
+        "Bob tried to reserve a range without being a minter",
+      ],
+      [
+        async () => {
+          await mintReserved(bob, 1);
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Bob tried to mint into Alice's reservation",
+      ],
+      [
+        async () => {
+          await mintReserved(alice, 2);
+        },
+        "panicked at 'only 1 reserved tokens remain'",
+        "Alice tried to mint more tokens than reserved",
+      ],
+    ]);
+
+    await mintReserved(alice, 1).catch(
+      failPromiseRejection(test, "minting reserved tokens")
+    );
+    test.is(
+      await store.view("get_token_reservation", { first_token_id: "0" }),
+      null
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "minted-token-ids",
+  async (test, { alice, bob, store }) => {
+    test.deepEqual(
+      await alice.call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 2 },
+        { attachedDeposit: "1" }
+      ),
+      ["0", "1"]
+    );
+    test.deepEqual(
+      await alice.call(
+        store,
+        "nft_batch_mint_distinct",
+        { owner_id: alice.accountId, metadata: [{}, {}] },
+        { attachedDeposit: "1" }
+      ),
+      ["2", "3"]
+    );
+    test.deepEqual(
+      await alice.call(
+        store,
+        "nft_batch_mint_multi",
+        {
+          recipients: [
+            [alice.accountId, 1],
+            [bob.accountId, 2],
+          ],
+          metadata: {},
+        },
+        { attachedDeposit: "1" }
+      ),
+      ["4", "6"]
+    );
+  }
+);
+
+STORE_WORKSPACE.test("mint-pools", async (test, { alice, bob, store }) => {
+  await alice
+    .call(
+      store,
+      "register_mint_pool",
+      { price: "0" },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "registering mint pool"));
+  const titles = ["common", "uncommon", "rare"];
+  await alice
+    .call(
+      store,
+      "add_to_mint_pool",
+      { pool_id: "0", metadata: titles.map((title) => ({ title })) },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "adding to mint pool"));
+  test.like(await store.view("get_mint_pool", { pool_id: "0" }), {
+    remaining: 3,
+    minted: 0,
+  });
+
+  const mintFromPool = async () => {
+    await bob.call(
+      store,
+      "nft_mint_from_pool",
+      { pool_id: "0" },
+      { attachedDeposit: mNEAR(100) }
+    );
+  };
+  for (let i = 0; i < 3; i++) {
+    await mintFromPool().catch(
+      failPromiseRejection(test, "minting from pool")
+    );
+  }
+
+  // each entry is assigned exactly once
+  const minted = await Promise.all(
+    ["0", "1", "2"].map(
+      (token_id) => store.view("nft_token", { token_id }) as Promise<any>
+    )
+  );
+  test.deepEqual(
+    minted.map((token) => token.metadata.title).sort(),
+    [...titles].sort()
+  );
+  test.true(minted.every((token) => token.owner_id === bob.accountId));
+
+  await assertContractPanics(test, [
+    [
+      mintFromPool,
+      "panicked at 'pool exhausted'",
+      "Bob tried to mint from an exhausted pool",
+    ],
+  ]);
+});
+
+STORE_WORKSPACE.test(
+  "mint-event-metadata",
+  async (test, { alice, store }) => {
+    const mint = async () => {
+      const mintCall = await alice.call_raw(
+        store,
+        "nft_batch_mint",
+        {
+          owner_id: alice.accountId,
+          metadata: { title: "Full" },
+          num_to_mint: 2,
+        },
+        { attachedDeposit: "1" }
+      );
+      return getMintMemo((mintCall as TransactionResult).logs[0]);
+    };
+
+    test.is(await store.view("get_mint_event_metadata", {}), false);
+    test.false("metadata" in (await mint()));
+
+    await alice
+      .call(
+        store,
+        "set_mint_event_metadata",
+        { enabled: true },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "enabling metadata in mint events"));
+    test.like((await mint()).metadata, { title: "Full", copies: 2 });
+  }
+);
+
+STORE_WORKSPACE.test(
+  "update-token-royalty",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: bob.accountId, metadata: {}, num_to_mint: 2 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    const updateRoyalty = (account: any) =>
+      account.call(
+        store,
+        "update_token_royalty",
+        {
+          lookup_id: "0",
+          royalty_args: {
+            split_between: { [alice.accountId]: 10000 },
+            percentage: 1000,
+          },
+        },
+        { attachedDeposit: "1" }
+      );
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await updateRoyalty(bob);
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Bob tried to update the royalty of Alice's batch",
+      ],
+    ]);
+
+    await updateRoyalty(alice).catch(
+      failPromiseRejection(test, "updating royalty")
+    );
+    test.deepEqual(await store.view("get_token_royalty", { token_id: "1" }), {
+      split_between: { [alice.accountId]: { numerator: 10000 } },
+      percentage: { numerator: 1000 },
+    });
+
+    await bob
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: alice, token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring"));
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await updateRoyalty(alice);
+        },
+        "panicked at 'tokens already transferred'",
+        "Alice tried to update the royalty after a transfer",
+      ],
+    ]);
+  }
+);
+
+STORE_WORKSPACE.test("royalty-cap", async (test, { alice, bob, store }) => {
+  const royaltyArgs = (percentage: number) => ({
+    split_between: { [alice.accountId]: 10000 },
+    percentage,
+  });
+  const mint = (percentage: number) =>
+    alice.call(
+      store,
+      "nft_batch_mint",
+      {
+        owner_id: alice.accountId,
+        metadata: {},
+        num_to_mint: 1,
+        royalty_args: royaltyArgs(percentage),
+      },
+      { attachedDeposit: "1" }
+    );
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await bob.call(
+          store,
+          "set_max_royalty_bps",
+          { max_royalty_bps: 500 },
+          { attachedDeposit: "1" }
+        );
+      },
+      "panicked at 'assertion failed: `(left == right)`",
+      "Bob tried to set the royalty cap",
+    ],
+  ]);
+  await alice
+    .call(
+      store,
+      "set_max_royalty_bps",
+      { max_royalty_bps: 500 },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "setting royalty cap"));
+  test.is(await store.view("get_max_royalty_bps"), 500);
+
+  await assertContractPanics(test, [
+    [
+      async () => {
+        await mint(1000);
+      },
+      "panicked at 'royalty exceeds cap of 500 bps'",
+      "Alice tried to mint with a royalty above the cap",
+    ],
+    [
+      async () => {
+        await alice.call(
+          store,
+          "set_default_royalty",
+          { royalty_args: royaltyArgs(1000) },
+          { attachedDeposit: "1" }
+        );
+      },
+      "panicked at 'royalty exceeds cap of 500 bps'",
+      "Alice tried to set a default royalty above the cap",
+    ],
+  ]);
+  await mint(500).catch(failPromiseRejection(test, "minting within cap"));
+});
+
 // TODO::testing::low factory ownership transfer
 // DEFAULT_WORKSPACE.test(
 //   "ownership::transfer-factory",
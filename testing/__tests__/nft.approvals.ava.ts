@@ -11,6 +11,8 @@ import {
   getBalance,
   assertContractTokenOwners,
   assertNoApproval,
+  getMintId,
+  failPromiseRejection,
 } from "./test-utils";
 // import * as ava from "near-workspaces-ava";
 
@@ -74,6 +76,28 @@ STORE_WORKSPACE.test(
             { token_id: 0, approval_id: 0, account_id: bob.accountId },
           ]),
         },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "taken",
+            account_id: alice.accountId,
+            amount: mNEAR(0.8).toString(),
+            method: "nft_approve",
+          }),
+        },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "refunded",
+            account_id: alice.accountId,
+            amount: mNEAR(0.01).toString(),
+            method: "nft_approve",
+          }),
+        },
       ],
       "approving"
     );
@@ -148,6 +172,17 @@ STORE_WORKSPACE.test(
             { token_id: 2, approval_id: 2, account_id: bob.accountId },
           ]),
         },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "taken",
+            account_id: alice.accountId,
+            amount: mNEAR(1.6).toString(),
+            method: "nft_batch_approve",
+          }),
+        },
       ],
       "batch approving"
     );
@@ -236,6 +271,17 @@ STORE_WORKSPACE.test(
           //  it's an object -> should have the same predictable structure
           data: JSON.stringify({ token_id: 2, account_id: bob.accountId }),
         },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "refunded",
+            account_id: alice.accountId,
+            amount: mNEAR(0.8).toString(),
+            method: "nft_revoke",
+          }),
+        },
       ],
       "revoking"
     );
@@ -338,6 +384,17 @@ STORE_WORKSPACE.test(
           // TODO::store::medium: wtf is this format?
           data: JSON.stringify({ data: "1" }),
         },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "refunded",
+            account_id: alice.accountId,
+            amount: mNEAR(1.6).toString(),
+            method: "nft_revoke_all",
+          }),
+        },
       ],
       "revoking all"
     );
@@ -481,6 +538,12 @@ STORE_WORKSPACE.test(
         { attachedDeposit: "1" }
       )
       .catch(failPromiseRejection("approved minting"));
+    const batchMintStorage = await store.view("estimate_mint_cost", {
+      metadata: {},
+      num_to_mint: 2,
+      roy_len: 0,
+      split_len: 0,
+    });
 
     // check logs
     assertEventLogs(
@@ -501,10 +564,24 @@ STORE_WORKSPACE.test(
                 meta_id: null,
                 meta_extra: null,
                 minter: bob.accountId,
+                mint_id: getMintId(
+                  (batchMintCall as TransactionResult).logs[0]
+                ),
               }),
             },
           ],
         },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "consumed",
+            account_id: bob.accountId,
+            amount: batchMintStorage,
+            method: "nft_batch_mint",
+          }),
+        },
       ],
       "approved minting"
     );
@@ -635,6 +712,18 @@ STORE_WORKSPACE.test(
       test,
       (transferCall as TransactionResult).logs,
       [
+        // the approval is cleared, and its deposit refunded to its payer
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "refunded",
+            account_id: alice.accountId,
+            amount: mNEAR(0.8).toString(),
+            method: "nft_transfer",
+          }),
+        },
         {
           standard: "nep171",
           version: "1.0.0",
@@ -818,3 +907,298 @@ STORE_WORKSPACE.test(
     // ]);
   }
 );
+
+STORE_WORKSPACE.test(
+  "expiring-approvals",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_approve",
+            { token_id: "0", account_id: bob.accountId, expires_at: 1 },
+            { attachedDeposit: mNEAR(0.81) }
+          );
+        },
+        "panicked at 'expiry must be in the future'",
+        "Alice tried to grant an already expired approval",
+      ],
+    ]);
+
+    // nanoseconds, a few seconds from now
+    const expiry = (Date.now() + 5000) * 1000000;
+    await alice
+      .call(
+        store,
+        "nft_approve",
+        { token_id: "0", account_id: bob.accountId, expires_at: expiry },
+        { attachedDeposit: mNEAR(0.81) }
+      )
+      .catch(failPromiseRejection(test, "approving"));
+    test.true(
+      await store.view("nft_is_approved", {
+        token_id: "0",
+        approved_account_id: bob.accountId,
+        approval_id: 0,
+      })
+    );
+
+    await new Promise((resolve) => setTimeout(resolve, 6000));
+    test.false(
+      await store.view("nft_is_approved", {
+        token_id: "0",
+        approved_account_id: bob.accountId,
+        approval_id: 0,
+      })
+    );
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "nft_transfer",
+            { receiver_id: carol.accountId, token_id: "0", approval_id: 0 },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'approval expired'",
+        "Bob tried to exercise an expired approval",
+      ],
+    ]);
+
+    // an expired approval doesn't hinder operators
+    await alice
+      .call(
+        store,
+        "approve_operator",
+        { operator_id: bob.accountId },
+        { attachedDeposit: mNEAR(0.81) }
+      )
+      .catch(failPromiseRejection(test, "approving operator"));
+    await bob
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: carol.accountId, token_id: "0", approval_id: 0 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring as operator"));
+    test.is(
+      ((await store.view("nft_token", { token_id: "0" })) as any).owner_id,
+      carol.accountId
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "operator-approvals",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 2 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    const approveCall = await alice
+      .call_raw(
+        store,
+        "approve_operator",
+        { operator_id: bob.accountId },
+        { attachedDeposit: mNEAR(0.81) }
+      )
+      .catch(failPromiseRejection(test, "approving operator"));
+    assertEventLogs(
+      test,
+      (approveCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_approve_operator",
+          data: JSON.stringify({
+            owner_id: alice.accountId,
+            operator_id: bob.accountId,
+          }),
+        },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "taken",
+            account_id: alice.accountId,
+            amount: mNEAR(0.8).toString(),
+            method: "approve_operator",
+          }),
+        },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "refunded",
+            account_id: alice.accountId,
+            amount: mNEAR(0.01).toString(),
+            method: "approve_operator",
+          }),
+        },
+      ],
+      "approving operator"
+    );
+    test.deepEqual(
+      await store.view("get_operators", { owner_id: alice.accountId }),
+      [bob.accountId]
+    );
+    test.true(
+      await store.view("nft_is_approved", {
+        token_id: "1",
+        approved_account_id: bob.accountId,
+      })
+    );
+
+    // operators need no per-token approval
+    await bob
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: carol.accountId, token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring as operator"));
+    const token: any = await store.view("nft_token", { token_id: "0" });
+    test.is(token.owner_id, carol.accountId);
+
+    const revokeCall = await alice
+      .call_raw(
+        store,
+        "revoke_operator",
+        { operator_id: bob.accountId },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "revoking operator"));
+    assertEventLogs(
+      test,
+      (revokeCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_revoke_operator",
+          data: JSON.stringify({
+            owner_id: alice.accountId,
+            operator_id: bob.accountId,
+          }),
+        },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "refunded",
+            account_id: alice.accountId,
+            amount: mNEAR(0.8).toString(),
+            method: "revoke_operator",
+          }),
+        },
+      ],
+      "revoking operator"
+    );
+    test.false(
+      await store.view("is_operator", {
+        owner_id: alice.accountId,
+        operator_id: bob.accountId,
+      })
+    );
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "nft_transfer",
+            { receiver_id: carol.accountId, token_id: "1" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'approval_id required'",
+        "Bob tried to transfer after being revoked as operator",
+      ],
+    ]);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "approval-deposits",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    await alice
+      .call(
+        store,
+        "nft_approve",
+        { token_id: "0", account_id: bob.accountId },
+        { attachedDeposit: mNEAR(0.81) }
+      )
+      .catch(failPromiseRejection(test, "approving"));
+
+    // revoking refunds the price paid, not the current price
+    await alice
+      .call(
+        store,
+        "set_storage_price_per_byte",
+        { new_price: "20000000000000000000" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "doubling the storage price"));
+    const revokeCall = await alice
+      .call_raw(
+        store,
+        "nft_revoke",
+        { token_id: "0", account_id: bob.accountId },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "revoking"));
+    assertEventLogs(
+      test,
+      (revokeCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_revoke",
+          data: JSON.stringify({ token_id: 0, account_id: bob.accountId }),
+        },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "refunded",
+            account_id: alice.accountId,
+            amount: mNEAR(0.8).toString(),
+            method: "nft_revoke",
+          }),
+        },
+      ],
+      "revoking"
+    );
+  }
+);
@@ -7,6 +7,11 @@ import {
   assertContractTokenOwners,
   assertEventLogs,
   failPromiseRejection,
+  getMintId,
+  mNEAR,
+  STORE_WORKSPACE,
+  Tgas,
+  assertEventLog,
 } from "./test-utils";
 
 FACTORY_WORKSPACE.test(
@@ -50,6 +55,12 @@ FACTORY_WORKSPACE.test(
         { attachedDeposit: "1" }
       )
       .catch(failPromiseRejection(test, "minting"));
+    const mintStorage = await store.view("estimate_mint_cost", {
+      metadata: {},
+      num_to_mint: 6,
+      roy_len: 0,
+      split_len: 0,
+    });
 
     // check minting logs
     assertEventLogs(
@@ -71,14 +82,42 @@ FACTORY_WORKSPACE.test(
                 meta_id: null,
                 meta_extra: null,
                 minter: alice.accountId,
+                mint_id: getMintId((mintCall as TransactionResult).logs[0]),
               }),
             },
           ],
         },
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_storage_deposit",
+          data: JSON.stringify({
+            action: "consumed",
+            account_id: alice.accountId,
+            amount: mintStorage,
+            method: "nft_batch_mint",
+          }),
+        },
       ],
       "minting"
     );
 
+    // querying the mint batch by its mint_id
+    test.like(
+      await store.view("get_mint_batch", {
+        mint_id: getMintId((mintCall as TransactionResult).logs[0]),
+      }),
+      {
+        minter: alice.accountId,
+        owner_id: alice.accountId,
+        first_token_id: 0,
+        last_token_id: 5,
+        metadata_id: 0,
+        royalty_id: null,
+      },
+      "querying mint batch"
+    );
+
     // inspecting minted tokens (implicitly tests `nft_token`)
     await assertContractTokenOwners(
       { test, store },
@@ -288,6 +327,147 @@ FACTORY_WORKSPACE.test(
       ],
     ]);
 
+    // transferring to a non-existing account with receiver verification
+    await alice
+      .call(
+        store,
+        "nft_transfer",
+        {
+          receiver_id: `nonexistent.${root.accountId}`,
+          token_id: "3",
+          verify_receiver: true,
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring with verification"));
+
+    // freezing a token blocks transfers and approvals
+    const freezeCall = await alice
+      .call_raw(
+        store,
+        "freeze_my_token",
+        { token_id: "3" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "freezing token"));
+    assertEventLogs(
+      test,
+      (freezeCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_freeze",
+          data: JSON.stringify({ token_id: "3", owner_id: alice.accountId }),
+        },
+      ],
+      "freezing token"
+    );
+    const frozenToken: any = await store.view("nft_token", { token_id: "3" });
+    test.true(frozenToken.frozen);
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_transfer",
+            { receiver_id: bob.accountId, token_id: "3" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'token frozen'",
+        "Alice tried to transfer a frozen token",
+      ],
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_approve",
+            { token_id: "3", account_id: bob.accountId },
+            { attachedDeposit: mNEAR(0.81) }
+          );
+        },
+        "panicked at 'token frozen'",
+        "Alice tried to approve a frozen token",
+      ],
+      [
+        async () => {
+          await bob.call(
+            store,
+            "unfreeze_my_token",
+            { token_id: "3" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: token.is_pred_owner()'",
+        "Bob tried to unfreeze an unowned token",
+      ],
+    ]);
+    const unfreezeCall = await alice
+      .call_raw(
+        store,
+        "unfreeze_my_token",
+        { token_id: "3" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "unfreezing token"));
+    assertEventLogs(
+      test,
+      (unfreezeCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_unfreeze",
+          data: JSON.stringify({ token_id: "3", owner_id: alice.accountId }),
+        },
+      ],
+      "unfreezing token"
+    );
+    await alice
+      .call(store, "freeze_token", { token_id: "3" }, { attachedDeposit: "1" })
+      .catch(failPromiseRejection(test, "freezing token"));
+    test.true(
+      ((await store.view("nft_token", { token_id: "3" })) as any).frozen
+    );
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_approve",
+            { token_id: "3", account_id: bob.accountId },
+            { attachedDeposit: mNEAR(0.81) }
+          );
+        },
+        "panicked at 'token frozen'",
+        "Alice tried to approve a token frozen by freeze_token",
+      ],
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_batch_approve",
+            { token_ids: ["3"], account_id: bob.accountId },
+            { attachedDeposit: mNEAR(0.81) }
+          );
+        },
+        "panicked at 'token frozen'",
+        "Alice tried to batch approve a token frozen by freeze_token",
+      ],
+    ]);
+    await alice
+      .call(
+        store,
+        "unfreeze_token",
+        { token_id: "3" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "unfreezing token"));
+    test.false(
+      ((await store.view("nft_token", { token_id: "3" })) as any).frozen
+    );
+
     // checking token ownership
     await assertContractTokenOwners(
       { test, store },
@@ -302,6 +482,65 @@ FACTORY_WORKSPACE.test(
       "After transfers"
     ).catch(failPromiseRejection(test, "checking token ownership"));
 
+    // co-signed transfers
+    await alice
+      .call(
+        store,
+        "set_co_signer",
+        { token_id: "3", co_signer: bob.accountId },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "setting co-signer"));
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_transfer",
+            { receiver_id: carol.accountId, token_id: "3" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'transfer requires co-signer'",
+        "Alice tried to transfer a co-signed token",
+      ],
+    ]);
+    await alice
+      .call(
+        store,
+        "nft_initiate_transfer",
+        { token_id: "3", receiver_id: carol.accountId },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "initiating co-signed transfer"));
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await carol.call(
+            store,
+            "nft_approve_transfer",
+            { token_id: "3" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'not the co-signer'",
+        "Carol tried to approve a co-signed transfer",
+      ],
+    ]);
+    await bob
+      .call(
+        store,
+        "nft_approve_transfer",
+        { token_id: "3" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "approving co-signed transfer"));
+    await assertContractTokenOwners(
+      { test, store },
+      [{ token_id: "3", owner_id: carol.accountId }],
+      "After co-signed transfer"
+    ).catch(failPromiseRejection(test, "checking co-signed transfer"));
+
     // burning tokens
     const burnCall = await alice
       .call_raw(
@@ -386,3 +625,1718 @@ FACTORY_WORKSPACE.test(
     // TODO::testing::low: undeploy contract (store owner)
   }
 );
+
+STORE_WORKSPACE.test(
+  "batch-transfer-aggregated",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 3 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    const batchTransferCall = await alice
+      .call_raw(
+        store,
+        "nft_batch_transfer",
+        {
+          token_ids: [
+            ["0", bob.accountId],
+            ["1", carol.accountId],
+            ["2", bob.accountId],
+          ],
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "batch transferring"));
+    assertEventLogs(
+      test,
+      (batchTransferCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_transfer",
+          data: [
+            {
+              authorized_id: null,
+              old_owner_id: alice.accountId,
+              new_owner_id: bob.accountId,
+              token_ids: ["0", "2"],
+              memo: null,
+            },
+            {
+              authorized_id: null,
+              old_owner_id: alice.accountId,
+              new_owner_id: carol.accountId,
+              token_ids: ["1"],
+              memo: null,
+            },
+          ],
+        },
+      ],
+      "batch transferring"
+    );
+    test.is(
+      await store.view("nft_supply_for_owner", { account_id: bob.accountId }),
+      "2"
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "transfer-call-resolution",
+  async (test, { root, alice, store }) => {
+    const helper = await root.createAndDeploy(
+      "helper",
+      "../wasm/helper.wasm",
+      { method: "new", args: {} }
+    );
+    // the helper returns `msg` as JSON, and panics if it cannot be parsed
+    const shapes: [string, string, boolean][] = [
+      ["false", "kept", true],
+      ["true", "returned", false],
+      ['"yes"', "malformed", false],
+      ["1", "malformed", false],
+      ["not json", "failed", false],
+    ];
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: shapes.length },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    for (const [i, [msg, outcome, transferred]] of shapes.entries()) {
+      const token_id = i.toString();
+      const call = await alice
+        .call_raw(
+          store,
+          "nft_transfer_call",
+          { receiver_id: helper.accountId, token_id, msg },
+          { attachedDeposit: "1", gas: Tgas(200) }
+        )
+        .catch(failPromiseRejection(test, `transfer call with ${msg}`));
+      const logs = (call as TransactionResult).logs;
+      assertEventLog(
+        test,
+        logs[logs.length - 1],
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_resolve_transfer",
+          data: JSON.stringify({
+            token_id,
+            owner_id: alice.accountId,
+            receiver_id: helper.accountId,
+            outcome,
+            transferred,
+          }),
+        },
+        `resolving transfer call with ${msg}`
+      );
+
+      const token: any = await store.view("nft_token", { token_id });
+      test.is(
+        token.owner_id,
+        transferred ? helper.accountId : alice.accountId,
+        `token owner after transfer call with ${msg}`
+      );
+    }
+  }
+);
+
+STORE_WORKSPACE.test(
+  "transfer-memo",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 3 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    const transferCall = await alice
+      .call_raw(
+        store,
+        "nft_transfer",
+        { receiver_id: bob.accountId, token_id: "0", memo: "invoice-1" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring"));
+    assertEventLogs(
+      test,
+      (transferCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_transfer",
+          data: [
+            {
+              authorized_id: null,
+              old_owner_id: alice.accountId,
+              new_owner_id: bob.accountId,
+              token_ids: ["0"],
+              memo: "invoice-1",
+            },
+          ],
+        },
+      ],
+      "transferring with memo"
+    );
+
+    const batchTransferCall = await alice
+      .call_raw(
+        store,
+        "nft_batch_transfer",
+        {
+          token_ids: [
+            ["1", bob.accountId],
+            ["2", carol.accountId],
+          ],
+          memo: "invoice-2",
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "batch transferring"));
+    assertEventLogs(
+      test,
+      (batchTransferCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_transfer",
+          data: [
+            {
+              authorized_id: null,
+              old_owner_id: alice.accountId,
+              new_owner_id: bob.accountId,
+              token_ids: ["1"],
+              memo: "invoice-2",
+            },
+            {
+              authorized_id: null,
+              old_owner_id: alice.accountId,
+              new_owner_id: carol.accountId,
+              token_ids: ["2"],
+              memo: "invoice-2",
+            },
+          ],
+        },
+      ],
+      "batch transferring with memo"
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "transfer-policy",
+  async (test, { root, alice, bob, carol, store }) => {
+    const helper = await root.createAndDeploy(
+      "helper",
+      "../wasm/helper.wasm",
+      { method: "new", args: {} }
+    );
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 4 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    const setPolicy = async (policy: any) =>
+      alice.call(
+        store,
+        "set_transfer_policy",
+        { policy },
+        { attachedDeposit: "1" }
+      );
+
+    // blocklist
+    await alice
+      .call(
+        store,
+        "update_transfer_policy_list",
+        { account_ids: [carol.accountId], listed: true },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "listing carol"));
+    await setPolicy("blocklist").catch(
+      failPromiseRejection(test, "setting blocklist")
+    );
+    test.false(
+      await store.view("can_receive_transfers", {
+        receiver_id: carol.accountId,
+      })
+    );
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_transfer",
+            { receiver_id: carol.accountId, token_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        `panicked at '${carol.accountId} may not receive tokens'`,
+        "Alice tried to transfer to a blocked account",
+      ],
+    ]);
+    await alice
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: bob.accountId, token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring under blocklist"));
+
+    // allowlist
+    await setPolicy("allowlist").catch(
+      failPromiseRejection(test, "setting allowlist")
+    );
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_transfer",
+            { receiver_id: bob.accountId, token_id: "1" },
+            { attachedDeposit: "1" }
+          );
+        },
+        `panicked at '${bob.accountId} may not receive tokens'`,
+        "Alice tried to transfer to an account not on the allowlist",
+      ],
+    ]);
+    await alice
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: carol.accountId, token_id: "1" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring under allowlist"));
+
+    // hook approving all transfers
+    await setPolicy({ hook: { hook_id: helper.accountId } }).catch(
+      failPromiseRejection(test, "setting hook")
+    );
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_batch_transfer",
+            { token_ids: [["2", bob.accountId]] },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'transfer policy hook requires nft_transfer'",
+        "Alice tried to batch transfer under a hook policy",
+      ],
+    ]);
+    await alice
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: bob.accountId, token_id: "2" },
+        { attachedDeposit: "1", gas: Tgas(100) }
+      )
+      .catch(failPromiseRejection(test, "transferring under hook"));
+    let token: any = await store.view("nft_token", { token_id: "2" });
+    test.is(token.owner_id, bob.accountId);
+
+    // hook without contract, thus rejecting all transfers
+    await setPolicy({ hook: { hook_id: carol.accountId } }).catch(
+      failPromiseRejection(test, "setting failing hook")
+    );
+    const rejectedCall = await alice
+      .call_raw(
+        store,
+        "nft_transfer",
+        { receiver_id: bob.accountId, token_id: "3" },
+        { attachedDeposit: "1", gas: Tgas(100) }
+      )
+      .catch(failPromiseRejection(test, "transferring under failing hook"));
+    const logs = (rejectedCall as TransactionResult).logs;
+    assertEventLog(
+      test,
+      logs[logs.length - 1],
+      {
+        standard: "nep171",
+        version: "1.0.0",
+        event: "nft_transfer_rejected",
+        data: JSON.stringify({
+          token_id: "3",
+          owner_id: alice.accountId,
+          receiver_id: bob.accountId,
+          hook_id: carol.accountId,
+        }),
+      },
+      "rejected transfer"
+    );
+    token = await store.view("nft_token", { token_id: "3" });
+    test.is(token.owner_id, alice.accountId);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "transfer-offers",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    // nanoseconds, a few seconds from now
+    let expiry = (Date.now() + 5000) * 1000000;
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_offer_transfer",
+            {
+              receiver_id: bob.accountId,
+              token_id: "0",
+              // a year from now
+              expires_at: (Date.now() + 365 * 24 * 3600 * 1000) * 1000000,
+            },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'transfer offer may last at most 720 hours'",
+        "Alice tried to hold a token in escrow for a year",
+      ],
+    ]);
+    await alice
+      .call(
+        store,
+        "nft_offer_transfer",
+        { receiver_id: bob.accountId, token_id: "0", expires_at: expiry },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "offering transfer"));
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_transfer",
+            { receiver_id: carol.accountId, token_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'token offered for transfer'",
+        "Alice tried to transfer a token in escrow",
+      ],
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_cancel_transfer_offer",
+            { token_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'transfer offer not expired'",
+        "Alice tried to cancel an unexpired offer",
+      ],
+      [
+        async () => {
+          await carol.call(
+            store,
+            "nft_accept_transfer",
+            { token_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Carol tried to accept an offer to Bob",
+      ],
+    ]);
+    await bob
+      .call(
+        store,
+        "nft_accept_transfer",
+        { token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "accepting transfer"));
+    let token: any = await store.view("nft_token", { token_id: "0" });
+    test.is(token.owner_id, bob.accountId);
+    test.is(token.transfer_offer, null);
+
+    // expired offers can no longer be accepted, but cancelled by the owner
+    expiry = (Date.now() + 5000) * 1000000;
+    await bob
+      .call(
+        store,
+        "nft_offer_transfer",
+        { receiver_id: carol.accountId, token_id: "0", expires_at: expiry },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "offering transfer"));
+    await new Promise((resolve) => setTimeout(resolve, 6000));
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await carol.call(
+            store,
+            "nft_accept_transfer",
+            { token_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'transfer offer expired'",
+        "Carol tried to accept an expired offer",
+      ],
+    ]);
+    await bob
+      .call(
+        store,
+        "nft_cancel_transfer_offer",
+        { token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "cancelling expired offer"));
+    test.is(await store.view("get_transfer_offer", { token_id: "0" }), null);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "token-leases",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    // nanoseconds, a few seconds from now
+    const expiry = (Date.now() + 5000) * 1000000;
+    const leaseCall = await alice
+      .call_raw(
+        store,
+        "nft_lease",
+        { token_id: "0", borrower_id: bob.accountId, expires_at: expiry },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "leasing"));
+    assertEventLogs(
+      test,
+      (leaseCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_lease",
+          data: JSON.stringify({
+            token_id: "0",
+            owner_id: alice.accountId,
+            borrower_id: bob.accountId,
+            expires_at: expiry,
+          }),
+        },
+      ],
+      "leasing"
+    );
+    test.is(
+      await store.view("nft_token_user", { token_id: "0" }),
+      bob.accountId
+    );
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_transfer",
+            { receiver_id: carol.accountId, token_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'token loaned'",
+        "Alice tried to transfer a leased token",
+      ],
+      [
+        async () => {
+          await bob.call(
+            store,
+            "nft_batch_burn",
+            { token_ids: ["0"] },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'none owned'",
+        "Bob tried to burn a leased token",
+      ],
+    ]);
+
+    // the lease expires automatically
+    await new Promise((resolve) => setTimeout(resolve, 6000));
+    test.is(await store.view("nft_token_user", { token_id: "0" }), null);
+
+    // borrowers may return a token early
+    await alice
+      .call(
+        store,
+        "nft_lease",
+        {
+          token_id: "0",
+          borrower_id: bob.accountId,
+          expires_at: (Date.now() + 60000) * 1000000,
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "leasing again"));
+    await bob
+      .call(
+        store,
+        "nft_return_lease",
+        { token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "returning lease"));
+    test.is(await store.view("nft_token_user", { token_id: "0" }), null);
+    await alice
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: carol.accountId, token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring after lease"));
+  }
+);
+
+STORE_WORKSPACE.test(
+  "lock-timeout",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    test.is(await store.view("get_lock_timeout"), 60);
+    await alice
+      .call(store, "set_lock_timeout", { minutes: 5 }, { attachedDeposit: "1" })
+      .catch(failPromiseRejection(test, "setting lock timeout"));
+    test.is(await store.view("get_lock_timeout"), 5);
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "set_lock_timeout",
+            { minutes: 1 },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Bob tried to set the lock timeout",
+      ],
+      [
+        async () => {
+          await alice.call(
+            store,
+            "force_unlock",
+            { token_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'token not locked'",
+        "Alice tried to unlock a token that isn't locked",
+      ],
+    ]);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "transfer-call-gas",
+  async (test, { root, alice, store }) => {
+    const helper = await root.createAndDeploy(
+      "helper",
+      "../wasm/helper.wasm",
+      { method: "new", args: {} }
+    );
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_transfer_call",
+            {
+              receiver_id: helper.accountId,
+              token_id: "0",
+              msg: "false",
+              gas_for_receiver: "201000000000000",
+            },
+            { attachedDeposit: "1", gas: Tgas(300) }
+          );
+        },
+        "panicked at 'gas_for_receiver: 201000000000000 must be <= 200000000000000'",
+        "Alice tried to forward too much gas to the receiver",
+      ],
+    ]);
+
+    await alice
+      .call(
+        store,
+        "nft_transfer_call",
+        {
+          receiver_id: helper.accountId,
+          token_id: "0",
+          msg: "false",
+          gas_for_receiver: "100000000000000",
+        },
+        { attachedDeposit: "1", gas: Tgas(200) }
+      )
+      .catch(failPromiseRejection(test, "transfer call with receiver gas"));
+    const token: any = await store.view("nft_token", { token_id: "0" });
+    test.is(token.owner_id, helper.accountId);
+
+    // the advertised gas covers the receiver and the resolution
+    const requirements: any = await store.view("get_method_requirements");
+    const { recommended_gas } = requirements.methods.find(
+      (m: any) => m.method_name === "nft_transfer_call"
+    );
+    test.is(recommended_gas, Tgas(100).toString());
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    const call = await alice
+      .call_raw(
+        store,
+        "nft_transfer_call",
+        { receiver_id: helper.accountId, token_id: "1", msg: "false" },
+        { attachedDeposit: "1", gas: recommended_gas }
+      )
+      .catch(failPromiseRejection(test, "transfer call with advertised gas"));
+    const logs = (call as TransactionResult).logs;
+    assertEventLog(
+      test,
+      logs[logs.length - 1],
+      {
+        standard: "nep171",
+        version: "1.0.0",
+        event: "nft_resolve_transfer",
+        data: JSON.stringify({
+          token_id: "1",
+          owner_id: alice.accountId,
+          receiver_id: helper.accountId,
+          outcome: "kept",
+          transferred: true,
+        }),
+      },
+      "resolving transfer call with advertised gas"
+    );
+    test.is(
+      ((await store.view("nft_token", { token_id: "1" })) as any).owner_id,
+      helper.accountId
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "batch-transfer-call",
+  async (test, { root, alice, store }) => {
+    const helper = await root.createAndDeploy(
+      "helper",
+      "../wasm/helper.wasm",
+      { method: "new", args: {} }
+    );
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 4 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    // the helper keeps token 0 and returns token 1
+    const call = await alice
+      .call_raw(
+        store,
+        "nft_batch_transfer_call",
+        {
+          receiver_id: helper.accountId,
+          token_ids: ["0", "1"],
+          memo: "deposit",
+          msg: "[false, true]",
+        },
+        { attachedDeposit: "1", gas: Tgas(200) }
+      )
+      .catch(failPromiseRejection(test, "batch transfer call"));
+    const logs = (call as TransactionResult).logs;
+    assertEventLog(
+      test,
+      logs[logs.length - 1],
+      {
+        standard: "nep171",
+        version: "1.0.0",
+        event: "nft_transfer",
+        data: [
+          {
+            authorized_id: null,
+            memo: "deposit",
+            new_owner_id: helper.accountId,
+            old_owner_id: alice.accountId,
+            token_ids: ["0"],
+          },
+        ],
+      },
+      "resolving batch transfer call"
+    );
+    const token0: any = await store.view("nft_token", { token_id: "0" });
+    test.is(token0.owner_id, helper.accountId);
+    const token1: any = await store.view("nft_token", { token_id: "1" });
+    test.is(token1.owner_id, alice.accountId);
+
+    // without one boolean per token, all tokens are returned
+    await alice
+      .call(
+        store,
+        "nft_batch_transfer_call",
+        {
+          receiver_id: helper.accountId,
+          token_ids: ["2", "3"],
+          msg: "[false]",
+        },
+        { attachedDeposit: "1", gas: Tgas(200) }
+      )
+      .catch(failPromiseRejection(test, "malformed batch transfer call"));
+    const token2: any = await store.view("nft_token", { token_id: "2" });
+    test.is(token2.owner_id, alice.accountId);
+    const token3: any = await store.view("nft_token", { token_id: "3" });
+    test.is(token3.owner_id, alice.accountId);
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_batch_transfer_call",
+            {
+              receiver_id: helper.accountId,
+              token_ids: ["2", "2"],
+              msg: "[false, false]",
+            },
+            { attachedDeposit: "1", gas: Tgas(200) }
+          );
+        },
+        "panicked at 'token locked'",
+        "Alice tried to batch transfer the same token twice",
+      ],
+    ]);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "gifts",
+  async (test, { alice, bob, store }) => {
+    const claim_pk = "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp";
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "gift_token",
+            { token_id: "0", claim_pk, memo: "x".repeat(257) },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'memo longer than 256 bytes'",
+        "Alice tried to gift a token with an oversized memo",
+      ],
+    ]);
+
+    const giftCall = await alice
+      .call_raw(
+        store,
+        "gift_token",
+        { token_id: "0", claim_pk, memo: "happy birthday" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "gifting"));
+    assertEventLogs(
+      test,
+      (giftCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_transfer",
+          data: [
+            {
+              authorized_id: null,
+              memo: "happy birthday",
+              new_owner_id: store.accountId,
+              old_owner_id: alice.accountId,
+              token_ids: ["0"],
+            },
+          ],
+        },
+      ],
+      "gifting"
+    );
+    test.deepEqual(await store.view("get_gift", { token_id: "0" }), {
+      sender_id: alice.accountId,
+      claim_pk,
+      memo: "happy birthday",
+    });
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "claim_gift",
+            {
+              token_id: "0",
+              receiver_id: bob.accountId,
+              signature: "A".repeat(86) + "==",
+            },
+            {}
+          );
+        },
+        "panicked at 'invalid signature'",
+        "Bob tried to claim a gift without the claim key",
+      ],
+      [
+        async () => {
+          await bob.call(
+            store,
+            "cancel_gift",
+            { token_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Bob tried to cancel Alice's gift",
+      ],
+    ]);
+
+    await alice
+      .call(store, "cancel_gift", { token_id: "0" }, { attachedDeposit: "1" })
+      .catch(failPromiseRejection(test, "cancelling gift"));
+    const token: any = await store.view("nft_token", { token_id: "0" });
+    test.is(token.owner_id, alice.accountId);
+    test.is(await store.view("get_gift", { token_id: "0" }), null);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "transfer-all",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 3 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    // frozen tokens are skipped
+    await alice
+      .call(
+        store,
+        "freeze_my_token",
+        { token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "freezing"));
+
+    const firstPage = await alice
+      .call(
+        store,
+        "nft_transfer_all",
+        { receiver_id: bob.accountId, limit: 2 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring first page"));
+    test.is(firstPage, "1");
+
+    const secondPage = await alice
+      .call(
+        store,
+        "nft_transfer_all",
+        { receiver_id: bob.accountId, limit: 2, from_index: firstPage },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring second page"));
+    test.is(secondPage, null);
+
+    const owners = await Promise.all(
+      ["0", "1", "2"].map(async (token_id) => {
+        const token: any = await store.view("nft_token", { token_id });
+        return token.owner_id;
+      })
+    );
+    test.deepEqual(owners, [alice.accountId, bob.accountId, bob.accountId]);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "transfer-fee",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: bob.accountId, metadata: {}, num_to_mint: 2 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    await alice
+      .call(
+        store,
+        "set_transfer_fee",
+        { transfer_fee: mNEAR(1).toString() },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "setting transfer fee"));
+    test.is(await store.view("get_transfer_fee"), mNEAR(1).toString());
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "nft_transfer",
+            { receiver_id: carol.accountId, token_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        `panicked at 'attached: 1; transfer fee: ${mNEAR(1)}'`,
+        "Bob tried to transfer without paying the transfer fee",
+      ],
+    ]);
+
+    await bob
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: carol.accountId, token_id: "0" },
+        { attachedDeposit: mNEAR(1) }
+      )
+      .catch(failPromiseRejection(test, "transferring with transfer fee"));
+    const token: any = await store.view("nft_token", { token_id: "0" });
+    test.is(token.owner_id, carol.accountId);
+
+    // batch transfers pay the fee per token, markets are exempt
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "nft_batch_transfer",
+            { token_ids: [["1", carol.accountId]] },
+            { attachedDeposit: "1" }
+          );
+        },
+        `panicked at 'attached: 1; transfer fee: ${mNEAR(1)}'`,
+        "Bob tried to batch transfer without paying the transfer fee",
+      ],
+    ]);
+    await carol
+      .call(
+        store,
+        "nft_approve",
+        { token_id: "0", account_id: bob.accountId },
+        { attachedDeposit: mNEAR(0.81) }
+      )
+      .catch(failPromiseRejection(test, "approving market"));
+    await bob
+      .call(
+        store,
+        "nft_transfer_payout",
+        {
+          receiver_id: alice.accountId,
+          token_id: "0",
+          approval_id: 0,
+          balance: mNEAR(100).toString(),
+          max_len_payout: 10,
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "selling with transfer fee"));
+    const sold: any = await store.view("nft_token", { token_id: "0" });
+    test.is(sold.owner_id, alice.accountId);
+
+    // disabling the fee requires one yoctoNEAR again
+    await alice
+      .call(
+        store,
+        "set_transfer_fee",
+        { transfer_fee: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "disabling transfer fee"));
+    await bob
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: carol.accountId, token_id: "1" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring without transfer fee"));
+  }
+);
+
+STORE_WORKSPACE.test(
+  "compose",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 3 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: bob.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting for Bob"));
+
+    const composeCall = await alice
+      .call_raw(
+        store,
+        "nft_compose",
+        { child_id: "1", parent_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "composing"));
+    assertEventLogs(
+      test,
+      (composeCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_compose",
+          data: JSON.stringify({
+            token_ids: ["1"],
+            parent: "0",
+            ttype: "t",
+            lroot: 0,
+            holder: alice.accountId,
+            depth: 1,
+          }),
+        },
+      ],
+      "composing"
+    );
+    await alice
+      .call(
+        store,
+        "nft_compose",
+        { child_id: "2", parent_id: "1" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "composing nested"));
+    const child: any = await store.view("nft_token", { token_id: "1" });
+    test.is(child.owner_id, "0");
+    test.is(await store.view("nft_holder", { token_id: "2" }), alice.accountId);
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_compose",
+            { child_id: "0", parent_id: "2" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'compose cycle'",
+        "Alice tried to compose a token into its descendant",
+      ],
+      [
+        async () => {
+          await bob.call(
+            store,
+            "nft_compose",
+            { child_id: "3", parent_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Bob tried to compose into Alice's token",
+      ],
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_transfer",
+            { receiver_id: bob.accountId, token_id: "1" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: self.nft_is_approved_internal(&token, env::predecessor_account_id(),",
+        "Alice tried to transfer a composed token",
+      ],
+    ]);
+
+    // composed tokens follow their parent
+    await alice
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: bob.accountId, token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring parent"));
+    test.is(await store.view("nft_holder", { token_id: "2" }), bob.accountId);
+
+    const uncomposeCall = await bob
+      .call_raw(
+        store,
+        "nft_uncompose",
+        { child_id: "1" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "uncomposing"));
+    assertEventLogs(
+      test,
+      (uncomposeCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_uncompose",
+          data: JSON.stringify({ token_ids: ["1"], holder: bob.accountId }),
+        },
+      ],
+      "uncomposing"
+    );
+    const uncomposed: any = await store.view("nft_token", { token_id: "1" });
+    test.is(uncomposed.owner_id, bob.accountId);
+    test.is(await store.view("nft_holder", { token_id: "2" }), bob.accountId);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "cross-compose",
+  async (test, { root, alice, bob, store }) => {
+    const parentStore = await root.createAndDeploy(
+      "parent",
+      "../wasm/store.wasm",
+      {
+        method: "new",
+        args: {
+          owner_id: alice.accountId,
+          metadata: { spec: "nft-1.0.0", name: "parent", symbol: "PRNT" },
+        },
+      }
+    );
+    // the helper doesn't implement `nft_token`, thus cannot verify parents
+    const helper = await root.createAndDeploy(
+      "helper",
+      "../wasm/helper.wasm",
+      { method: "new", args: {} }
+    );
+    for (const nft of [store, parentStore]) {
+      await alice
+        .call(
+          nft,
+          "nft_batch_mint",
+          { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+          { attachedDeposit: "1" }
+        )
+        .catch(failPromiseRejection(test, `minting on ${nft.accountId}`));
+    }
+
+    // failed verification rolls back to the previous owner
+    const rejected = await alice
+      .call(
+        store,
+        "nft_cross_compose",
+        { child_id: "0", parent_id: "0", parent_contract_id: helper.accountId },
+        { attachedDeposit: "1", gas: Tgas(100) }
+      )
+      .catch(failPromiseRejection(test, "composing into missing parent"));
+    test.false(rejected);
+    const kept: any = await store.view("nft_token", { token_id: "0" });
+    test.is(kept.owner_id, alice.accountId);
+
+    const composed = await alice
+      .call(
+        store,
+        "nft_cross_compose",
+        {
+          child_id: "0",
+          parent_id: "0",
+          parent_contract_id: parentStore.accountId,
+        },
+        { attachedDeposit: "1", gas: Tgas(100) }
+      )
+      .catch(failPromiseRejection(test, "cross-composing"));
+    test.true(composed);
+    const parentKey = `0:${parentStore.accountId}`;
+    test.is(await store.view("nft_holder", { token_id: "0" }), parentKey);
+
+    // only the owner of the parent may uncompose
+    const stolen = await bob
+      .call(
+        store,
+        "nft_cross_uncompose",
+        { child_id: "0" },
+        { attachedDeposit: "1", gas: Tgas(100) }
+      )
+      .catch(failPromiseRejection(test, "uncomposing as Bob"));
+    test.false(stolen);
+    test.is(await store.view("nft_holder", { token_id: "0" }), parentKey);
+
+    const uncomposed = await alice
+      .call(
+        store,
+        "nft_cross_uncompose",
+        { child_id: "0" },
+        { attachedDeposit: "1", gas: Tgas(100) }
+      )
+      .catch(failPromiseRejection(test, "uncomposing"));
+    test.true(uncomposed);
+    const token: any = await store.view("nft_token", { token_id: "0" });
+    test.is(token.owner_id, alice.accountId);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "compose-views",
+  async (test, { alice, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 4 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    for (const [child_id, parent_id] of [
+      ["2", "0"],
+      ["1", "0"],
+      ["3", "1"],
+    ]) {
+      await alice
+        .call(
+          store,
+          "nft_compose",
+          { child_id, parent_id },
+          { attachedDeposit: "1" }
+        )
+        .catch(failPromiseRejection(test, `composing ${child_id}`));
+    }
+
+    const children: any[] = await store.view("nft_token_children", {
+      token_id: "0",
+    });
+    test.deepEqual(
+      children.map((token) => [token.token_id, token.owner_id]),
+      [
+        ["1", "0"],
+        ["2", "0"],
+      ]
+    );
+    const page: any[] = await store.view("nft_token_children", {
+      token_id: "0",
+      from_index: "1",
+      limit: 1,
+    });
+    test.deepEqual(page.map((token) => token.token_id), ["2"]);
+    test.deepEqual(
+      await store.view("nft_token_children", { token_id: "3" }),
+      []
+    );
+
+    const root: any = await store.view("nft_token_root", { token_id: "3" });
+    test.is(root.token_id, "0");
+    test.is(root.owner_id, alice.accountId);
+    const topLevel: any = await store.view("nft_token_root", { token_id: "0" });
+    test.is(topLevel.token_id, "0");
+  }
+);
+
+STORE_WORKSPACE.test(
+  "compose-depth",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 3 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    test.is(await store.view("get_max_compose_depth"), 10);
+
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "set_max_compose_depth",
+            { max_compose_depth: 1 },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Bob tried to set the max compose depth",
+      ],
+      [
+        async () => {
+          await alice.call(
+            store,
+            "set_max_compose_depth",
+            { max_compose_depth: 11 },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'max_compose_depth must be <= 10'",
+        "Alice tried to exceed the hard compose depth limit",
+      ],
+    ]);
+    await alice
+      .call(
+        store,
+        "set_max_compose_depth",
+        { max_compose_depth: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "setting max compose depth"));
+    test.is(await store.view("get_max_compose_depth"), 1);
+
+    await alice
+      .call(
+        store,
+        "nft_compose",
+        { child_id: "2", parent_id: "1" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "composing"));
+    test.is(await store.view("nft_token_depth", { token_id: "1" }), 0);
+    test.is(await store.view("nft_token_depth", { token_id: "2" }), 1);
+
+    // composing token 1 would move token 2 to depth 2
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await alice.call(
+            store,
+            "nft_compose",
+            { child_id: "1", parent_id: "0" },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'compose depth exceeds 1'",
+        "Alice tried to compose deeper than allowed",
+      ],
+    ]);
+  }
+);
+
+STORE_WORKSPACE.test(
+  "compose-bundle-transfer",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 4 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    for (const [child_id, parent_id] of [
+      ["1", "0"],
+      ["2", "1"],
+      ["3", "0"],
+    ]) {
+      await alice
+        .call(
+          store,
+          "nft_compose",
+          { child_id, parent_id },
+          { attachedDeposit: "1" }
+        )
+        .catch(failPromiseRejection(test, `composing ${child_id}`));
+    }
+
+    const bundleCall = await alice
+      .call_raw(
+        store,
+        "nft_transfer_bundle",
+        { receiver_id: bob.accountId, token_id: "0", memo: "bundle" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring bundle"));
+    assertEventLogs(
+      test,
+      (bundleCall as TransactionResult).logs,
+      [
+        {
+          standard: "nep171",
+          version: "1.0.0",
+          event: "nft_transfer",
+          data: [
+            {
+              authorized_id: null,
+              memo: "bundle",
+              new_owner_id: bob.accountId,
+              old_owner_id: alice.accountId,
+              token_ids: ["0", "1", "2", "3"],
+            },
+          ],
+        },
+      ],
+      "transferring bundle"
+    );
+    for (const token_id of ["0", "1", "2", "3"]) {
+      test.is(await store.view("nft_holder", { token_id }), bob.accountId);
+    }
+  }
+);
+
+STORE_WORKSPACE.test(
+  "compose-consent",
+  async (test, { alice, bob, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting for Alice"));
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: bob.accountId, metadata: {}, num_to_mint: 1 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting for Bob"));
+
+    const composeBobsToken = async () => {
+      await alice.call(
+        store,
+        "nft_compose",
+        { child_id: "1", parent_id: "0" },
+        { attachedDeposit: "1" }
+      );
+    };
+    await assertContractPanics(test, [
+      [
+        composeBobsToken,
+        "panicked at 'composing requires approval of the child owner'",
+        "Alice tried to compose Bob's token without approval",
+      ],
+    ]);
+
+    // revoked approvals may not be used
+    await bob
+      .call(
+        store,
+        "nft_approve_compose",
+        { child_id: "1", parent_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "approving compose"));
+    const approved: any = await store.view("nft_token", { token_id: "1" });
+    test.is(approved.compose_approval, 0);
+    await bob
+      .call(
+        store,
+        "nft_revoke_compose",
+        { child_id: "1" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "revoking compose approval"));
+    await assertContractPanics(test, [
+      [
+        composeBobsToken,
+        "panicked at 'composing requires approval of the child owner'",
+        "Alice tried to compose Bob's token after revocation",
+      ],
+    ]);
+
+    await bob
+      .call(
+        store,
+        "nft_approve_compose",
+        { child_id: "1", parent_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "approving compose again"));
+    await composeBobsToken().catch(
+      failPromiseRejection(test, "composing Bob's token")
+    );
+    const composed: any = await store.view("nft_token", { token_id: "1" });
+    test.is(composed.owner_id, "0");
+    test.is(composed.compose_approval, null);
+    test.is(await store.view("nft_holder", { token_id: "1" }), alice.accountId);
+    test.deepEqual(
+      await store.view("nft_tokens_for_owner_set", {
+        account_id: alice.accountId,
+      }),
+      [0]
+    );
+  }
+);
+
+STORE_WORKSPACE.test(
+  "compose-payout",
+  async (test, { alice, bob, carol, store }) => {
+    await alice
+      .call(
+        store,
+        "nft_batch_mint",
+        { owner_id: alice.accountId, metadata: {}, num_to_mint: 3 },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "minting"));
+    for (const child_id of ["1", "2"]) {
+      await alice
+        .call(
+          store,
+          "nft_compose",
+          { child_id, parent_id: "0" },
+          { attachedDeposit: "1" }
+        )
+        .catch(failPromiseRejection(test, `composing token ${child_id}`));
+    }
+
+    // the holder lists composed tokens, Bob acts as market
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await carol.call(
+            store,
+            "nft_approve",
+            { token_id: "1", account_id: bob.accountId },
+            { attachedDeposit: mNEAR(0.81) }
+          );
+        },
+        "panicked at 'assertion failed: `(left == right)`",
+        "Carol tried to approve Alice's composed token",
+      ],
+    ]);
+    for (const token_id of ["1", "2"]) {
+      await alice
+        .call(
+          store,
+          "nft_approve",
+          { token_id, account_id: bob.accountId },
+          { attachedDeposit: mNEAR(0.81) }
+        )
+        .catch(failPromiseRejection(test, `approving token ${token_id}`));
+    }
+
+    const payout: any = await bob
+      .call(
+        store,
+        "nft_transfer_payout",
+        {
+          receiver_id: carol.accountId,
+          token_id: "1",
+          approval_id: 0,
+          balance: "10000000000",
+          max_len_payout: 10,
+        },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "selling composed token"));
+    test.deepEqual(payout.payout, { [alice.accountId]: "10000000000" });
+    const sold: any = await store.view("nft_token", { token_id: "1" });
+    test.is(sold.owner_id, carol.accountId);
+    test.deepEqual(
+      await store.view("nft_tokens_for_owner_set", {
+        account_id: carol.accountId,
+      }),
+      [1]
+    );
+
+    // listings of composed tokens end with their holder
+    await alice
+      .call(
+        store,
+        "nft_transfer",
+        { receiver_id: carol.accountId, token_id: "0" },
+        { attachedDeposit: "1" }
+      )
+      .catch(failPromiseRejection(test, "transferring parent"));
+    await assertContractPanics(test, [
+      [
+        async () => {
+          await bob.call(
+            store,
+            "nft_transfer_payout",
+            {
+              receiver_id: bob.accountId,
+              token_id: "2",
+              approval_id: 1,
+              balance: "10000000000",
+              max_len_payout: 10,
+            },
+            { attachedDeposit: "1" }
+          );
+        },
+        "panicked at 'assertion failed: self.nft_is_approved_internal(&token, env::predecessor_account_id(),",
+        "Bob tried to sell a composed token listed by its previous holder",
+      ],
+    ]);
+    const kept: any = await store.view("nft_token", { token_id: "2" });
+    test.is(kept.owner_id, "0");
+  }
+);
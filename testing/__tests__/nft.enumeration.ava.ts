@@ -106,8 +106,46 @@ STORE_WORKSPACE.test("enumeration", async (test, { alice, bob, store }) => {
     [{ token_id: "2", owner_id: bob.accountId }],
     "`nft_tokens_for_owner({ from_index, limit })` output is wrong"
   );
-});
 
-// TODO:
-// - [] test `nft_tokens_for_owner_set`, but only after syncing back wether it
-//      is used e.g. in mintbase-js, otherwise make it private
+  // call `nft_tokens_for_owner_set` for Bob, with and without pagination
+  test.deepEqual(
+    await store.view("nft_tokens_for_owner_set", { account_id: bob.accountId }),
+    [2, 3]
+  );
+  test.deepEqual(
+    await store.view("nft_tokens_for_owner_set", {
+      account_id: bob.accountId,
+      from_index: "1",
+      limit: 1,
+    }),
+    [3]
+  );
+
+  // token-gating: Alice owns tokens of lookup_id 0, Bob of lookup_id 2
+  test.true(
+    await store.view("owns_any", {
+      account_id: bob.accountId,
+      lookup_ids: [0, 2],
+    })
+  );
+  test.false(
+    await store.view("owns_any", {
+      account_id: alice.accountId,
+      lookup_ids: [2],
+    })
+  );
+  test.is(
+    await store.view("owned_count_for_metadata", {
+      account_id: alice.accountId,
+      lookup_id: 0,
+    }),
+    2
+  );
+  test.is(
+    await store.view("owned_count_for_metadata", {
+      account_id: bob.accountId,
+      lookup_id: 0,
+    }),
+    0
+  );
+});
@@ -1,3 +1,4 @@
+import { BN } from "near-workspaces-ava";
 import { batchMint, failPromiseRejection, STORE_WORKSPACE } from "./test-utils";
 
 STORE_WORKSPACE.test("metadata", async (test, { alice, store }) => {
@@ -47,6 +48,95 @@ STORE_WORKSPACE.test("metadata", async (test, { alice, store }) => {
     extra: "No more extras for you!",
   });
 
+  test.deepEqual(
+    await store.view("verify_media_hashes", {
+      entries: [
+        ["0", "bWVkaWE="],
+        ["1", "cmVmZXJlbmNl"],
+        ["1", "b3RoZXI="],
+        ["42", "bWVkaWE="],
+      ],
+    }),
+    [true, true, false, false]
+  );
+
+  test.deepEqual(await store.view("supported_standards"), [
+    { standard: "nep171", version: "1.0.0" },
+    { standard: "nep177", version: "1.0.0" },
+    { standard: "nep178", version: "1.0.0" },
+    { standard: "nep181", version: "1.0.0" },
+    { standard: "nep199", version: "2.0.0" },
+    { standard: "nep297", version: "1.0.0" },
+  ]);
+
+  // media hints are kept with the metadata
+  await alice
+    .call(
+      store,
+      "nft_batch_mint",
+      {
+        owner_id: alice.accountId,
+        metadata: {
+          media: "media",
+          media_size_bytes: 1024,
+          media_width: 640,
+          media_height: 480,
+          animation_url: "animation",
+        },
+        num_to_mint: 1,
+      },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting with media hints"));
+
+  test.like(await store.view("nft_token_metadata", { token_id: "2" }), {
+    media: "media",
+    media_size_bytes: 1024,
+    media_width: 640,
+    media_height: 480,
+    animation_url: "animation",
+  });
+
+  // minting tokens with distinct metadata
+  await alice
+    .call(
+      store,
+      "nft_batch_mint_distinct",
+      {
+        owner_id: alice.accountId,
+        metadata: [{ title: "First" }, { title: "Second" }],
+      },
+      { attachedDeposit: "1" }
+    )
+    .catch(failPromiseRejection(test, "minting distinct metadata"));
+
+  test.like(await store.view("nft_token_metadata", { token_id: "3" }), {
+    title: "First",
+    copies: 1,
+  });
+  test.like(await store.view("nft_token_metadata", { token_id: "4" }), {
+    title: "Second",
+    copies: 1,
+  });
+
+  // each additional copy costs the storage of a token and its owner entry
+  const requirements: any = await store.view("get_method_requirements");
+  const estimate = async (num_to_mint: number) =>
+    new BN(
+      await store.view("estimate_mint_cost", {
+        metadata: { title: "Yadda" },
+        num_to_mint,
+        roy_len: 0,
+        split_len: 1,
+      })
+    );
+  test.is(
+    (await estimate(2)).sub(await estimate(1)).toString(),
+    new BN(requirements.storage_token)
+      .add(new BN(requirements.storage_common))
+      .toString()
+  );
+
   // TODO::testing::low: deploying with icon/base URI
   // TODO::testing::low: changing icon/base URI
 });
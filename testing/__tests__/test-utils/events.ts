@@ -26,6 +26,20 @@ export function assertEventLog(
   test.deepEqual(event, expected, baseMsg);
 }
 
+/**
+ * Extracts the `mint_id` from the memo of an `nft_mint` event log. As the
+ * `mint_id` depends on the block height, it cannot be known upfront.
+ */
+export function getMintId(log: string): string {
+  return getMintMemo(log).mint_id;
+}
+
+/** Parses the memo of the first entry of a mint event */
+export function getMintMemo(log: string): any {
+  const event = JSON.parse(log.slice(12));
+  return JSON.parse(event.data[0].memo);
+}
+
 function parseEvent(test: ExecutionContext, log: string, msg: string) {
   // FIXME::contracts::medium: standard has no space between colon and JSON
   test.is(log.slice(0, 12), "EVENT_JSON: ", `${msg}: Not an event log`);